@@ -1,52 +1,280 @@
+from datetime import date
 from decimal import Decimal
-from typing import Any, Iterable, overload
+from fractions import Fraction
+from typing import IO, Any, Callable, Hashable, Iterable, Iterator, Literal, overload
+
+__version__: str
+MONEY_PRECISION: int
+DEFAULT_ROUNDING: str
+KNOWN_VAT_RATES: tuple[Decimal, ...]
+GERMAN_VAT_RATES: tuple[Decimal, ...]
 
 class Money:
     def __init__(
-        self, amount: Money | Decimal | float | int | str | None = None
+        self,
+        amount: Money | Decimal | float | int | str | None = None,
+        currency: Currency | str | None = None,
     ) -> None: ...
     @property
     def amount(self) -> Decimal: ...
-    def round(self, n: int) -> Money: ...
+    @property
+    def currency(self) -> Currency | None: ...
+    def round(
+        self, n: int | None = None, strategy: str = "ROUND_HALF_EVEN"
+    ) -> Money: ...
     def __str__(self) -> str: ...
+    def __format__(self, spec: str) -> str: ...
     def __repr__(self) -> str: ...
+    def __float__(self) -> float: ...
+    def __int__(self) -> int: ...
+    def __trunc__(self) -> int: ...
+    def format(
+        self, locale: str = "de_DE", symbol: bool = True, decimals: int | None = None
+    ) -> str: ...
+    @staticmethod
+    def parse(text: str, locale: str | None = None) -> Money: ...
     def __hash__(self) -> int: ...
     def __add__(self, other: Money | Decimal | float | int) -> Money: ...
     def __radd__(self, other: Money | Decimal | float | int) -> Money: ...
     def __sub__(self, other: Money | Decimal | float | int) -> Money: ...
     def __rsub__(self, other: Money | Decimal | float | int) -> Money: ...
-    def __mul__(self, other: Decimal | float | int) -> Money: ...
-    def __rmul__(self, other: Decimal | float | int) -> Money: ...
+    def abs_diff(self, other: Money) -> tuple[Money, Money]: ...
+    def copysign(self, other: Money | Decimal | float | int) -> Money: ...
+    def compare_total(self, other: Money | Decimal | float | int) -> Decimal: ...
+    def apply_vat(self, rate: Decimal | float | int | str) -> MoneyWithVAT: ...
+    def sort_key(self) -> tuple[int, int]: ...
+    def stable_hash(self) -> int: ...
+    def add_opt(self, other: Money | Decimal | float | int | None) -> Money: ...
+    def sub_opt(self, other: Money | Decimal | float | int | None) -> Money: ...
+    def __mul__(self, other: Fraction | Decimal | float | int) -> Money: ...
+    def __rmul__(self, other: Fraction | Decimal | float | int) -> Money: ...
     @overload
     def __truediv__(self, other: Money) -> Decimal: ...
     @overload
-    def __truediv__(self, other: Decimal | float | int) -> Money: ...
-    def __truediv__(self, other: Money | Decimal | float | int) -> Money | Decimal: ...
+    def __truediv__(self, other: Fraction | Decimal | float | int) -> Money: ...
+    def __truediv__(
+        self, other: Money | Fraction | Decimal | float | int
+    ) -> Money | Decimal: ...
     @overload
     def __rtruediv__(self, other: Money) -> Decimal: ...
     @overload
     def __rtruediv__(self, other: Decimal | float | int) -> Money: ...
     def __rtruediv__(self, other: Money | Decimal | float | int) -> Money | Decimal: ...
     def __neg__(self) -> Money: ...
+    def __pos__(self) -> Money: ...
     def __abs__(self) -> Money: ...
+    def __mod__(self, other: Money | Decimal | float | int) -> Money: ...
+    @overload
+    def __floordiv__(self, other: Money) -> Decimal: ...
+    @overload
+    def __floordiv__(self, other: Decimal | float | int) -> Money: ...
+    def __floordiv__(self, other: Money | Decimal | float | int) -> Money | Decimal: ...
+    def __rfloordiv__(self, other: Decimal | float | int) -> Money: ...
+    @overload
+    def __divmod__(self, other: Money) -> tuple[Decimal, Money]: ...
+    @overload
+    def __divmod__(self, other: Decimal | float | int) -> tuple[Money, Money]: ...
+    def __divmod__(
+        self, other: Money | Decimal | float | int
+    ) -> tuple[Money | Decimal, Money]: ...
     def __eq__(self, other: Money) -> bool: ...
     def __ne__(self, other: Money) -> bool: ...
-    def __lt__(self, other: Money) -> bool: ...
-    def __le__(self, other: Money) -> bool: ...
-    def __gt__(self, other: Money) -> bool: ...
-    def __ge__(self, other: Money) -> bool: ...
+    def __lt__(self, other: Money | Decimal | float | int) -> bool: ...
+    def __le__(self, other: Money | Decimal | float | int) -> bool: ...
+    def __gt__(self, other: Money | Decimal | float | int) -> bool: ...
+    def __ge__(self, other: Money | Decimal | float | int) -> bool: ...
     def __bool__(self) -> bool: ...
-    def for_json(self) -> str: ...
+    def __reduce__(self) -> tuple[type[Money], tuple[str, str | None]]: ...
+    def __class_getitem__(cls, currency: str) -> Any: ...
+    def __replace__(
+        self,
+        *,
+        amount: Money | Decimal | float | int | str = ...,
+        currency: Currency | str = ...,
+    ) -> Money: ...
+    def ratio_to(self, other: Money) -> Decimal: ...
+    @staticmethod
+    def variance(actual: Money, budget: Money) -> Money: ...
+    @staticmethod
+    def variance_pct(actual: Money, budget: Money) -> Decimal | None: ...
+    @staticmethod
+    def max(*args: object, default: Any = ...) -> Money: ...
+    @staticmethod
+    def min(*args: object, default: Any = ...) -> Money: ...
+    def prorate(
+        self,
+        period_start: date,
+        period_end: date,
+        sub_start: date,
+        sub_end: date,
+        day_count: Literal["act/365", "act/360", "30/360"] = "act/365",
+    ) -> Money: ...
+    def allocate(
+        self, ratios: Iterable[Decimal | float | int | str]
+    ) -> list[Money]: ...
+    def split_units(self) -> tuple[int, Money]: ...
+    def apply_indices(
+        self,
+        indices: Iterable[tuple[Any, Decimal | float | int]],
+        round_only_at_end: bool = False,
+    ) -> Money: ...
+    @staticmethod
+    def from_parts(mantissa: int, exponent: int) -> Money: ...
+    def to_parts(self) -> tuple[int, int]: ...
+    def as_decimal(self) -> Decimal: ...
+    def as_integer_ratio(self) -> tuple[int, int]: ...
+    def divide_with_remainder(
+        self, divisor: Decimal | float | int
+    ) -> tuple[Money, Money]: ...
+    def for_json(self) -> str | dict[str, str]: ...
+    def to_msgpack_bytes(self) -> bytes: ...
+    @staticmethod
+    def from_msgpack_bytes(data: bytes) -> Money: ...
+    def to_json_str(self) -> str: ...
+    @staticmethod
+    def to_json_str_batch(elems: Iterable[Money | None]) -> str: ...
     @staticmethod
-    def validate(value: Any, schema_info: Any) -> Money: ...
+    def from_json_str(text: str) -> Money: ...
+    @staticmethod
+    def from_json_str_batch(text: str) -> list[Money | None]: ...
+    def to_excel(self) -> tuple[float, bool, str]: ...
+    def to_datev(self) -> tuple[str, str]: ...
+    def to_sepa_amount(self) -> str: ...
+    @staticmethod
+    def from_rows(rows: Iterable[Any], column: int | str = 0) -> list[Money | None]: ...
+    @staticmethod
+    def to_excel_batch(
+        elems: Iterable[Money | None],
+    ) -> list[tuple[float, bool, str] | None]: ...
+    @staticmethod
+    def validate(
+        value: Any,
+        schema_info: Any = None,
+        locale: Literal["en", "de"] | None = None,
+    ) -> Money: ...
+
+@overload
+def sum_(
+    elems: Iterable[Money | None], per_currency: Literal[False] = False
+) -> Money: ...
+@overload
+def sum_(elems: Iterable[Money | None], per_currency: Literal[True]) -> MoneyBag: ...
+def sum_ndjson(
+    source: bytes | str | IO[bytes] | IO[str],
+) -> tuple[MoneyWithVAT, int]: ...
+@overload
+def average_(
+    elems: Iterable[Money | None], per_currency: Literal[False] = False
+) -> Money | None: ...
+@overload
+def average_(
+    elems: Iterable[MoneyWithVAT | None], per_currency: Literal[False] = False
+) -> MoneyWithVAT | None: ...
+@overload
+def average_(
+    elems: Iterable[Money | MoneyWithVAT | None], per_currency: Literal[True]
+) -> MoneyBag: ...
+def diffs(elems: Iterable[Money]) -> list[Money]: ...
+def set_money_division(mode: Literal["decimal", "raise"]) -> None: ...
+def set_negative_zero_policy(policy: Literal["preserve", "normalize"]) -> None: ...
+def set_pydantic_serialization(mode: Literal["json", "always"]) -> None: ...
+def get_config() -> dict[str, Any]: ...
+def set_stats(mode: Literal["enabled", "disabled"]) -> None: ...
+def get_stats() -> dict[str, int]: ...
+def reset_stats() -> None: ...
+def bucketize(
+    elems: Iterable[Money | None], boundaries: list[Money]
+) -> list[tuple[int, Money]]: ...
+def evaluate(
+    formula: str, variables: dict[str, Money | Decimal | float | int] | None = None
+) -> Money | Decimal: ...
+
+class MoneyError(ValueError): ...
+class SepaAmountError(MoneyError): ...
+class InvalidAmountError(MoneyError): ...
+class CurrencyMismatchError(MoneyError): ...
+class DivisionByZero(MoneyError, ZeroDivisionError): ...
+
+_Condition = Money | Decimal | float | int | Callable[[Money], Any] | Iterable[bool]
+
+class MoneyArray:
+    def __init__(
+        self,
+        elems: Iterable[Money | Decimal | float | int | str] = ...,
+        currency: Currency | str | None = None,
+    ) -> None: ...
+    @property
+    def currency(self) -> Currency | None: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[Money]: ...
+    @overload
+    def __getitem__(self, index: int) -> Money: ...
+    @overload
+    def __getitem__(self, index: slice) -> MoneyArray: ...
+    def __setitem__(
+        self, index: int, value: Money | Decimal | float | int | str
+    ) -> None: ...
+    def append(self, value: Money | Decimal | float | int | str) -> None: ...
+    def extend(self, elems: Iterable[Money | Decimal | float | int | str]) -> None: ...
+    def sum(self) -> Money: ...
+    def add(
+        self, other: MoneyArray | Money | Decimal | float | int | str
+    ) -> MoneyArray: ...
+    def mul_scalar(self, factor: Decimal | float | int | str) -> MoneyArray: ...
+    def cumsum(self) -> MoneyArray: ...
+    def round(self, n: int = 0) -> MoneyArray: ...
+    def round_to_cents(self, strategy: str = "ROUND_HALF_EVEN") -> MoneyArray: ...
+    def rounded_to_money_field_precision(
+        self, precision: int | None = None
+    ) -> MoneyArray: ...
+    def sum_where(self, condition: _Condition) -> Money: ...
+    def count_where(self, condition: _Condition) -> int: ...
+    def group_sum(self, labels: Iterable[Hashable]) -> dict[Any, Money]: ...
+    def tolist(self) -> list[Money]: ...
+
+class MoneyWithVATArray:
+    def __init__(
+        self,
+        elems: Iterable[MoneyWithVAT] = ...,
+        currency: Currency | str | None = None,
+    ) -> None: ...
+    @property
+    def currency(self) -> Currency | None: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[MoneyWithVAT]: ...
+    @overload
+    def __getitem__(self, index: int) -> MoneyWithVAT: ...
+    @overload
+    def __getitem__(self, index: slice) -> MoneyWithVATArray: ...
+    @property
+    def net(self) -> MoneyArray: ...
+    @property
+    def tax(self) -> MoneyArray: ...
+    def append(self, value: MoneyWithVAT) -> None: ...
+    def extend(self, elems: Iterable[MoneyWithVAT]) -> None: ...
+    def sum(self) -> MoneyWithVAT: ...
+    def add(self, other: MoneyWithVATArray | MoneyWithVAT) -> MoneyWithVATArray: ...
+    def mul_scalar(self, factor: Decimal | float | int | str) -> MoneyWithVATArray: ...
+    def cumsum(self) -> MoneyWithVATArray: ...
+    def group_by_rate(self) -> dict[Decimal, MoneyWithVAT]: ...
+    def tolist(self) -> list[MoneyWithVAT]: ...
 
-def sum_(elems: Iterable[Money | None]) -> Money: ...
+class MoneyExpr:
+    def __init__(self, start: Money) -> None: ...
+    def add(self, other: Money | Decimal | float | int) -> MoneyExpr: ...
+    def sub(self, other: Money | Decimal | float | int) -> MoneyExpr: ...
+    def mul(self, factor: Fraction | Decimal | float | int) -> MoneyExpr: ...
+    def div(self, divisor: Fraction | Decimal | float | int) -> MoneyExpr: ...
+    def materialize(self, n: int = 2) -> Money: ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
 
 class MoneyWithVAT:
     def __init__(
         self,
         net: Money | Decimal | float | int | str | None = None,
         tax: Money | Decimal | float | int | str | None = None,
+        currency: Currency | str | None = None,
     ) -> None: ...
     @property
     def net(self) -> Money: ...
@@ -54,45 +282,111 @@ class MoneyWithVAT:
     def tax(self) -> Money: ...
     @property
     def gross(self) -> Money: ...
+    def as_decimals(self) -> tuple[Decimal, Decimal, Decimal]: ...
+    @property
+    def currency(self) -> Currency | None: ...
+    def __reduce__(
+        self,
+    ) -> tuple[type[MoneyWithVAT], tuple[str, str, str | None]]: ...
+    def __class_getitem__(cls, currency: str) -> Any: ...
+    def __replace__(
+        self,
+        *,
+        net: Money | Decimal | float | int | str = ...,
+        tax: Money | Decimal | float | int | str = ...,
+        currency: Currency | str = ...,
+    ) -> MoneyWithVAT: ...
     @property
     def tax_rate(self) -> Decimal: ...
     @property
     def tax_rate_for_display(self) -> Decimal: ...
     @property
+    def tax_rate_of_gross(self) -> Decimal: ...
+    @property
+    def net_share(self) -> Decimal: ...
+    @property
+    def tax_share(self) -> Decimal: ...
+    @property
     def is_positive(self) -> bool: ...
     @property
     def is_negative(self) -> bool: ...
     def is_equal_up_to_cents(self, other: MoneyWithVAT) -> bool: ...
     def is_lower_up_to_cents(self, other: MoneyWithVAT) -> bool: ...
     def is_lower_or_equal_up_to_cents(self, other: MoneyWithVAT) -> bool: ...
-    def rounded_to_cents(self) -> MoneyWithVAT: ...
-    def rounded_to_money_field_precision(self) -> MoneyWithVAT: ...
+    def add_to_net(self, money: Money) -> MoneyWithVAT: ...
+    def allocate(
+        self, ratios: Iterable[Decimal | float | int | str]
+    ) -> list[MoneyWithVAT]: ...
+    def add_to_tax(self, money: Money) -> MoneyWithVAT: ...
+    def add_net_only(self, money: Money) -> MoneyWithVAT: ...
+    def with_tax_rate(
+        self, rate: Decimal | float | int, *, stable: Literal["net", "gross"] = "net"
+    ) -> MoneyWithVAT: ...
+    def redistribute(self, rate: Decimal | float | int) -> MoneyWithVAT: ...
+    @staticmethod
+    def from_net_and_rate(
+        net: Money | Decimal | float | int | str, rate: Decimal | float | int | str
+    ) -> MoneyWithVAT: ...
+    @staticmethod
+    def from_rows(rows: Iterable[Any]) -> list[MoneyWithVAT]: ...
+    @staticmethod
+    def from_gross_and_rate(
+        gross: Money | Decimal | float | int | str, rate: Decimal | float | int | str
+    ) -> MoneyWithVAT: ...
+    def scale_to_gross(self, target_gross: Money) -> MoneyWithVAT: ...
+    def rounded_to_cents(
+        self,
+        stable: Literal["gross", "net", "tax"] = "gross",
+        strategy: str = "ROUND_HALF_EVEN",
+    ) -> MoneyWithVAT: ...
+    def rounded_to_money_field_precision(
+        self, precision: int | None = None
+    ) -> MoneyWithVAT: ...
+    def convert(
+        self,
+        rate: Decimal | float | int,
+        round_before_sum: bool = False,
+        currency: Currency | str | None = None,
+    ) -> MoneyWithVAT: ...
     def __str__(self) -> str: ...
+    def __format__(self, spec: str) -> str: ...
     def __repr__(self) -> str: ...
     def __hash__(self) -> int: ...
     def __add__(self, other: MoneyWithVAT | Decimal | float | int) -> MoneyWithVAT: ...
     def __radd__(self, other: MoneyWithVAT | Decimal | float | int) -> MoneyWithVAT: ...
     def __sub__(self, other: MoneyWithVAT | Decimal | float | int) -> MoneyWithVAT: ...
     def __rsub__(self, other: MoneyWithVAT | Decimal | float | int) -> MoneyWithVAT: ...
+    @staticmethod
+    def variance(actual: MoneyWithVAT, budget: MoneyWithVAT) -> MoneyWithVAT: ...
+    @staticmethod
+    def variance_pct(actual: MoneyWithVAT, budget: MoneyWithVAT) -> Decimal | None: ...
+    def delta(self, other: MoneyWithVAT) -> tuple[MoneyWithVAT, MoneyWithVAT]: ...
+    def sort_key(self) -> tuple[int, int]: ...
+    def stable_hash(self) -> int: ...
+    def add_opt(self, other: MoneyWithVAT | int | None) -> MoneyWithVAT: ...
+    def sub_opt(self, other: MoneyWithVAT | int | None) -> MoneyWithVAT: ...
     def __mul__(
-        self, other: MoneyWithVATRatio | Decimal | float | int
+        self, other: MoneyWithVATRatio | Fraction | Decimal | float | int
     ) -> MoneyWithVAT: ...
     def __rmul__(
-        self, other: MoneyWithVATRatio | Decimal | float | int
+        self, other: MoneyWithVATRatio | Fraction | Decimal | float | int
     ) -> MoneyWithVAT: ...
-    def __truediv__(self, other: Decimal | float | int) -> MoneyWithVAT: ...
+    def __truediv__(self, other: Fraction | Decimal | float | int) -> MoneyWithVAT: ...
     def __rtruediv__(self, other: Decimal | float | int) -> MoneyWithVAT: ...
     def __neg__(self) -> MoneyWithVAT: ...
+    def __pos__(self) -> MoneyWithVAT: ...
     def __abs__(self) -> MoneyWithVAT: ...
-    def __eq__(self, other: MoneyWithVAT) -> bool: ...
-    def __ne__(self, other: MoneyWithVAT) -> bool: ...
-    def __lt__(self, other: MoneyWithVAT) -> bool: ...
-    def __le__(self, other: MoneyWithVAT) -> bool: ...
-    def __gt__(self, other: MoneyWithVAT) -> bool: ...
-    def __ge__(self, other: MoneyWithVAT) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __lt__(self, other: MoneyWithVAT | Money | Decimal | float | int) -> bool: ...
+    def __le__(self, other: MoneyWithVAT | Money | Decimal | float | int) -> bool: ...
+    def __gt__(self, other: MoneyWithVAT | Money | Decimal | float | int) -> bool: ...
+    def __ge__(self, other: MoneyWithVAT | Money | Decimal | float | int) -> bool: ...
     def __bool__(self) -> bool: ...
     @staticmethod
-    def max(*args: object) -> MoneyWithVAT: ...
+    def max(*args: object, default: Any = ...) -> MoneyWithVAT: ...
+    @staticmethod
+    def min(*args: object, default: Any = ...) -> MoneyWithVAT: ...
     @staticmethod
     def ratio(dividend: MoneyWithVAT, divisor: MoneyWithVAT) -> MoneyWithVATRatio: ...
     @staticmethod
@@ -104,21 +398,59 @@ class MoneyWithVAT:
         dividend: MoneyWithVAT | None,
         divisor: Decimal | None,
     ) -> MoneyWithVAT | None: ...
+    @overload
+    @staticmethod
+    def fast_sum(
+        iterable: Iterable[Any],
+        key: str | Callable[[Any], Any] | None = None,
+        per_currency: Literal[False] = False,
+    ) -> MoneyWithVAT: ...
+    @overload
     @staticmethod
-    def fast_sum(iterable: Iterable[MoneyWithVAT | None]) -> MoneyWithVAT: ...
+    def fast_sum(
+        iterable: Iterable[Any],
+        key: str | Callable[[Any], Any] | None = None,
+        *,
+        per_currency: Literal[True],
+    ) -> MoneyBag: ...
     @staticmethod
     def fast_sum_with_none(
-        iterable: Iterable[MoneyWithVAT | None],
+        iterable: Iterable[Any], key: str | Callable[[Any], Any] | None = None
     ) -> MoneyWithVAT | None: ...
-    def for_json(self) -> dict: ...
+    @staticmethod
+    def fast_sum_with_breakdown(
+        iterable: Iterable[Any], key: str | Callable[[Any], Any] | None = None
+    ) -> TaxBreakdown: ...
+    @staticmethod
+    def fast_sum_rounded(
+        iterable: Iterable[Any],
+        policy: VATRoundingPolicy,
+        key: str | Callable[[Any], Any] | None = None,
+    ) -> MoneyWithVAT: ...
+    def to_datev_booking(self, input_tax: bool = True) -> dict[str, str]: ...
+    def for_json(self, with_schema_version: bool = False) -> dict: ...
+    def to_msgpack_bytes(self) -> bytes: ...
+    @staticmethod
+    def from_msgpack_bytes(data: bytes) -> MoneyWithVAT: ...
+    def to_json_str(self) -> str: ...
+    @staticmethod
+    def to_json_str_batch(elems: Iterable[MoneyWithVAT | None]) -> str: ...
+    @staticmethod
+    def from_json_str(text: str) -> MoneyWithVAT: ...
+    @staticmethod
+    def from_json_str_batch(text: str) -> list[MoneyWithVAT | None]: ...
     @staticmethod
     def from_json(dict: dict) -> MoneyWithVAT: ...
     @staticmethod
-    def validate(value: Any, schema_info: Any) -> MoneyWithVAT: ...
+    def validate(
+        value: Any,
+        schema_info: Any = None,
+        locale: Literal["en", "de"] | None = None,
+    ) -> MoneyWithVAT: ...
     @staticmethod
     def german_vat_rates() -> list[Decimal]: ...
     @staticmethod
-    def known_vat_rates() -> list[Decimal]: ...
+    def known_vat_rates(country: Country | None = None) -> list[Decimal]: ...
 
 class MoneyWithVATRatio:
     def __init__(
@@ -132,12 +464,229 @@ class MoneyWithVATRatio:
     def zero() -> MoneyWithVATRatio: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
+    def __reduce__(self) -> tuple[type[MoneyWithVATRatio], tuple[str, str]]: ...
     def __neg__(self) -> MoneyWithVATRatio: ...
     def __add__(self, other: MoneyWithVATRatio) -> MoneyWithVATRatio: ...
     def __sub__(self, other: MoneyWithVATRatio) -> MoneyWithVATRatio: ...
     def __mul__(self, other: Decimal | float | int) -> MoneyWithVATRatio: ...
     def __truediv__(self, other: Decimal | float | int) -> MoneyWithVATRatio: ...
     def __eq__(self, other: MoneyWithVATRatio) -> bool: ...
+    def __replace__(
+        self,
+        *,
+        net_ratio: Decimal | float | int = ...,
+        gross_ratio: Decimal | float | int = ...,
+    ) -> MoneyWithVATRatio: ...
     def for_json(self) -> dict: ...
+    def to_json_str(self) -> str: ...
+    @staticmethod
+    def to_json_str_batch(elems: Iterable[MoneyWithVATRatio | None]) -> str: ...
+    @staticmethod
+    def from_json_str(text: str) -> MoneyWithVATRatio: ...
+    @staticmethod
+    def from_json_str_batch(text: str) -> list[MoneyWithVATRatio | None]: ...
     @staticmethod
     def validate(value: Any, schema_info: Any) -> MoneyWithVATRatio: ...
+
+class VATRoundingPolicy:
+    def __init__(
+        self,
+        level: Literal["line", "category", "document"] = "category",
+        adjust: Literal["net", "tax", "gross"] = "gross",
+    ) -> None: ...
+    @property
+    def level(self) -> str: ...
+    @property
+    def adjust(self) -> str: ...
+    def __repr__(self) -> str: ...
+
+class InvoiceTotals:
+    @staticmethod
+    def en16931(
+        lines: Iterable[MoneyWithVAT], prepaid: Money | None = None
+    ) -> InvoiceTotals: ...
+    @staticmethod
+    def from_lines(
+        lines: Iterable[MoneyWithVAT],
+        policy: VATRoundingPolicy | None = None,
+        prepaid: Money | None = None,
+    ) -> InvoiceTotals: ...
+    @property
+    def total(self) -> MoneyWithVAT: ...
+    @property
+    def prepaid(self) -> Money: ...
+    @property
+    def payable(self) -> Money: ...
+    @property
+    def subtotals(self) -> dict[Decimal, MoneyWithVAT]: ...
+    def __repr__(self) -> str: ...
+
+class TaxBreakdown:
+    @property
+    def total(self) -> MoneyWithVAT: ...
+    @property
+    def buckets(self) -> dict[Decimal, MoneyWithVAT]: ...
+    @property
+    def rates(self) -> list[Decimal]: ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+class MoneyBag:
+    def __len__(self) -> int: ...
+    def __getitem__(self, currency: Currency | str | None) -> Money | MoneyWithVAT: ...
+    def __contains__(self, currency: Currency | str | None) -> bool: ...
+    def __iter__(self) -> Iterator[Currency | None]: ...
+    def keys(self) -> list[Currency | None]: ...
+    def values(self) -> list[Money | MoneyWithVAT]: ...
+    def items(self) -> list[tuple[Currency | None, Money | MoneyWithVAT]]: ...
+    def __repr__(self) -> str: ...
+
+class Country:
+    AT: Country
+    BE: Country
+    BG: Country
+    CH: Country
+    CY: Country
+    CZ: Country
+    DE: Country
+    DK: Country
+    EE: Country
+    ES: Country
+    FI: Country
+    FR: Country
+    GB: Country
+    GR: Country
+    HR: Country
+    HU: Country
+    IE: Country
+    IT: Country
+    LT: Country
+    LU: Country
+    LV: Country
+    MT: Country
+    NL: Country
+    NO: Country
+    PL: Country
+    PT: Country
+    RO: Country
+    SE: Country
+    SI: Country
+    SK: Country
+    @staticmethod
+    def from_code(code: str) -> Country: ...
+    @staticmethod
+    def all() -> list[Country]: ...
+    @property
+    def code(self) -> str: ...
+    @property
+    def is_eu(self) -> bool: ...
+    @property
+    def currency(self) -> str: ...
+    @property
+    def standard_rate(self) -> Decimal: ...
+    @property
+    def reduced_rates(self) -> list[Decimal]: ...
+    def classify_rate(
+        self, rate: Decimal | float | int | str
+    ) -> Literal["standard", "reduced", "zero", "unknown"]: ...
+    def vat_rates(self) -> list[Decimal]: ...
+
+class Currency:
+    BGN: Currency
+    CHF: Currency
+    CZK: Currency
+    DKK: Currency
+    EUR: Currency
+    GBP: Currency
+    HUF: Currency
+    JPY: Currency
+    NOK: Currency
+    PLN: Currency
+    RON: Currency
+    SEK: Currency
+    USD: Currency
+    @staticmethod
+    def from_code(code: str) -> Currency: ...
+    @staticmethod
+    def all() -> list[Currency]: ...
+    @property
+    def code(self) -> str: ...
+    @property
+    def exponent(self) -> int: ...
+    @property
+    def symbol(self) -> str: ...
+
+class CurrencyConstraint:
+    @property
+    def currency(self) -> Currency: ...
+    def __get_pydantic_core_schema__(self, source: Any, handler: Any) -> Any: ...
+
+def default_vat_rate_for_vat_id(vat_id: str) -> tuple[Decimal, bool]: ...
+def as_money(value: Any) -> Money: ...
+def as_money_with_vat(value: Any) -> MoneyWithVAT: ...
+def register_cattrs_hooks(converter: Any) -> None: ...
+def validate_money(instance: Any, attribute: Any, value: Any) -> None: ...
+def validate_money_with_vat(instance: Any, attribute: Any, value: Any) -> None: ...
+
+class MoneyBuilder:
+    def __init__(
+        self,
+        amount: Money | Decimal | float | int | str | None = None,
+        currency: Currency | str | None = None,
+    ) -> None: ...
+    @property
+    def amount(self) -> Decimal: ...
+    @amount.setter
+    def amount(self, amount: Money | Decimal | float | int | str) -> None: ...
+    @property
+    def currency(self) -> Currency | None: ...
+    def add(self, amount: Money | Decimal | float | int | str) -> MoneyBuilder: ...
+    def build(self) -> Money: ...
+    def __repr__(self) -> str: ...
+
+class MoneyWithVATBuilder:
+    def __init__(
+        self,
+        net: Money | Decimal | float | int | str | None = None,
+        tax: Money | Decimal | float | int | str | None = None,
+        currency: Currency | str | None = None,
+    ) -> None: ...
+    @property
+    def net(self) -> Money: ...
+    @net.setter
+    def net(self, net: Money | Decimal | float | int | str) -> None: ...
+    @property
+    def tax(self) -> Money: ...
+    @tax.setter
+    def tax(self, tax: Money | Decimal | float | int | str) -> None: ...
+    @property
+    def currency(self) -> Currency | None: ...
+    def add(self, value: MoneyWithVAT) -> MoneyWithVATBuilder: ...
+    def build(self) -> MoneyWithVAT: ...
+    def __repr__(self) -> str: ...
+def amortization_schedule(
+    principal: Money, rate: Decimal | float | int, periods: int
+) -> list[dict[str, Any]]: ...
+def annuity(principal: Money, rate: Decimal | float | int, periods: int) -> Money: ...
+def discount(
+    money: Money, rate: Decimal | float | int, periods: Decimal | float | int
+) -> Money: ...
+def ecb_reference_rates(data: str | bytes) -> dict[Currency, Decimal]: ...
+def fx_gain_loss(
+    original: Money,
+    booked_rate: Decimal | float | int,
+    settlement_rate: Decimal | float | int,
+) -> tuple[Money, Literal["gain", "loss", "none"]]: ...
+def late_payment_interest(
+    principal: Money,
+    start: date,
+    end: date,
+    spread: Decimal | float | int | None = None,
+    base_rates: Iterable[tuple[date, Decimal | float | int]] | None = None,
+    day_count: Literal["act/365", "act/360", "30/360"] = "act/365",
+) -> tuple[list[dict[str, Any]], Money]: ...
+def npv(
+    rate: Decimal | float | int,
+    cash_flows: Iterable[tuple[date | Decimal | int, Money]],
+    day_count: Literal["act/365", "act/360", "30/360"] = "act/365",
+) -> Money: ...
@@ -0,0 +1,39 @@
+"""Construction heavy workloads, run with `python benchmarks/construction.py`."""
+
+import timeit
+from decimal import Decimal
+
+import alasco_money as _money
+
+NUMBER = 100_000
+
+DECIMALS = [Decimal(i) / 100 for i in range(1000)]
+STRINGS = [str(value) for value in DECIMALS]
+FLOATS = [float(value) for value in DECIMALS]
+SCIENTIFIC = [f"{value:e}" for value in DECIMALS]
+
+CASES = {
+    "Money(Decimal)": lambda: [_money.Money(value) for value in DECIMALS],
+    "Money(str)": lambda: [_money.Money(value) for value in STRINGS],
+    "Money(int)": lambda: [_money.Money(value) for value in range(1000)],
+    "Money(float)": lambda: [_money.Money(value) for value in FLOATS],
+    "Money(scientific str)": lambda: [_money.Money(value) for value in SCIENTIFIC],
+    "MoneyWithVAT(Decimal, Decimal)": lambda: [
+        _money.MoneyWithVAT(value, value) for value in DECIMALS
+    ],
+    "Money * Decimal": lambda: [_money.Money(1) * value for value in DECIMALS],
+    "Money.amount": lambda: [
+        money.amount for money in map(_money.Money, range(1000))
+    ],
+}
+
+
+def main():
+    for name, case in CASES.items():
+        repeat = NUMBER // 1000
+        seconds = min(timeit.repeat(case, number=repeat, repeat=5))
+        print(f"{name:32} {seconds / NUMBER * 1e9:8.1f} ns per element")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,83 @@
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+@_pytest.mark.parametrize(
+    "value, attribute",
+    [
+        (_money.Money(1), "amount"),
+        (_money.Money(1), "currency"),
+        (_money.MoneyWithVAT(1, 2), "net"),
+        (_money.MoneyWithVAT(1, 2), "gross"),
+    ],
+)
+def test_frozen(value, attribute):
+    with _pytest.raises(AttributeError):
+        setattr(value, attribute, _money.Money(5))
+
+
+def test_money_builder():
+    builder = _money.MoneyBuilder()
+    assert builder.build() == _money.Money(0)
+
+    builder.amount = "1.50"
+    builder.add(2).add(_money.Money("0.25"))
+
+    assert builder.amount == _decimal.Decimal("3.75")
+    assert repr(builder) == "MoneyBuilder(amount='3.75')"
+
+    money = builder.build()
+    builder.add(1)
+    assert money == _money.Money("3.75")
+    assert money in {_money.Money("3.75")}
+
+    with _pytest.raises(ValueError):
+        builder.amount = "abc"
+
+
+def test_money_with_vat_builder():
+    builder = _money.MoneyWithVATBuilder(net=100)
+    builder.tax = "19"
+    builder.add(_money.MoneyWithVAT(10, "1.9"))
+
+    result = builder.build()
+    assert (result.net, result.tax) == (_money.Money(110), _money.Money("20.9"))
+    assert (builder.net, builder.tax) == (result.net, result.tax)
+    assert repr(builder) == "MoneyWithVATBuilder(net='110', tax='20.9')"
+
+    with _pytest.raises(TypeError):
+        builder.add(_money.Money(1))
+
+
+def test_builders_keep_the_currency():
+    builder = _money.MoneyBuilder("1.50", "EUR").add(_money.Money(1))
+    assert builder.build().currency == _money.Currency.EUR
+    assert repr(builder) == "MoneyBuilder(amount='2.50', currency='EUR')"
+
+    vat_builder = _money.MoneyWithVATBuilder(net=_money.Money(100, "USD"))
+    vat_builder.add(_money.MoneyWithVAT(10, 1))
+    assert vat_builder.build().currency == _money.Currency.USD
+    assert vat_builder.tax.currency == _money.Currency.USD
+    assert repr(vat_builder) == (
+        "MoneyWithVATBuilder(net='110', tax='1', currency='USD')"
+    )
+
+
+@_pytest.mark.parametrize(
+    "action",
+    [
+        lambda: _money.MoneyBuilder(1, "EUR").add(_money.Money(1, "USD")),
+        lambda: setattr(
+            _money.MoneyBuilder(1, "EUR"), "amount", _money.Money(1, "USD")
+        ),
+        lambda: _money.MoneyWithVATBuilder(1, _money.Money(0, "USD"), "EUR"),
+        lambda: _money.MoneyWithVATBuilder(currency="EUR").add(
+            _money.MoneyWithVAT(1, 0, "USD")
+        ),
+    ],
+)
+def test_builders_currency_mismatch(action):
+    with _pytest.raises(_money.CurrencyMismatchError):
+        action()
@@ -0,0 +1,72 @@
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_module_constants():
+    assert _money.MONEY_PRECISION == 12
+    assert _money.DEFAULT_ROUNDING == _decimal.ROUND_HALF_EVEN
+    assert _money.KNOWN_VAT_RATES == tuple(_money.MoneyWithVAT.known_vat_rates())
+    assert _money.GERMAN_VAT_RATES == tuple(_money.MoneyWithVAT.german_vat_rates())
+    assert _decimal.Decimal("0.19") in _money.GERMAN_VAT_RATES
+    assert isinstance(_money.__version__, str)
+
+
+def test_get_config():
+    config = _money.get_config()
+
+    assert config == {
+        "version": _money.__version__,
+        "money_precision": 12,
+        "default_rounding": "ROUND_HALF_EVEN",
+        "known_vat_rates": list(_money.KNOWN_VAT_RATES),
+        "german_vat_rates": list(_money.GERMAN_VAT_RATES),
+        "money_division": "decimal",
+        "pydantic_serialization": "json",
+        "stats": "disabled",
+        "negative_zero_policy": "preserve",
+    }
+
+
+def test_get_config_runtime_settings():
+    _money.set_money_division("raise")
+    _money.set_negative_zero_policy("normalize")
+    try:
+        config = _money.get_config()
+    finally:
+        _money.set_money_division("decimal")
+        _money.set_negative_zero_policy("preserve")
+
+    assert config["money_division"] == "raise"
+    assert config["negative_zero_policy"] == "normalize"
+
+
+def test_stats():
+    _money.reset_stats()
+    _money.Money(1) + _money.Money(2)
+    assert _money.get_stats() == {"constructions": 0, "operations": 0, "roundings": 0}
+
+    _money.set_stats("enabled")
+    try:
+        assert _money.get_config()["stats"] == "enabled"
+
+        total = _money.Money(1) + _money.Money(2)
+        total = 1 - total
+        total.round(2)
+        _money.MoneyWithVAT(100, 19).rounded_to_cents()
+        stats = _money.get_stats()
+    finally:
+        _money.set_stats("disabled")
+
+    assert stats == {"constructions": 5, "operations": 2, "roundings": 3}
+
+    total + total
+    assert _money.get_stats() == stats
+    _money.reset_stats()
+    assert _money.get_stats() == {"constructions": 0, "operations": 0, "roundings": 0}
+
+
+def test_stats_invalid_mode():
+    with _pytest.raises(ValueError, match="Unknown stats mode"):
+        _money.set_stats("on")
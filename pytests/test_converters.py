@@ -0,0 +1,107 @@
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+@_pytest.mark.parametrize(
+    "value", [123, "123.00000", _decimal.Decimal("123"), _money.Money(123)]
+)
+def test_as_money(value):
+    assert _money.as_money(value) == _money.Money(123)
+
+
+@_pytest.mark.parametrize("value", ["a", None, {"amount": "1"}])
+def test_as_money_fail(value):
+    with _pytest.raises(ValueError):
+        _money.as_money(value)
+
+
+@_pytest.mark.parametrize(
+    "value",
+    [
+        {"net": 100, "tax": 19},
+        {"net": "100.00", "tax": _decimal.Decimal("19")},
+        _money.MoneyWithVAT(100, 19),
+    ],
+)
+def test_as_money_with_vat(value):
+    result = _money.as_money_with_vat(value)
+    assert result.net == _money.Money(100)
+    assert result.tax == _money.Money(19)
+
+
+@_pytest.mark.parametrize("value", [100, {"net": 100}, {"net": "a", "tax": 19}])
+def test_as_money_with_vat_fail(value):
+    with _pytest.raises(ValueError):
+        _money.as_money_with_vat(value)
+
+
+@_pytest.mark.parametrize("value", [_money.Money(123), "123", 123])
+def test_validate_money(value):
+    assert _money.validate_money(None, None, value) is None
+
+
+@_pytest.mark.parametrize("value", ["a", None, {"amount": "1"}])
+def test_validate_money_fail(value):
+    with _pytest.raises(ValueError):
+        _money.validate_money(None, None, value)
+
+
+@_pytest.mark.parametrize(
+    "value", [_money.MoneyWithVAT(100, 19), {"net": 100, "tax": 19}]
+)
+def test_validate_money_with_vat(value):
+    assert _money.validate_money_with_vat(None, None, value) is None
+
+
+@_pytest.mark.parametrize("value", [100, {"net": 100}, {"net": "a", "tax": 19}])
+def test_validate_money_with_vat_fail(value):
+    with _pytest.raises(ValueError):
+        _money.validate_money_with_vat(None, None, value)
+
+
+class _RecordingConverter:
+    """Stands in for a cattrs converter, keeping the registered hooks by class"""
+
+    def __init__(self):
+        self.structure_hooks = {}
+        self.unstructure_hooks = {}
+
+    def register_structure_hook(self, cls, hook):
+        self.structure_hooks[cls] = hook
+
+    def register_unstructure_hook(self, cls, hook):
+        self.unstructure_hooks[cls] = hook
+
+
+def test_register_cattrs_hooks():
+    converter = _RecordingConverter()
+    _money.register_cattrs_hooks(converter)
+
+    structure = converter.structure_hooks
+    unstructure = converter.unstructure_hooks
+
+    assert structure[_money.Money]("12.5", _money.Money) == _money.Money("12.5")
+    assert unstructure[_money.Money](_money.Money("12.5")) == "12.500000000000"
+
+    money_with_vat = structure[_money.MoneyWithVAT](
+        {"net": "100", "tax": "19"}, _money.MoneyWithVAT
+    )
+    assert money_with_vat == _money.MoneyWithVAT(100, 19)
+    assert unstructure[_money.MoneyWithVAT](money_with_vat) == {
+        "net": "100.000000000000",
+        "tax": "19.000000000000",
+    }
+
+    ratio = structure[_money.MoneyWithVATRatio](
+        {"net_ratio": "0.5", "gross_ratio": "0.6"}, _money.MoneyWithVATRatio
+    )
+    assert ratio == _money.MoneyWithVATRatio("0.5", "0.6")
+    assert unstructure[_money.MoneyWithVATRatio](ratio) == {
+        "net_ratio": "0.5",
+        "gross_ratio": "0.6",
+    }
+
+    with _pytest.raises(ValueError):
+        structure[_money.Money]("a", _money.Money)
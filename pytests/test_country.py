@@ -0,0 +1,112 @@
+from decimal import Decimal as _Decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_germany():
+    subject = _money.Country.DE
+
+    assert subject.code == "DE"
+    assert subject.is_eu
+    assert subject.currency == "EUR"
+    assert subject.standard_rate == _Decimal("0.19")
+    assert subject.reduced_rates == [_Decimal("0.07")]
+    assert subject.vat_rates() == [_Decimal("0"), _Decimal("0.07"), _Decimal("0.19")]
+
+
+@_pytest.mark.parametrize(
+    "code, expected",
+    [("DE", _money.Country.DE), ("at", _money.Country.AT), (" ch ", _money.Country.CH)],
+)
+def test_from_code(code, expected):
+    assert _money.Country.from_code(code) == expected
+
+
+def test_from_code_unknown():
+    with _pytest.raises(ValueError, match="Unknown country"):
+        _money.Country.from_code("XX")
+
+
+def test_all():
+    countries = _money.Country.all()
+
+    assert len(countries) == 30
+    assert [country.code for country in countries] == sorted(
+        country.code for country in countries
+    )
+    assert sum(country.is_eu for country in countries) == 27
+    assert len(set(countries)) == 30
+
+
+def test_rates_are_fractions():
+    for country in _money.Country.all():
+        rates = country.vat_rates()
+        assert rates == sorted(rates)
+        assert all(_Decimal(0) <= rate < 1 for rate in rates)
+
+
+def test_non_eu():
+    assert not _money.Country.CH.is_eu
+    assert _money.Country.CH.currency == "CHF"
+    assert _money.Country.CH.standard_rate == _Decimal("0.081")
+    assert _money.Country.FI.standard_rate == _Decimal("0.255")
+
+
+def test_known_vat_rates_for_country():
+    assert _money.MoneyWithVAT.known_vat_rates(_money.Country.AT) == [
+        _Decimal("0"),
+        _Decimal("0.1"),
+        _Decimal("0.13"),
+        _Decimal("0.2"),
+    ]
+    assert len(_money.MoneyWithVAT.known_vat_rates()) == 9
+
+
+@_pytest.mark.parametrize(
+    "vat_id, expected",
+    [
+        ("ATU12345678", (_Decimal("0.2"), True)),
+        ("DE 123 456 789", (_Decimal("0.19"), True)),
+        ("el123456789", (_Decimal("0.24"), True)),
+        ("CHE-123.456.789 MWST", (_Decimal("0.081"), False)),
+        ("GB123456789", (_Decimal("0.2"), False)),
+    ],
+)
+def test_default_vat_rate_for_vat_id(vat_id, expected):
+    assert _money.default_vat_rate_for_vat_id(vat_id) == expected
+
+
+@_pytest.mark.parametrize("vat_id", ["", "1234", "XX123", "A"])
+def test_default_vat_rate_for_vat_id_invalid(vat_id):
+    with _pytest.raises(ValueError):
+        _money.default_vat_rate_for_vat_id(vat_id)
+
+
+@_pytest.mark.parametrize(
+    "rate, expected",
+    [
+        (_Decimal("0.19"), "standard"),
+        (_Decimal("0.1900"), "standard"),
+        (19, "standard"),
+        ("0.07", "reduced"),
+        (7, "reduced"),
+        (0, "zero"),
+        (_Decimal("0.00"), "zero"),
+        (_Decimal("0.16"), "unknown"),
+        (_Decimal("0.2"), "unknown"),
+    ],
+)
+def test_classify_rate(rate, expected):
+    assert _money.Country.DE.classify_rate(rate) == expected
+
+
+def test_classify_rate_other_country():
+    assert _money.Country.FR.classify_rate(_Decimal("0.055")) == "reduced"
+    assert _money.Country.FR.classify_rate(_Decimal("0.2")) == "standard"
+    assert _money.Country.DK.classify_rate(_Decimal("0.07")) == "unknown"
+
+
+def test_classify_rate_invalid():
+    with _pytest.raises(ValueError):
+        _money.Country.DE.classify_rate("abc")
@@ -0,0 +1,225 @@
+from decimal import Decimal as _Decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_currency():
+    subject = _money.Currency.JPY
+
+    assert subject.code == "JPY"
+    assert subject.exponent == 0
+    assert subject.symbol == "¥"
+    assert _money.Currency.EUR.exponent == 2
+
+
+@_pytest.mark.parametrize(
+    "code, expected",
+    [
+        ("EUR", _money.Currency.EUR),
+        ("chf", _money.Currency.CHF),
+        (" usd ", _money.Currency.USD),
+    ],
+)
+def test_from_code(code, expected):
+    assert _money.Currency.from_code(code) == expected
+
+
+def test_from_code_unknown():
+    with _pytest.raises(ValueError, match="Unknown currency"):
+        _money.Currency.from_code("XXX")
+
+
+def test_all_currencies_of_countries_are_known():
+    for country in _money.Country.all():
+        assert _money.Currency.from_code(country.currency).code == country.currency
+
+
+def test_money_currency():
+    assert _money.Money(1).currency is None
+    assert _money.Money(1, "EUR").currency == _money.Currency.EUR
+    assert _money.Money(1, _money.Currency.USD).currency == _money.Currency.USD
+    assert _money.Money(_money.Money(1, "EUR")).currency == _money.Currency.EUR
+    assert repr(_money.Money("1.50", "EUR")) == "Money('1.50', 'EUR')"
+    assert repr(_money.Money("1.50")) == "Money('1.50')"
+
+
+def test_money_currency_conflict():
+    with _pytest.raises(_money.CurrencyMismatchError):
+        _money.Money(_money.Money(1, "EUR"), "USD")
+
+
+def test_money_arithmetic_keeps_currency():
+    subject = _money.Money(10, "EUR")
+
+    assert (subject + _money.Money(1)).currency == _money.Currency.EUR
+    assert (_money.Money(1) + subject).currency == _money.Currency.EUR
+    assert (subject - 1).currency == _money.Currency.EUR
+    assert (subject * 2).currency == _money.Currency.EUR
+    assert (subject / 4).currency == _money.Currency.EUR
+    assert (-subject).currency == _money.Currency.EUR
+    assert subject.round(0).currency == _money.Currency.EUR
+
+
+@_pytest.mark.parametrize(
+    "operation",
+    [
+        lambda a, b: a + b,
+        lambda a, b: a - b,
+        lambda a, b: a / b,
+        lambda a, b: a % b,
+        lambda a, b: a // b,
+        lambda a, b: a < b,
+        lambda a, b: _money.sum_([a, None, b]),
+        lambda a, b: _money.Money.max(a, b),
+        lambda a, b: _money.average_([a, b]),
+        lambda a, b: _money.diffs([a, b]),
+        lambda a, b: _money.bucketize([a, b], []),
+        lambda a, b: _money.bucketize([b], [a]),
+    ],
+)
+def test_money_currency_mismatch(operation):
+    with _pytest.raises(
+        _money.CurrencyMismatchError, match="Can't combine EUR with USD"
+    ):
+        operation(_money.Money(10, "EUR"), _money.Money(3, "USD"))
+
+
+def test_money_equality_across_currencies():
+    assert _money.Money(1, "EUR") != _money.Money(1, "USD")
+    assert _money.Money(1, "EUR") == _money.Money(1, "EUR")
+    assert _money.Money(1, "EUR") != _money.Money(1)
+    assert not _money.Money(1, "EUR") == _money.Money(1)
+    assert _money.Money(1) < _money.Money(2, "EUR")
+
+
+def test_money_hash_includes_currency():
+    assert hash(_money.Money(1, "EUR")) == hash(_money.Money("1.00", "EUR"))
+    assert len({_money.Money(1, "EUR"), _money.Money(1), _money.Money(1, "USD")}) == 3
+
+
+def test_currency_mismatch_is_value_error():
+    assert issubclass(_money.CurrencyMismatchError, ValueError)
+
+
+def test_sum_keeps_currency():
+    result = _money.sum_([_money.Money(1, "EUR"), None, _money.Money(2)])
+
+    assert result == _money.Money(3, "EUR")
+    assert result.currency == _money.Currency.EUR
+
+
+def test_money_replace_currency():
+    subject = _money.Money(1, "EUR")
+    result = subject.__replace__(currency="USD")
+
+    assert result.amount == _Decimal(1)
+    assert result.currency == _money.Currency.USD
+    assert subject.__replace__(amount=2).currency == _money.Currency.EUR
+
+
+def test_money_with_vat_currency():
+    subject = _money.MoneyWithVAT(100, 19, currency="EUR")
+
+    assert subject.currency == _money.Currency.EUR
+    assert subject.net.currency == _money.Currency.EUR
+    assert subject.tax.currency == _money.Currency.EUR
+    assert subject.gross.currency == _money.Currency.EUR
+    assert (subject * 2).currency == _money.Currency.EUR
+    usd = _money.MoneyWithVAT(_money.Money(1, "USD"), 0)
+    assert usd.currency == _money.Currency.USD
+    assert repr(subject) == "MoneyWithVAT(net='100', tax='19', currency='EUR')"
+
+
+def test_money_with_vat_currency_conflict():
+    with _pytest.raises(_money.CurrencyMismatchError):
+        _money.MoneyWithVAT(_money.Money(1, "EUR"), _money.Money(1, "USD"))
+
+
+@_pytest.mark.parametrize(
+    "operation",
+    [
+        lambda a, b: a + b,
+        lambda a, b: a - b,
+        lambda a, b: a > b,
+        lambda a, b: _money.MoneyWithVAT.fast_sum([a, b]),
+        lambda a, b: _money.MoneyWithVAT.max(a, b),
+        lambda a, b: _money.MoneyWithVAT.min(a, b),
+        lambda a, b: _money.average_([a, b]),
+        lambda a, b: a.add_to_net(b.net),
+    ],
+)
+def test_money_with_vat_currency_mismatch(operation):
+    with _pytest.raises(_money.CurrencyMismatchError):
+        operation(_money.MoneyWithVAT(1, 0, "EUR"), _money.MoneyWithVAT(1, 0, "USD"))
+
+
+def test_fast_sum_keeps_currency():
+    result = _money.MoneyWithVAT.fast_sum(
+        [_money.MoneyWithVAT(1, 0, "EUR"), None, _money.MoneyWithVAT(2, 1)]
+    )
+
+    assert (result.net, result.tax) == (_money.Money(3, "EUR"), _money.Money(1, "EUR"))
+    assert result.currency == _money.Currency.EUR
+
+
+def test_sum_per_currency():
+    result = _money.sum_(
+        [_money.Money(1, "EUR"), _money.Money(2, "USD"), None, _money.Money(3, "EUR")],
+        per_currency=True,
+    )
+
+    assert len(result) == 2
+    assert list(result) == [_money.Currency.EUR, _money.Currency.USD]
+    assert result["EUR"] == _money.Money(4, "EUR")
+    assert result[_money.Currency.USD] == _money.Money(2, "USD")
+    assert "USD" in result and "CHF" not in result and None not in result
+    assert repr(result) == (
+        "MoneyBag({'EUR': Money('4', 'EUR'), 'USD': Money('2', 'USD')})"
+    )
+    with _pytest.raises(KeyError):
+        result["CHF"]
+
+
+def test_sum_per_currency_keeps_amounts_without_currency_apart():
+    result = _money.sum_([_money.Money(1), _money.Money(2, "EUR")], per_currency=True)
+
+    assert result.items() == [
+        (None, _money.Money(1)),
+        (_money.Currency.EUR, _money.Money(2, "EUR")),
+    ]
+    assert len(_money.sum_([], per_currency=True)) == 0
+
+
+def test_fast_sum_per_currency():
+    result = _money.MoneyWithVAT.fast_sum(
+        [
+            _money.MoneyWithVAT(100, 19, "EUR"),
+            None,
+            _money.MoneyWithVAT(10, 1, "CHF"),
+            _money.MoneyWithVAT(50, "9.5", "EUR"),
+        ],
+        per_currency=True,
+    )
+
+    assert result.keys() == [_money.Currency.EUR, _money.Currency.CHF]
+    assert (result["EUR"].net, result["EUR"].tax) == (
+        _money.Money(150, "EUR"),
+        _money.Money("28.5", "EUR"),
+    )
+    assert result["CHF"].currency == _money.Currency.CHF
+
+
+def test_average_per_currency():
+    result = _money.average_(
+        [_money.Money(1, "EUR"), _money.Money(4, "USD"), _money.Money(2, "EUR")],
+        per_currency=True,
+    )
+
+    assert result.values() == [_money.Money("1.5", "EUR"), _money.Money(4, "USD")]
+
+    with_vat = _money.average_(
+        [_money.MoneyWithVAT(100, 19, "EUR"), _money.MoneyWithVAT(10, 1, "USD")],
+        per_currency=True,
+    )
+    assert with_vat["USD"].tax == _money.Money(1, "USD")
@@ -0,0 +1,68 @@
+import decimal as _decimal
+import pickle as _pickle
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+@_pytest.mark.parametrize(
+    "error",
+    [
+        _money.InvalidAmountError,
+        _money.CurrencyMismatchError,
+        _money.SepaAmountError,
+        _money.DivisionByZero,
+    ],
+)
+def test_errors_derive_from_money_error(error):
+    assert issubclass(error, _money.MoneyError)
+    assert issubclass(error, ValueError)
+    assert error.__module__ == "alasco_money"
+
+
+@_pytest.mark.parametrize(
+    "operation",
+    [
+        lambda: _money.Money(1) / 0,
+        lambda: _money.Money(1) // _money.Money(0),
+        lambda: _money.Money(1) % 0,
+        lambda: _money.MoneyWithVAT(1, 0) / 0,
+        lambda: _money.MoneyWithVAT(100, 19).redistribute(-1),
+        lambda: _money.evaluate("net / 0", {"net": _money.Money(1)}),
+    ],
+)
+def test_division_by_zero(operation):
+    with _pytest.raises(_money.DivisionByZero, match="Division by zero") as info:
+        operation()
+
+    assert isinstance(info.value, ZeroDivisionError)
+    assert isinstance(info.value, _money.MoneyError)
+
+
+def test_division_by_zero_pickles():
+    error = _pickle.loads(_pickle.dumps(_money.DivisionByZero("Division by zero")))
+    assert type(error) is _money.DivisionByZero
+
+
+@_pytest.mark.parametrize(
+    "value, message",
+    [
+        ([1, 2], "Validation error: can't use [1, 2] as Money"),
+        (_decimal.Decimal("NaN"), "Validation error: can't use Decimal('NaN') as Money"),
+    ],
+)
+def test_validation_error(value, message):
+    with _pytest.raises(_money.InvalidAmountError) as info:
+        _money.Money.validate(value)
+
+    assert str(info.value) == message
+
+
+def test_validation_error_money_with_vat():
+    with _pytest.raises(_money.InvalidAmountError, match="as MoneyWithVAT"):
+        _money.MoneyWithVAT.validate({"net": 1})
+
+
+def test_money_error_catches_currency_mismatch():
+    with _pytest.raises(_money.MoneyError):
+        _money.Money(1, "EUR") + _money.Money(1, "USD")
@@ -0,0 +1,276 @@
+import datetime as _datetime
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_late_payment_interest_splits_at_base_rate_changes():
+    items, total = _money.late_payment_interest(
+        _money.Money(10000),
+        _datetime.date(2024, 6, 1),
+        _datetime.date(2024, 8, 1),
+        spread=9,
+    )
+
+    assert items == [
+        {
+            "start": _datetime.date(2024, 6, 1),
+            "end": _datetime.date(2024, 7, 1),
+            "days": 30,
+            "rate": _decimal.Decimal("12.62"),
+            "interest": _money.Money("103.73"),
+        },
+        {
+            "start": _datetime.date(2024, 7, 1),
+            "end": _datetime.date(2024, 8, 1),
+            "days": 31,
+            "rate": _decimal.Decimal("12.37"),
+            "interest": _money.Money("105.06"),
+        },
+    ]
+    assert total == _money.Money("208.79")
+
+
+def test_late_payment_interest_custom_schedule_and_day_count():
+    base_rates = [
+        (_datetime.date(2030, 7, 1), 3),
+        (_datetime.date(2030, 1, 1), 1),
+    ]
+    items, total = _money.late_payment_interest(
+        _money.Money(3600),
+        _datetime.date(2030, 5, 31),
+        _datetime.date(2030, 8, 1),
+        base_rates=base_rates,
+        day_count="30/360",
+    )
+
+    # May 31st counts as 30th, so 31 days at 6% and 30 days at 8% with 360 days per year
+    assert [(item["days"], item["rate"]) for item in items] == [
+        (31, _decimal.Decimal(6)),
+        (30, _decimal.Decimal(8)),
+    ]
+    assert total == _money.Money("18.60") + _money.Money(24)
+
+
+def test_late_payment_interest_empty_range():
+    day = _datetime.date(2024, 3, 1)
+    assert _money.late_payment_interest(_money.Money(100), day, day) == (
+        [],
+        _money.Money(0),
+    )
+
+
+@_pytest.mark.parametrize(
+    "start, end, kwargs, match",
+    [
+        ((2024, 2, 1), (2024, 1, 1), {}, "before the start"),
+        ((2010, 1, 1), (2024, 1, 1), {}, "No base rate"),
+        ((2024, 1, 1), (2024, 2, 1), {"day_count": "act/act"}, "Unknown day count"),
+    ],
+)
+def test_late_payment_interest_invalid(start, end, kwargs, match):
+    with _pytest.raises(ValueError, match=match):
+        _money.late_payment_interest(
+            _money.Money(100),
+            _datetime.date(*start),
+            _datetime.date(*end),
+            **kwargs,
+        )
+
+
+def test_discount():
+    assert _money.discount(_money.Money("121"), "0.1", 2) == _money.Money(100)
+    assert _money.discount(_money.Money(100), "0.1", 0) == _money.Money(100)
+    assert _money.discount(_money.Money(100), "0.1", -1) == _money.Money(110)
+    assert _money.discount(_money.Money(100), "0.21", "0.5").round(2) == _money.Money(
+        "90.91"
+    )
+
+
+def test_discount_invalid_rate():
+    with _pytest.raises(ValueError, match="above -100%"):
+        _money.discount(_money.Money(100), -1, 1)
+
+
+def test_npv_periods():
+    cash_flows = [
+        (0, _money.Money(-1000)),
+        (1, _money.Money(550)),
+        (2, _money.Money(605)),
+    ]
+    assert _money.npv("0.1", cash_flows) == _money.Money(0)
+
+
+def test_npv_dates_with_day_count():
+    cash_flows = [
+        (_datetime.date(2025, 1, 1), _money.Money(1100)),
+        (_datetime.date(2024, 1, 1), _money.Money(-1000)),
+    ]
+    # 2024 has 366 days, so slightly more than a year under act/365
+    assert _money.npv("0.1", cash_flows).round(2) == _money.Money("-0.26")
+    assert _money.npv("0.1", cash_flows, day_count="30/360") == _money.Money(0)
+
+
+def test_npv_mixed_dates_and_periods():
+    cash_flows = [
+        (_datetime.date(2024, 1, 1), _money.Money(-1000)),
+        (1, _money.Money(1100)),
+    ]
+    with _pytest.raises(ValueError, match="mix dates and periods"):
+        _money.npv("0.1", cash_flows)
+
+
+def test_annuity():
+    assert _money.annuity(_money.Money(1000), "0.01", 3) == _money.Money("340.02")
+    assert _money.annuity(_money.Money(100), 0, 3) == _money.Money("33.33")
+
+
+@_pytest.mark.parametrize(
+    "principal, rate, periods",
+    [
+        ("100", "-0.99", 100),
+        ("79228162514264337593543950335", "1000", 1),
+    ],
+)
+def test_annuity_out_of_range(principal, rate, periods):
+    with _pytest.raises(ValueError, match="The annuity is out of range"):
+        _money.annuity(_money.Money(principal), rate, periods)
+
+
+def test_amortization_schedule_corrects_last_row():
+    schedule = _money.amortization_schedule(_money.Money(1000), "0.01", 3)
+
+    assert [
+        (row["period"], row["payment"], row["interest"], row["principal"])
+        for row in schedule
+    ] == [
+        (1, _money.Money("340.02"), _money.Money("10.00"), _money.Money("330.02")),
+        (2, _money.Money("340.02"), _money.Money("6.70"), _money.Money("333.32")),
+        (3, _money.Money("340.03"), _money.Money("3.37"), _money.Money("336.66")),
+    ]
+    assert [row["balance"] for row in schedule] == [
+        _money.Money("669.98"),
+        _money.Money("336.66"),
+        _money.Money(0),
+    ]
+
+
+def test_amortization_schedule_without_interest():
+    schedule = _money.amortization_schedule(_money.Money(100), 0, 3)
+
+    assert [row["payment"] for row in schedule] == [
+        _money.Money("33.33"),
+        _money.Money("33.33"),
+        _money.Money("33.34"),
+    ]
+    assert sum(row["principal"] for row in schedule) == _money.Money(100)
+
+
+@_pytest.mark.parametrize(
+    "rate, periods, match",
+    [
+        ("0.01", 0, "at least one period"),
+        (-1, 3, "above -100%"),
+    ],
+)
+def test_amortization_schedule_invalid(rate, periods, match):
+    with _pytest.raises(ValueError, match=match):
+        _money.amortization_schedule(_money.Money(100), rate, periods)
+
+
+@_pytest.mark.parametrize(
+    "original, settlement_rate, expected",
+    [
+        ("1000", "0.95", (_money.Money("50.00"), "gain")),
+        ("1000", "0.85", (_money.Money("-50.00"), "loss")),
+        ("-1000", "0.95", (_money.Money("-50.00"), "loss")),
+        ("1000", "0.9", (_money.Money(0), "none")),
+    ],
+)
+def test_fx_gain_loss(original, settlement_rate, expected):
+    assert (
+        _money.fx_gain_loss(_money.Money(original), "0.9", settlement_rate) == expected
+    )
+
+
+def test_fx_gain_loss_rounds_local_amounts_first():
+    # 0.05005 and 0.05045 both round to 0.05
+    assert _money.fx_gain_loss(_money.Money("0.05"), "1.001", "1.009") == (
+        _money.Money(0),
+        "none",
+    )
+
+
+def test_fx_gain_loss_invalid_rate():
+    with _pytest.raises(ValueError, match="must be positive"):
+        _money.fx_gain_loss(_money.Money(100), 0, 1)
+
+
+_ECB_XML = """<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01"
+    xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+  <gesmes:subject>Reference rates</gesmes:subject>
+  <Cube>
+    <Cube time='2024-01-05'>
+      <Cube currency='USD' rate='1.0921'/>
+      <Cube currency='JPY' rate='158.08'/>
+      <Cube currency='ISK' rate='150.10'/>
+      <Cube currency='CHF' rate='0.9314'/>
+    </Cube>
+    <Cube time='2024-01-04'>
+      <Cube currency='USD' rate='1.0953'/>
+    </Cube>
+  </Cube>
+</gesmes:Envelope>
+"""
+
+_ECB_CSV = (
+    "Date, USD, JPY, ISK, CHF, \n05 January 2024, 1.0921, 158.08, 150.10, 0.9314, \n"
+)
+
+_ECB_HISTORY_CSV = (
+    "Date,USD,JPY,ISK,CHF,\n2024-01-05,1.0921,158.08,N/A,0.9314,\n"
+    "2024-01-04,1.0953,157.67,N/A,0.9297,\n"
+)
+
+
+@_pytest.mark.parametrize(
+    "data", [_ECB_XML, _ECB_XML.encode(), _ECB_CSV, _ECB_HISTORY_CSV]
+)
+def test_ecb_reference_rates(data):
+    rates = _money.ecb_reference_rates(data)
+
+    assert rates == {
+        _money.Currency.USD: _decimal.Decimal("1.0921"),
+        _money.Currency.JPY: _decimal.Decimal("158.08"),
+        _money.Currency.CHF: _decimal.Decimal("0.9314"),
+    }
+    assert list(rates) == [
+        _money.Currency.USD,
+        _money.Currency.JPY,
+        _money.Currency.CHF,
+    ]
+
+
+def test_ecb_reference_rates_convert():
+    rates = _money.ecb_reference_rates(_ECB_XML)
+    converted = _money.MoneyWithVAT(100, 19, "EUR").convert(
+        rates[_money.Currency.JPY], currency="JPY"
+    )
+
+    assert converted.gross == _money.Money(18812, "JPY")
+
+
+@_pytest.mark.parametrize(
+    "data, match",
+    [
+        ("", "No ECB reference rates found"),
+        ("<Cube><Cube time='2024-01-05'></Cube></Cube>", "No ECB reference rates"),
+        ("Date, USD\n05 January 2024, abc\n", "Invalid ECB reference rate 'abc'"),
+        ("Date, USD\n05 January 2024, -1\n", "rate '-1' for USD"),
+    ],
+)
+def test_ecb_reference_rates_invalid(data, match):
+    with _pytest.raises(ValueError, match=match):
+        _money.ecb_reference_rates(data)
@@ -0,0 +1,105 @@
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+@_pytest.mark.parametrize(
+    "formula, variables, expected",
+    [
+        ("1 + 2 * 3", None, _decimal.Decimal(7)),
+        ("(1 + 2) * 3", None, _decimal.Decimal(9)),
+        ("-2 * -net", {"net": _money.Money(5)}, _money.Money(10)),
+        (
+            "0.19 * net + fee",
+            {"net": _money.Money(100), "fee": _money.Money("2.5")},
+            _money.Money("21.5"),
+        ),
+        (
+            "net / other",
+            {"net": _money.Money(10), "other": _money.Money(4)},
+            _decimal.Decimal("2.5"),
+        ),
+        ("net / 4", {"net": _money.Money(10)}, _money.Money("2.5")),
+        ("net - rate", {"net": _money.Money(10), "rate": "0.5"}, _money.Money("9.5")),
+    ],
+)
+def test_evaluate(formula, variables, expected):
+    result = _money.evaluate(formula, variables)
+
+    assert type(result) is type(expected)
+    assert result == expected
+
+
+def test_evaluate_exact_semantics():
+    variables = {"a": _money.Money("-0"), "b": _money.Money("-0")}
+    result = _money.evaluate("a + b", variables)
+    assert str(result.amount) == "-0"
+
+
+@_pytest.mark.parametrize(
+    "formula, variables, error",
+    [
+        ("net * net", {"net": _money.Money(1)}, TypeError),
+        ("net / 0", {"net": _money.Money(1)}, ZeroDivisionError),
+        ("unknown + 1", {}, ValueError),
+        ("(1 + 2", None, ValueError),
+        ("1 + ", None, ValueError),
+        ("1 2", None, ValueError),
+        ("import os", None, ValueError),
+        ("1 ** 2", None, ValueError),
+        ("a", {"a": object()}, TypeError),
+    ],
+)
+def test_evaluate_errors(formula, variables, error):
+    with _pytest.raises(error):
+        _money.evaluate(formula, variables)
+
+
+@_pytest.mark.parametrize(
+    "formula",
+    [
+        "-" * 200000 + "net",
+        "(" * 100000 + "net" + ")" * 100000,
+        "(" * 257 + "1" + ")" * 257,
+    ],
+)
+def test_evaluate_too_deeply_nested(formula):
+    with _pytest.raises(ValueError, match="nested deeper than 256 levels"):
+        _money.evaluate(formula, {"net": _money.Money(1)})
+
+
+def test_evaluate_nesting_within_limit():
+    formula = "(" * 100 + "-" * 100 + "net" + ")" * 100
+    assert _money.evaluate(formula, {"net": _money.Money(1)}) == _money.Money(1)
+
+
+@_pytest.mark.parametrize(
+    "formula",
+    [
+        "net * 79228162514264337593543950335 * 10",
+        "net + 79228162514264337593543950335",
+        "net / 0.0000000000000000000000000001",
+    ],
+)
+def test_evaluate_out_of_range(formula):
+    with _pytest.raises(_money.InvalidAmountError, match="out of range"):
+        _money.evaluate(formula, {"net": _money.Money("79228162514264337593543950335")})
+
+
+def test_evaluate_keeps_currency():
+    variables = {"a": _money.Money(1, "EUR"), "b": _money.Money(2), "rate": "0.5"}
+
+    assert _money.evaluate("a + b * rate", variables) == _money.Money(2, "EUR")
+    assert _money.evaluate("-a / 2", variables) == _money.Money("-0.5", "EUR")
+    assert _money.evaluate("a / b", variables) == _decimal.Decimal("0.5")
+
+
+@_pytest.mark.parametrize("formula", ["a + b", "a - b", "a / b", "(a * 2) + -b"])
+def test_evaluate_currency_mismatch(formula):
+    variables = {"a": _money.Money(1, "EUR"), "b": _money.Money(1, "CHF")}
+
+    with _pytest.raises(
+        _money.CurrencyMismatchError, match="Can't combine EUR with CHF"
+    ):
+        _money.evaluate(formula, variables)
@@ -0,0 +1,130 @@
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_en16931_empty():
+    totals = _money.InvoiceTotals.en16931([])
+
+    assert totals.total == _money.MoneyWithVAT()
+    assert totals.payable == _money.Money(0)
+    assert totals.subtotals == {}
+
+
+def test_en16931_rounds_lines_and_categories():
+    lines = [
+        _money.MoneyWithVAT("10.005", "1.90095"),
+        _money.MoneyWithVAT("10.005", "1.90095"),
+        _money.MoneyWithVAT("3.333", "0.23331"),
+    ]
+    totals = _money.InvoiceTotals.en16931(lines)
+
+    assert totals.subtotals == {
+        _decimal.Decimal("0.19"): _money.MoneyWithVAT("20.02", "3.80"),
+        _decimal.Decimal("0.07"): _money.MoneyWithVAT("3.33", "0.23"),
+    }
+    assert totals.total.net == _money.Money("23.35")
+    assert totals.total.tax == _money.Money("4.03")
+    assert totals.total.gross == _money.Money("27.38")
+
+
+def test_en16931_category_tax_is_category_net_times_rate():
+    # BR-CO-17: 10.30 * 19% = 1.957, not the 2.00 the rounded line taxes add up to
+    lines = [_money.MoneyWithVAT("1.03", "0.1957")] * 10
+    totals = _money.InvoiceTotals.en16931(lines)
+
+    assert totals.subtotals == {
+        _decimal.Decimal("0.19"): _money.MoneyWithVAT("10.30", "1.96"),
+    }
+    per_line = _money.InvoiceTotals.from_lines(lines, _money.VATRoundingPolicy("line"))
+    assert per_line.total.tax == _money.Money("2.00")
+
+
+def test_en16931_prepaid():
+    totals = _money.InvoiceTotals.en16931(
+        [_money.MoneyWithVAT(100, 19)], prepaid=_money.Money(19)
+    )
+
+    assert totals.prepaid == _money.Money(19)
+    assert totals.payable == _money.Money(100)
+
+
+def test_en16931_invalid_line():
+    with _pytest.raises(TypeError):
+        _money.InvoiceTotals.en16931([_money.Money(1)])
+
+
+_THIRDS = [_money.MoneyWithVAT("0.333", "0.06327")] * 3
+
+
+@_pytest.mark.parametrize(
+    "level, adjust, expected_net, expected_tax",
+    [
+        ("line", "gross", "0.99", "0.18"),
+        ("line", "tax", "0.99", "0.21"),
+        ("line", "net", "1.02", "0.18"),
+        ("category", "gross", "0.99", "0.19"),
+        ("document", "gross", "1.00", "0.19"),
+        ("document", "net", "1.00", "0.19"),
+    ],
+)
+def test_rounding_policy(level, adjust, expected_net, expected_tax):
+    policy = _money.VATRoundingPolicy(level, adjust)
+    totals = _money.InvoiceTotals.from_lines(_THIRDS, policy)
+
+    assert (totals.total.net, totals.total.tax) == (
+        _money.Money(expected_net),
+        _money.Money(expected_tax),
+    )
+    fast_sum = _money.MoneyWithVAT.fast_sum_rounded(_THIRDS, policy)
+    assert (fast_sum.net, fast_sum.tax) == (totals.total.net, totals.total.tax)
+
+
+def test_rounding_policy_defaults_to_en16931():
+    policy = _money.VATRoundingPolicy()
+    assert (policy.level, policy.adjust) == ("category", "gross")
+    assert repr(policy) == "VATRoundingPolicy(level='category', adjust='gross')"
+
+    lines = [_money.MoneyWithVAT("10.005", "1.90095"), _money.MoneyWithVAT(3, "0.21")]
+    assert _money.InvoiceTotals.from_lines(lines).subtotals == (
+        _money.InvoiceTotals.en16931(lines).subtotals
+    )
+
+
+def test_document_rounding_difference_lands_on_largest_category():
+    lines = [
+        _money.MoneyWithVAT("1.004", "0.19076"),
+        _money.MoneyWithVAT("0.004", "0.00028"),
+    ]
+    totals = _money.InvoiceTotals.from_lines(
+        lines, _money.VATRoundingPolicy("document")
+    )
+
+    assert (totals.total.net, totals.total.tax) == (
+        _money.Money("1.01"),
+        _money.Money("0.19"),
+    )
+    subtotals = {
+        rate: (subtotal.net, subtotal.tax)
+        for rate, subtotal in totals.subtotals.items()
+    }
+    assert subtotals == {
+        _decimal.Decimal("0.19"): (_money.Money("1.01"), _money.Money("0.19")),
+        _decimal.Decimal("0.07"): (_money.Money(0), _money.Money(0)),
+    }
+
+
+def test_fast_sum_rounded_skips_none_and_applies_key():
+    items = [{"amount": line} for line in _THIRDS] + [None]
+    result = _money.MoneyWithVAT.fast_sum_rounded(
+        items, _money.VATRoundingPolicy("line"), key=lambda item: item["amount"]
+    )
+
+    assert (result.net, result.tax) == (_money.Money("0.99"), _money.Money("0.18"))
+
+
+@_pytest.mark.parametrize("level, adjust", [("invoice", "gross"), ("line", "vat")])
+def test_rounding_policy_invalid(level, adjust):
+    with _pytest.raises(ValueError, match="Unknown VAT rounding"):
+        _money.VATRoundingPolicy(level, adjust)
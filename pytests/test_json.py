@@ -0,0 +1,115 @@
+import decimal as _decimal
+import json as _json
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_money_to_json_str():
+    value = _money.Money("1.5")
+
+    assert value.to_json_str() == _json.dumps(value.for_json())
+    assert _json.loads(value.to_json_str()) == "1.500000000000"
+
+
+def test_money_json_str_batch():
+    values = [_money.Money("1.5"), None, _money.Money(-2)]
+    text = _money.Money.to_json_str_batch(values)
+
+    assert _json.loads(text) == ["1.500000000000", None, "-2.000000000000"]
+    assert _money.Money.from_json_str_batch(text) == values
+
+
+@_pytest.mark.parametrize("text", ['"1.50"', "1.50", ' "1.5" ', "1.5e0"])
+def test_money_from_json_str(text):
+    assert _money.Money.from_json_str(text) == _money.Money("1.5")
+
+
+def test_money_from_json_str_keeps_precision():
+    value = _money.Money.from_json_str("0.1000000000000000000000000001")
+    assert value.amount == _decimal.Decimal("0.1000000000000000000000000001")
+
+
+@_pytest.mark.parametrize("text", ["", "[1]", '"abc"', "{}", "null", "1 2"])
+def test_money_from_json_str_invalid(text):
+    with _pytest.raises(ValueError, match="Invalid JSON"):
+        _money.Money.from_json_str(text)
+
+
+def test_money_json_str_with_currency():
+    values = [_money.Money("1.5", "EUR"), None, _money.Money(2)]
+    text = _money.Money.to_json_str_batch(values)
+
+    assert _json.loads(values[0].to_json_str()) == values[0].for_json()
+    assert _json.loads(text)[0] == {"amount": "1.500000000000", "currency": "EUR"}
+    assert _money.Money.from_json_str(values[0].to_json_str()) == values[0]
+    assert _money.Money.from_json_str_batch(text) == values
+
+
+def test_money_from_json_str_unknown_currency():
+    with _pytest.raises(ValueError, match="Unknown currency 'XYZ'"):
+        _money.Money.from_json_str('{"amount": 1, "currency": "XYZ"}')
+
+
+def test_money_with_vat_to_json_str():
+    value = _money.MoneyWithVAT("100", "19")
+
+    assert _json.loads(value.to_json_str()) == value.for_json()
+
+
+def test_money_with_vat_json_str_batch():
+    values = [_money.MoneyWithVAT("100", "19"), None, _money.MoneyWithVAT("-1.5", 0)]
+    text = _money.MoneyWithVAT.to_json_str_batch(values)
+
+    assert _json.loads(text) == [
+        values[0].for_json(),
+        None,
+        values[2].for_json(),
+    ]
+    result = _money.MoneyWithVAT.from_json_str_batch(text)
+    assert [(value.net, value.tax) for value in result if value] == [
+        (_money.Money(100), _money.Money(19)),
+        (_money.Money("-1.5"), _money.Money(0)),
+    ]
+    assert result[1] is None
+
+
+def test_money_with_vat_json_str_with_currency():
+    values = [_money.MoneyWithVAT(100, 19, "CHF"), _money.MoneyWithVAT(1, 0)]
+    text = _money.MoneyWithVAT.to_json_str_batch(values)
+
+    assert [_json.loads(value.to_json_str()) for value in values] == [
+        value.for_json() for value in values
+    ]
+    result = _money.MoneyWithVAT.from_json_str_batch(text)
+    assert [value.currency for value in result] == [_money.Currency.CHF, None]
+    assert _money.MoneyWithVAT.from_json_str(values[0].to_json_str()).tax == (
+        _money.Money(19, "CHF")
+    )
+
+
+def test_money_with_vat_from_json_str():
+    value = _money.MoneyWithVAT.from_json_str('{"net": 100, "tax": "19.00"}')
+
+    assert (value.net, value.tax) == (_money.Money(100), _money.Money(19))
+
+
+@_pytest.mark.parametrize("text", ['{"net": 100}', '{"net": "x", "tax": 1}', "[]"])
+def test_money_with_vat_from_json_str_invalid(text):
+    with _pytest.raises(ValueError, match="Invalid JSON"):
+        _money.MoneyWithVAT.from_json_str(text)
+
+
+def test_money_with_vat_ratio_json_str():
+    value = _money.MoneyWithVATRatio("0.25", "0.75")
+    text = value.to_json_str()
+
+    assert _json.loads(text) == value.for_json()
+    assert _money.MoneyWithVATRatio.from_json_str(text) == value
+    batch = _money.MoneyWithVATRatio.to_json_str_batch([value, None])
+    assert _money.MoneyWithVATRatio.from_json_str_batch(batch) == [value, None]
+
+
+def test_json_str_batch_rejects_other_types():
+    with _pytest.raises(TypeError):
+        _money.Money.to_json_str_batch([_money.MoneyWithVAT(1, 0)])
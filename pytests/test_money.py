@@ -1,10 +1,25 @@
 # This set of tests is copied from the py-moneyed library
 # https://github.com/py-moneyed/py-moneyed/blob/d734ffa7ebd28040cc3f3fcb376876751989e54a/moneyed/test_moneyed_classes.py
 
+import math
+import numbers
+import re
+import sqlite3
+import statistics
+from datetime import date
 from decimal import Decimal
+from fractions import Fraction
+from functools import cmp_to_key
 
 import pytest
-from alasco_money import Money
+from alasco_money import (
+    InvalidAmountError,
+    Money,
+    MoneyWithVAT,
+    SepaAmountError,
+    set_money_division,
+    set_negative_zero_policy,
+)
 
 
 class CustomDecimal(Decimal):
@@ -33,6 +48,58 @@ def test_init_scientific(value):
     assert money.amount == Decimal(value)
 
 
+@pytest.mark.parametrize(
+    "value, expected",
+    [
+        ("1.5e3", Decimal("1500")),
+        ("-2E-2", Decimal("-0.02")),
+        ("1e+2", Decimal("100")),
+        (".5e1", Decimal("5")),
+        ("1.5000e-27", Decimal("1.5e-27")),
+        ("1e28", Decimal("1e28")),
+        ("-1.25e26", Decimal("-125000000000000000000000000")),
+        ("-0e-30", Decimal("-0")),
+    ],
+)
+def test_init_scientific_exact(value, expected):
+    money = Money(value)
+
+    assert money.amount == expected
+    assert money.amount.is_signed() == expected.is_signed()
+
+
+@pytest.mark.parametrize("value", ["1e-30", "1e40", "1e", "e5", "1.5.0e3"])
+def test_init_scientific_invalid(value):
+    with pytest.raises(ValueError):
+        Money(value)
+
+
+@pytest.mark.parametrize(
+    "value",
+    [float("nan"), float("inf"), float("-inf"), Decimal("NaN"), Decimal("-Infinity")],
+)
+def test_init_non_finite(value):
+    with pytest.raises(InvalidAmountError, match="NaN and Infinity"):
+        Money(value)
+
+    with pytest.raises(ValueError):
+        Money(value)
+
+
+@pytest.mark.parametrize(
+    "value, message",
+    [("abc", "Invalid amount 'abc'"), (object(), "Invalid amount <object object")],
+)
+def test_init_invalid(value, message):
+    with pytest.raises(InvalidAmountError, match=re.escape(message)):
+        Money(value)
+
+
+def test_init_float_out_of_range():
+    with pytest.raises(InvalidAmountError, match="out of range"):
+        Money(1e300)
+
+
 def test_repr():
     assert repr(Money(Decimal("1000000"))) == "Money('1000000')"
     assert repr(Money(Decimal("2.000"))) == "Money('2.000')"
@@ -50,6 +117,141 @@ def test_str():
     assert isinstance(str(Money(Decimal("1000000"))), str)
 
 
+@pytest.mark.parametrize(
+    "value, expected",
+    [
+        ("1000000", "1000000.00"),
+        ("123.45", "123.45"),
+        ("123.454", "123.45"),
+        ("-2.5", "-2.50"),
+    ],
+)
+def test_str_display(value, expected):
+    assert str(Money(value)) == expected
+
+
+@pytest.mark.parametrize(
+    "money, locale, expected",
+    [
+        (Money("1234.567"), "de_DE", "1.234,57 €"),
+        (Money("1234.567"), "en_IE", "€1,234.57"),
+        (Money("1234.567", "USD"), "en_US", "$1,234.57"),
+        (Money("1234567.5"), "en_US", "$1,234,567.50"),
+        (Money("1234.5", "CHF"), "de_CH", "CHF 1\u2019234.50"),
+        (Money("1234.6", "JPY"), "en_US", "¥1,235"),
+        (Money("1234.5"), "fr-FR", "1 234,50 €"),
+        (Money("-1234.5"), "de_DE", "-1.234,50 €"),
+        (Money("-0.001"), "de_DE", "0,00 €"),
+        (Money("12"), "nl_NL", "€ 12,00"),
+    ],
+)
+def test_format(money, locale, expected):
+    assert money.format(locale) == expected
+
+
+def test_format_defaults():
+    assert Money("1234.567").format() == "1.234,57 €"
+    assert Money("1234.567").format(symbol=False) == "1.234,57"
+    assert Money("1234.567").format(decimals=0) == "1.235 €"
+    assert Money("0.5", "JPY").format("en_US", decimals=3) == "¥0.500"
+    assert Money("1.5", "JPY").format("en_US") == "¥2"
+
+
+def test_format_unknown_locale():
+    with pytest.raises(ValueError, match="Unknown locale 'xx_XX'"):
+        Money(1).format("xx_XX")
+
+
+@pytest.mark.parametrize(
+    "text, expected",
+    [
+        ("1.234,56 €", Money("1234.56", "EUR")),
+        ("  -€1,234.56 ", Money("-1234.56", "EUR")),
+        ("€ -5", Money("-5", "EUR")),
+        ("CHF 1'234.50", Money("1234.50", "CHF")),
+        ("CHF 1\u2019234.50", Money("1234.50", "CHF")),
+        ("1 234,5 EUR", Money("1234.5", "EUR")),
+        ("1.234.567", Money("1234567")),
+        ("1,234,567.891", Money("1234567.891")),
+        ("0.123", Money("0.123")),
+        ("1234.567", Money("1234.567")),
+        ("12,5", Money("12.5")),
+        (".5 usd", Money("0.5", "USD")),
+        ("+7", Money("7")),
+    ],
+)
+def test_parse(text, expected):
+    parsed = Money.parse(text)
+    assert parsed.amount == expected.amount
+    assert parsed.currency == expected.currency
+
+
+def test_parse_locale():
+    assert Money.parse("1.234", "de_DE").amount == Decimal("1234")
+    assert Money.parse("1,234", "en_US").amount == Decimal("1234")
+    assert Money.parse("1.234", "en_US").amount == Decimal("1.234")
+    assert Money.parse("1 234,5 €", "fr_FR").amount == Decimal("1234.5")
+    assert Money.parse("1\u2019234.5", "de_CH").amount == Decimal("1234.5")
+    assert Money.parse("1'234.5", "de_CH").amount == Decimal("1234.5")
+
+
+@pytest.mark.parametrize(
+    "text, locale, message",
+    [
+        ("1.234", None, "Ambiguous amount '1.234', pass a locale"),
+        ("1,234 €", None, "Ambiguous amount '1,234 €', pass a locale"),
+        ("1.23.4", None, "Misplaced thousands separator in amount '1.23.4'"),
+        ("1.5", "de_DE", "Misplaced thousands separator in amount '1.5'"),
+        ("1,234.5", "de_CH", "Unexpected ',' in amount '1,234.5' for locale de_CH"),
+        ("€ 5 EUR", None, "Amount '€ 5 EUR' names more than one currency"),
+        ("--5", None, "Invalid amount '--5'"),
+        ("1 EUR 2", None, "Invalid amount '1 EUR 2'"),
+        ("1,", None, "Invalid amount '1,'"),
+        ("", None, "Invalid amount ''"),
+    ],
+)
+def test_parse_invalid(text, locale, message):
+    with pytest.raises(InvalidAmountError, match=re.escape(message)):
+        Money.parse(text, locale)
+
+
+@pytest.mark.parametrize(
+    "text, locale, message",
+    [
+        ("kr 5", None, "Ambiguous currency symbol 'kr', use one of NOK, SEK"),
+        ("5 XYZ", None, "Unknown currency 'XYZ'"),
+        ("1", "xx", "Unknown locale 'xx'"),
+    ],
+)
+def test_parse_invalid_currency_or_locale(text, locale, message):
+    with pytest.raises(ValueError, match=re.escape(message)):
+        Money.parse(text, locale)
+
+
+@pytest.mark.parametrize(
+    "money, spec, expected",
+    [
+        (Money("1234.567"), "", "1234.57"),
+        (Money("1234.567"), ".2f", "1234.57"),
+        (Money("1234.567"), ".1f", "1234.6"),
+        (Money("1234.567"), "n", "1234.57"),
+        (Money("1234567.5"), ",n", "1,234,567.50"),
+        (Money("1234567.5"), ",.0n", "1,234,568"),
+        (Money("1234.5", "JPY"), ",n", "1,234"),
+        (Money("-5"), "+>8n", "+++-5.00"),
+        (Money("5"), "+n", "+5.00"),
+    ],
+)
+def test_format_spec(money, spec, expected):
+    assert format(money, spec) == expected
+    assert f"{money:{spec}}" == expected
+
+
+def test_format_spec_invalid():
+    with pytest.raises(ValueError):
+        format(Money(1), "x")
+
+
 def test_hash():
     assert Money(Decimal("1000000")) in {Money(Decimal("1000000"))}
 
@@ -81,6 +283,38 @@ def test_rsub_non_money():
         assert "abc" - Money(3) == Money(-2)
 
 
+def test_abs_diff():
+    assert Money(3).abs_diff(Money(5)) == (Money(-2), Money(2))
+    assert Money(5).abs_diff(Money(3)) == (Money(2), Money(2))
+
+
+@pytest.mark.parametrize(
+    "value, other",
+    [
+        ("12.50", Money(-1)),
+        ("-12.50", Money(1)),
+        ("-12.50", Decimal("-0")),
+        ("12.50", Decimal("0")),
+        ("0", -3),
+    ],
+)
+def test_copysign(value, other):
+    sign_source = other.amount if isinstance(other, Money) else Decimal(other)
+    expected = Decimal(value).copy_sign(sign_source)
+
+    assert str(Money(value).copysign(other).amount) == str(expected)
+
+
+def test_add_sub_opt():
+    assert Money(3).add_opt(None) == Money(3)
+    assert Money(3).add_opt(Money(2)) == Money(5)
+    assert Money(3).sub_opt(None) == Money(3)
+    assert Money(3).sub_opt(2) == Money(1)
+
+    with pytest.raises(TypeError):
+        Money(3).add_opt("abc")
+
+
 def test_mul():
     x = Money(111.33)
     assert 3 * x == Money(333.99)
@@ -92,6 +326,21 @@ def test_mul_bad():
         Money(Decimal("1000000")) * Money(Decimal("1000000"))
 
 
+def test_mul_fraction():
+    assert Money(100) * Fraction(1, 3) == Money(100) / 3
+    assert Fraction(2, 3) * Money(3) == Money(2)
+    assert (Money(1) * Fraction(1, 3)).amount == Decimal(1) / Decimal(3)
+    assert (Money(1) * Fraction(1, 3)).amount != (Money(1) * Decimal(1 / 3)).amount
+
+
+def test_div_fraction():
+    assert Money(100) / Fraction(3, 2) == Money(200) / 3
+    assert Money(2) / Fraction(2, 3) == Money(3)
+
+    with pytest.raises(ZeroDivisionError):
+        Money(1) / Fraction(0)
+
+
 def test_div():
     x = Money(50)
     y = Money(2)
@@ -99,6 +348,102 @@ def test_div():
     assert x / y == Decimal(25)
 
 
+@pytest.mark.parametrize(
+    "value, divisor, quotient, remainder",
+    [
+        ("100", 3, "33.33", "0.01"),
+        ("200", 3, "66.66", "0.02"),
+        ("-100", 3, "-33.33", "-0.01"),
+        ("10", 4, "2.5", "0"),
+        ("0.01", 2, "0", "0.01"),
+    ],
+)
+def test_divide_with_remainder(value, divisor, quotient, remainder):
+    result = Money(value).divide_with_remainder(divisor)
+
+    assert result == (Money(quotient), Money(remainder))
+    assert result[0] * divisor + result[1] == Money(value)
+
+
+def test_divide_with_remainder_by_zero():
+    with pytest.raises(ZeroDivisionError):
+        Money(1).divide_with_remainder(0)
+
+
+@pytest.mark.parametrize(
+    "value, units, fraction",
+    [
+        ("12.34", 12, "0.34"),
+        ("-12.34", -12, "-0.34"),
+        ("0.5", 0, "0.5"),
+        ("100", 100, "0"),
+        ("123456789012345678901234", 123456789012345678901234, "0"),
+    ],
+)
+def test_split_units(value, units, fraction):
+    assert Money(value).split_units() == (units, Money(fraction))
+
+
+@pytest.mark.parametrize(
+    "value, ratios, expected",
+    [
+        ("100", [1, 1, 1], ["33.34", "33.33", "33.33"]),
+        ("0.05", [3, 7], ["0.02", "0.03"]),
+        ("-10", [1, 2], ["-3.33", "-6.67"]),
+        ("10", [Decimal("0.5"), 0, "0.25"], ["6.67", "0.00", "3.33"]),
+        ("1.005", [1, 1], ["0.503", "0.502"]),
+    ],
+)
+def test_allocate(value, ratios, expected):
+    parts = Money(value).allocate(ratios)
+
+    assert [str(part.amount) for part in parts] == expected
+    assert sum(parts, Money(0)) == Money(value)
+
+
+def test_allocate_in_minor_unit():
+    parts = Money(100, "JPY").allocate([1, 1, 1])
+
+    assert [part.amount for part in parts] == [Decimal(34), Decimal(33), Decimal(33)]
+    assert all(part.currency.code == "JPY" for part in parts)
+
+
+@pytest.mark.parametrize(
+    "ratios, message",
+    [
+        ([], "At least one ratio is required"),
+        ([1, -1], "Ratios must not be negative"),
+        ([0, 0], "Ratios must not all be zero"),
+    ],
+)
+def test_allocate_invalid(ratios, message):
+    with pytest.raises(ValueError, match=message):
+        Money(1).allocate(ratios)
+
+
+def test_ratio_to():
+    assert Money(50).ratio_to(Money(2)) == Decimal(25)
+
+    with pytest.raises(ZeroDivisionError):
+        Money(50).ratio_to(Money(0))
+
+
+def test_money_division_mode():
+    set_money_division("raise")
+    try:
+        with pytest.raises(TypeError):
+            Money(50) / Money(2)
+        assert Money(50) / 2 == Money(25)
+        assert Money(50).ratio_to(Money(2)) == Decimal(25)
+    finally:
+        set_money_division("decimal")
+
+    assert Money(50) / Money(2) == Decimal(25)
+
+    with pytest.raises(ValueError):
+        set_money_division("unknown")
+
+
 def test_div_by_non_Money():
     x = Money(50)
     y = 2
@@ -129,6 +474,16 @@ def test_negation_with_operands(right):
     assert str((-money_right).amount) == str(expected)
 
 
+@pytest.mark.parametrize("right", [*OPERANDS, Decimal("-0.00"), Decimal("1.50")])
+def test_pos_with_operands(right):
+    expected = +Decimal(right)
+
+    money_right = Money(right)
+
+    assert str((+money_right).amount) == str(expected)
+    assert +money_right is not money_right
+
+
 @pytest.mark.parametrize("left", OPERANDS)
 @pytest.mark.parametrize("right", OPERANDS)
 def test_add_with_operands(left, right):
@@ -194,6 +549,24 @@ def test_div_with_operands(left, right):
     assert str((left / money_right).amount) == str(expected)
 
 
+def test_negative_zero_policy():
+    set_negative_zero_policy("normalize")
+    try:
+        assert str(Money("-0").amount) == "0"
+        assert str((Money("-0") + Money("-0")).amount) == "0"
+        assert str((Money(0) * -1).amount) == "0"
+        assert str(Money(0) * -1) == "0.00"
+        assert (Money(0) * -1).for_json() == "0.000000000000"
+    finally:
+        set_negative_zero_policy("preserve")
+
+    assert str(Money("-0").amount) == "-0"
+    assert (Money(0) * -1).for_json() == "-0.000000000000"
+
+    with pytest.raises(ValueError):
+        set_negative_zero_policy("unknown")
+
+
 def test_equality_to_other_types():
     x = Money(0)
     assert x != None  # noqa: E711
@@ -247,6 +620,59 @@ def test_round_even():
     assert x.round(0) == Money(4)
 
 
+@pytest.mark.parametrize(
+    "value, strategy, expected",
+    [
+        ("2.345", "half_even", "2.34"),
+        ("2.345", "half_up", "2.35"),
+        ("2.345", "ROUND_HALF_UP", "2.35"),
+        ("-2.345", "half_up", "-2.35"),
+        ("2.345", "half_down", "2.34"),
+        ("2.346", "half_down", "2.35"),
+        ("2.349", "down", "2.34"),
+        ("2.341", "up", "2.35"),
+        ("-2.341", "up", "-2.35"),
+    ],
+)
+def test_round_strategy(value, strategy, expected):
+    assert Money(value).round(2, strategy) == Money(expected)
+
+
+def test_round_strategy_negative_digits():
+    assert Money("1250").round(-2, strategy="half_up") == Money("1300")
+    assert Money("1250").round(-2) == Money("1200")
+
+
+def test_round_unknown_strategy():
+    with pytest.raises(ValueError, match="Unknown rounding strategy half_odd"):
+        Money(1).round(2, "half_odd")
+
+
+def test_float():
+    assert float(Money("1234.5")) == 1234.5
+    assert float(Money("-0.1")) == -0.1
+    assert isinstance(float(Money(1)), float)
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    [
+        ("12.99", 12),
+        ("-12.99", -12),
+        ("0.5", 0),
+        ("79228162514264337593543950335", 2**96 - 1),
+    ],
+)
+def test_int(value, expected):
+    assert int(Money(value)) == expected
+    assert math.trunc(Money(value)) == expected
+
+
+def test_numeric_apis():
+    assert statistics.mean([Money(1), Money(2)]) == Money("1.5")
+    assert statistics.fmean([Money(1), Money(2)]) == 1.5
+
+
 def test_bool():
     assert bool(Money(1))
     assert bool(Money("0.0000000000000000000000000001"))
@@ -265,3 +691,509 @@ def test_decimal_doesnt_use_str_when_dividing():
     a = CustomDecimal("3.2")
     result = m / a
     assert result == Money("4.875")
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    [
+        ("1234.5", (1234.5, True, "1234.5")),
+        ("0.10", (0.1, True, "0.1")),
+        ("-7", (-7.0, True, "-7")),
+        (
+            "0.1234567890123456789",
+            (0.12345678901234568, False, "0.1234567890123456789"),
+        ),
+    ],
+)
+def test_to_excel(value, expected):
+    assert Money(value).to_excel() == expected
+
+
+def test_to_excel_batch():
+    assert Money.to_excel_batch([Money(1), None]) == [(1.0, True, "1"), None]
+
+    with pytest.raises(TypeError):
+        Money.to_excel_batch([1])
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    [
+        ("1234.567", ("1234,57", "S")),
+        ("0", ("0,00", "S")),
+        ("-0.001", ("0,00", "S")),
+        ("-12.5", ("12,50", "H")),
+    ],
+)
+def test_to_datev(value, expected):
+    assert Money(value).to_datev() == expected
+
+
+@pytest.mark.parametrize(
+    "value, expected",
+    [
+        ("1234.5", "1234.50"),
+        ("0.01", "0.01"),
+        ("12.3400", "12.34"),
+        ("999999999.99", "999999999.99"),
+    ],
+)
+def test_to_sepa_amount(value, expected):
+    assert Money(value).to_sepa_amount() == expected
+
+
+@pytest.mark.parametrize(
+    "value, message",
+    [
+        ("1.001", "at most 2 decimals"),
+        ("0", "must be positive"),
+        ("-5", "must be positive"),
+        ("1000000000", "must not exceed"),
+    ],
+)
+def test_to_sepa_amount_invalid(value, message):
+    with pytest.raises(SepaAmountError, match=message):
+        Money(value).to_sepa_amount()
+
+    with pytest.raises(ValueError):
+        Money(value).to_sepa_amount()
+
+
+@pytest.mark.parametrize(
+    "value, locale, expected",
+    [
+        ("1,234.56", "en", Decimal("1234.56")),
+        ("1 234 567.8", "en", Decimal("1234567.8")),
+        ("-1234.5", "en", Decimal("-1234.5")),
+        ("1.234,56", "de", Decimal("1234.56")),
+        ("-1.234.567,8", "de", Decimal("-1234567.8")),
+        ("1234", "de", Decimal("1234")),
+        ("1,5", "de", Decimal("1.5")),
+        ("12.5", None, Decimal("12.5")),
+    ],
+)
+def test_validate_locale(value, locale, expected):
+    assert Money.validate(value, locale=locale) == Money(expected)
+
+
+@pytest.mark.parametrize(
+    "value, locale",
+    [
+        ("1,234.56", None),
+        ("1.234,56", "en"),
+        ("1,23,4", "en"),
+        ("1.2345,6", "de"),
+        ("abc", "de"),
+    ],
+)
+def test_validate_locale_invalid(value, locale):
+    with pytest.raises(ValueError, match="Validation error"):
+        Money.validate(value, locale=locale)
+
+
+def test_validate_unknown_locale():
+    with pytest.raises(ValueError, match="Unknown locale"):
+        Money.validate("1", locale="fr")
+
+
+@pytest.mark.parametrize(
+    "value, other",
+    [
+        ("1.0", "1.00"),
+        ("1.00", "1.0"),
+        ("1", "1"),
+        ("-1.0", "-1.00"),
+        ("-1.00", "-1.0"),
+        ("-0", "0"),
+        ("0", "-0"),
+        ("0.00", "0"),
+        ("-2", "1"),
+        ("3", "2.99"),
+        ("-3", "-2.99"),
+    ],
+)
+def test_compare_total(value, other):
+    expected = Decimal(value).compare_total(Decimal(other))
+
+    assert Money(value).compare_total(Money(other)) == expected
+    assert Money(value).compare_total(Decimal(other)) == expected
+
+
+def test_compare_total_sorting():
+    values = ["1.00", "-1", "1.0", "0", "-0", "-1.0", "1"]
+
+    result = sorted(map(Money, values), key=cmp_to_key(Money.compare_total))
+
+    expected = sorted(map(Decimal, values), key=cmp_to_key(Decimal.compare_total))
+    assert [str(money.amount) for money in result] == [str(d) for d in expected]
+
+
+def test_sort_key():
+    values = [
+        "1.005",
+        "-1.5",
+        "0",
+        "-0.001",
+        "2",
+        "-2",
+        "1.0050",
+        "79228162514264337593543950335",
+    ]
+
+    result = sorted(map(Money, values), key=Money.sort_key)
+
+    assert [money.amount for money in result] == sorted(map(Decimal, values))
+    assert Money("1.0").sort_key() == Money("1.00").sort_key()
+    assert Money("-0").sort_key() == Money("0").sort_key()
+    assert hash(Money("-1.5").sort_key()) == hash(Money("-1.50").sort_key())
+
+
+def test_stable_hash():
+    assert Money("1.00").stable_hash() == Money("1.0").stable_hash()
+    assert Money("-0").stable_hash() == Money("0.000").stable_hash()
+    assert Money("1").stable_hash() != Money("-1").stable_hash()
+    assert Money("1").stable_hash() != Money("10").stable_hash()
+    # Pinned to catch accidental changes, the value has to stay the same forever
+    assert Money("12.5").stable_hash() == 0x1F6438F102D7BD7B
+
+
+def test_from_rows():
+    rows = [(1, "12.50"), (2, Decimal("-3")), (3, None), (4, 7)]
+
+    assert Money.from_rows(rows, column=1) == [
+        Money("12.5"),
+        Money(-3),
+        None,
+        Money(7),
+    ]
+    assert Money.from_rows(iter(rows)) == [Money(1), Money(2), Money(3), Money(4)]
+
+
+def test_from_rows_cursor():
+    connection = sqlite3.connect(":memory:")
+    connection.execute("CREATE TABLE costs (amount TEXT)")
+    connection.executemany(
+        "INSERT INTO costs VALUES (?)", [("1.10",), (None,), ("-2",)]
+    )
+
+    cursor = connection.execute("SELECT amount FROM costs ORDER BY rowid")
+
+    assert Money.from_rows(cursor) == [Money("1.1"), None, Money(-2)]
+
+
+def test_from_rows_dict_rows():
+    rows = [{"amount": "1"}, {"amount": None}]
+
+    assert Money.from_rows(rows, column="amount") == [Money(1), None]
+
+
+def test_from_rows_invalid():
+    with pytest.raises(ValueError):
+        Money.from_rows([("abc",)])
+
+
+class _DecimalSubclass(Decimal):
+    def __str__(self):
+        return "not a number"
+
+
+class _DecimalWrapper:
+    def __init__(self, value, method="__decimal__"):
+        setattr(self, method, lambda: value)
+
+
+def test_init_decimal_subclass():
+    value = _DecimalSubclass("-1.50")
+
+    assert Money(value).amount == Decimal("-1.50")
+    assert Money(1) * value == Money("-1.5")
+    assert Money(_DecimalSubclass("-0")).amount.is_signed()
+
+
+@pytest.mark.parametrize("method", ["__decimal__", "as_decimal"])
+def test_init_decimal_protocol(method):
+    value = _DecimalWrapper(Decimal("12.25"), method)
+
+    assert Money(value) == Money("12.25")
+    assert Money(2) * value == Money("24.5")
+    assert Money(49) / value == Money(4)
+
+
+def test_init_decimal_protocol_invalid():
+    with pytest.raises(ValueError):
+        Money(_DecimalWrapper(1.5))
+
+
+@pytest.mark.parametrize(
+    "value, rate, expected_tax",
+    [
+        ("100", Decimal("0.19"), "19.00"),
+        ("4.44", "0.07", "0.3108"),
+        ("-10", 0, "0"),
+        ("12.5", Decimal("0.19"), "2.375"),
+    ],
+)
+def test_apply_vat(value, rate, expected_tax):
+    result = Money(value).apply_vat(rate)
+
+    assert isinstance(result, MoneyWithVAT)
+    assert result.net == Money(value)
+    assert result.tax == Money(expected_tax)
+
+
+def test_apply_vat_invalid_rate():
+    with pytest.raises(ValueError):
+        Money(1).apply_vat("abc")
+
+
+def test_ordering_against_numbers():
+    assert Money("0.01") > 0
+    assert Money(5) >= Decimal(5)
+    assert Money(-1) < 0.5
+    assert Money(5) != 5
+
+    with pytest.raises(TypeError):
+        assert Money(5) < "6"
+
+
+@pytest.mark.parametrize(
+    "value, divisor, expected",
+    [
+        ("10.07", "0.05", "0.02"),
+        ("10.05", "0.05", "0.00"),
+        ("-7", 3, "-1"),
+        ("7.5", Money(2), "1.5"),
+    ],
+)
+def test_mod(value, divisor, expected):
+    assert Money(value) % divisor == Money(expected)
+
+
+def test_mod_by_zero():
+    with pytest.raises(ZeroDivisionError):
+        Money(1) % 0
+
+
+@pytest.mark.parametrize(
+    "value, divisor, expected",
+    [
+        ("10.07", "0.05", "201"),
+        ("7.5", 2, "3"),
+        ("-7", 2, "-3"),
+        ("7", -2, "-3"),
+        ("-1", 3, "-0"),
+    ],
+)
+def test_floordiv(value, divisor, expected):
+    result = Money(value) // divisor
+
+    assert isinstance(result, Money)
+    assert str(result.amount) == str(Decimal(value) // Decimal(divisor)) == expected
+
+
+def test_floordiv_by_money():
+    result = Money("7.5") // Money(2)
+
+    assert isinstance(result, Decimal)
+    assert result == Decimal(3)
+
+
+def test_rfloordiv():
+    assert 7 // Money(2) == Money(3)
+
+
+def test_divmod():
+    quotient, remainder = divmod(Money("-7.5"), 2)
+
+    assert quotient == Money(-3)
+    assert remainder == Money("-1.5")
+    assert quotient * 2 + remainder == Money("-7.5")
+    assert divmod(Money(7), Money(2)) == (Decimal(3), Money(1))
+
+
+@pytest.mark.parametrize(
+    "operation",
+    [lambda: Money(1) // 0, lambda: 1 // Money(0), lambda: divmod(Money(1), Money(0))],
+)
+def test_floordiv_by_zero(operation):
+    with pytest.raises(ZeroDivisionError, match="Division by zero"):
+        operation()
+
+
+@pytest.mark.parametrize(
+    "operation",
+    [
+        lambda money: money // Decimal("0.1"),
+        lambda money: divmod(money, Decimal("0.1")),
+        lambda money: money.amount // Money("0.1"),
+    ],
+)
+def test_floordiv_out_of_range(operation):
+    with pytest.raises(InvalidAmountError, match="out of range"):
+        operation(Money("79228162514264337593543950335"))
+
+
+def test_replace():
+    money = Money("12.50")
+
+    assert money.__replace__(amount="3") == Money(3)
+    assert money.__replace__() == money
+    assert money == Money("12.50")
+
+    with pytest.raises(TypeError):
+        money.__replace__(net=1)
+
+
+def test_is_number():
+    assert isinstance(Money(1), numbers.Number)
+    assert not isinstance(Money(1), numbers.Real)
+    assert not isinstance(MoneyWithVAT(1, 1), numbers.Number)
+
+
+@pytest.mark.parametrize(
+    "mantissa, exponent, expected",
+    [
+        (1500000, -6, "1.500000"),
+        (150, -2, "1.50"),
+        (-7, 0, "-7"),
+        (12, 3, "12000"),
+        (1, -28, "1E-28"),
+    ],
+)
+def test_from_parts(mantissa, exponent, expected):
+    money = Money.from_parts(mantissa, exponent)
+
+    assert str(money.amount) == expected
+    assert Money.from_parts(*money.to_parts()).amount.as_tuple() == (
+        money.amount.as_tuple()
+    )
+
+
+def test_to_parts():
+    assert Money("1.50").to_parts() == (150, -2)
+    assert Money("-0.001").to_parts() == (-1, -3)
+    assert Money(0).to_parts() == (0, 0)
+
+
+@pytest.mark.parametrize("mantissa, exponent", [(1, -29), (1, 29), (10**30, 0)])
+def test_from_parts_out_of_range(mantissa, exponent):
+    with pytest.raises(InvalidAmountError, match="out of range"):
+        Money.from_parts(mantissa, exponent)
+
+
+@pytest.mark.parametrize("value", ["1.50", "-0.125", "-0.00", "100", "1E-28"])
+def test_as_decimal(value):
+    result = Money(value).as_decimal()
+
+    assert type(result) is Decimal
+    assert result.as_tuple() == Decimal(value).as_tuple()
+
+
+@pytest.mark.parametrize(
+    "value", ["1.50", "-0.125", "0", "-0.00", "100", "1E-28", "0.333333333333"]
+)
+def test_as_integer_ratio(value):
+    numerator, denominator = Money(value).as_integer_ratio()
+
+    assert (numerator, denominator) == Decimal(value).as_integer_ratio()
+    assert Fraction(numerator, denominator) == Fraction(Decimal(value))
+
+
+@pytest.mark.parametrize(
+    "value, zero", [("1.50", 0), ("-0", 0), ("-0", Decimal("-0")), ("2", Decimal(0))]
+)
+def test_add_sub_zero_operands(value, zero):
+    money = Money(value)
+
+    for result, expected in [
+        (money + zero, Decimal(value) + zero),
+        (zero + money, zero + Decimal(value)),
+        (money - zero, Decimal(value) - zero),
+    ]:
+        assert str(result.amount) == str(expected)
+
+    assert sum([money, money]) == money * 2
+    assert sum([MoneyWithVAT(money, 1)]).net == money
+
+
+@pytest.mark.parametrize(
+    "actual, budget, expected, expected_pct",
+    [
+        ("110", "100", "10", Decimal(10)),
+        ("75", "100", "-25", Decimal(-25)),
+        ("-120", "-100", "-20", Decimal(-20)),
+        ("50", "0", "50", None),
+        ("0", "0", "0", None),
+    ],
+)
+def test_variance(actual, budget, expected, expected_pct):
+    assert Money.variance(Money(actual), Money(budget)) == Money(expected)
+    assert Money.variance_pct(Money(actual), Money(budget)) == expected_pct
+
+
+def test_apply_indices():
+    indices = [(date(2025, 1, 1), Decimal("3.3")), (date(2024, 1, 1), "2.5")]
+
+    # 1000.00 * 1.025 ==> 1025.00, * 1.033 ==> 1058.825 ==> 1058.82 (half even)
+    assert Money(1000).apply_indices(indices) == Money("1058.82")
+    assert Money("999.99").apply_indices(indices) == Money("1058.81")
+    assert Money("999.99").apply_indices(indices, round_only_at_end=True) == Money(
+        "1058.81"
+    )
+    assert Money(1000).apply_indices([]) == Money(1000)
+    assert Money(100).apply_indices([(1, -10), (2, 10)]) == Money(99)
+
+
+def test_apply_indices_round_only_at_end():
+    indices = [(period, "0.5") for period in range(3)]
+
+    # 10.05, 10.10, 10.15 when rounding per period vs 10.150751... exactly
+    assert Money(10).apply_indices(indices) == Money("10.15")
+    assert Money(10).apply_indices(indices, round_only_at_end=True) == Money("10.15")
+    assert Money(1).apply_indices(indices) == Money("1.00")
+    assert Money(1).apply_indices(indices, round_only_at_end=True) == Money("1.02")
+
+
+def test_prorate():
+    rent = Money(3100)
+    assert rent.prorate(
+        date(2024, 1, 1), date(2024, 2, 1), date(2024, 1, 11), date(2024, 2, 1)
+    ) == Money(2100)
+    # The sub-range is clipped to the period
+    assert rent.prorate(
+        date(2024, 1, 1), date(2024, 2, 1), date(2023, 12, 1), date(2024, 1, 2)
+    ) == Money(100)
+    assert rent.prorate(
+        date(2024, 1, 1), date(2024, 2, 1), date(2024, 3, 1), date(2024, 4, 1)
+    ) == Money(0)
+
+
+def test_prorate_30_360():
+    rent = Money(3000)
+    assert rent.prorate(
+        date(2024, 1, 1),
+        date(2024, 2, 1),
+        date(2024, 1, 16),
+        date(2024, 2, 1),
+        day_count="30/360",
+    ) == Money(1500)
+
+
+def test_prorate_empty_period():
+    with pytest.raises(ValueError, match="at least one day"):
+        Money(100).prorate(
+            date(2024, 1, 1), date(2024, 1, 1), date(2024, 1, 1), date(2024, 1, 1)
+        )
+
+
+def test_max_and_min():
+    items = [None, Money(3), Money("-1.5"), None, Money(2)]
+
+    assert Money.max(items) == Money(3)
+    assert Money.min(*items) == Money("-1.5")
+    assert Money.max([None], default=Money(0)) == Money(0)
+    assert Money.min([], default=None) is None
+    with pytest.raises(ValueError, match="Insufficient arguments"):
+        Money.min([None])
+    with pytest.raises(TypeError):
+        Money.max([Money(1), "2"])
@@ -0,0 +1,449 @@
+import decimal as _decimal
+from decimal import Decimal as _Decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_init():
+    subject = _money.MoneyArray([_money.Money(1), "2.50", 3, _Decimal("-0.5")])
+
+    assert len(subject) == 4
+    assert subject.tolist() == [
+        _money.Money(1),
+        _money.Money("2.5"),
+        _money.Money(3),
+        _money.Money("-0.5"),
+    ]
+
+
+def test_init_empty():
+    subject = _money.MoneyArray()
+
+    assert len(subject) == 0
+    assert subject.sum() == _money.Money(0)
+
+
+def test_init_invalid():
+    with _pytest.raises(ValueError):
+        _money.MoneyArray(["abc"])
+
+
+def test_repr():
+    subject = _money.MoneyArray(["1.50", 2])
+
+    assert repr(subject) == "MoneyArray(['1.50', '2'])"
+
+
+def test_append_extend():
+    subject = _money.MoneyArray()
+
+    subject.append(_money.Money(1))
+    subject.append("2")
+    subject.extend([3, _money.Money(4)])
+    subject.extend(_money.MoneyArray([5]))
+
+    assert list(subject) == [_money.Money(value) for value in range(1, 6)]
+
+
+def test_extend_with_itself():
+    subject = _money.MoneyArray([1, 2], "EUR")
+
+    subject.extend(subject)
+    subject.extend(money for money in subject)
+
+    assert subject.tolist() == [_money.Money(value, "EUR") for value in [1, 2] * 4]
+
+
+@_pytest.mark.parametrize("index, expected", [(0, 1), (2, 3), (-1, 4), (-4, 1)])
+def test_getitem(index, expected):
+    subject = _money.MoneyArray([1, 2, 3, 4])
+
+    assert subject[index] == _money.Money(expected)
+
+
+@_pytest.mark.parametrize("index", [4, -5])
+def test_getitem_out_of_range(index):
+    subject = _money.MoneyArray([1, 2, 3, 4])
+
+    with _pytest.raises(IndexError):
+        subject[index]
+
+
+@_pytest.mark.parametrize(
+    "index",
+    [slice(1, 3), slice(None, None, 2), slice(None, None, -1), slice(-2, None)],
+)
+def test_getitem_slice(index):
+    values = [1, 2, 3, 4, 5]
+    subject = _money.MoneyArray(values)
+
+    result = subject[index]
+
+    assert isinstance(result, _money.MoneyArray)
+    assert result.tolist() == [_money.Money(value) for value in values[index]]
+
+
+def test_setitem():
+    subject = _money.MoneyArray([1, 2, 3])
+
+    subject[-1] = "3.33"
+
+    assert subject[2] == _money.Money("3.33")
+    with _pytest.raises(IndexError):
+        subject[3] = 1
+
+
+def test_sum():
+    subject = _money.MoneyArray(["0.1", "0.2", "-0.3", "1000000.005"])
+
+    assert subject.sum() == _money.Money("1000000.005")
+
+
+def test_add():
+    subject = _money.MoneyArray([1, "2.5", -3])
+
+    assert subject.add(_money.MoneyArray([1, 1, 1])).tolist() == [
+        _money.Money(2),
+        _money.Money("3.5"),
+        _money.Money(-2),
+    ]
+    assert subject.add(_money.Money("0.5")).tolist() == [
+        _money.Money("1.5"),
+        _money.Money(3),
+        _money.Money("-2.5"),
+    ]
+
+
+def test_add_length_mismatch():
+    with _pytest.raises(ValueError, match="Array lengths differ: 2 and 1"):
+        _money.MoneyArray([1, 2]).add(_money.MoneyArray([1]))
+
+
+def test_mul_scalar():
+    result = _money.MoneyArray(["1.10", -2]).mul_scalar("0.5")
+
+    assert result.tolist() == [_money.Money("0.55"), _money.Money(-1)]
+
+
+def test_cumsum():
+    subject = _money.MoneyArray(["0.1", "0.2", "-0.3"])
+
+    assert subject.cumsum().tolist() == [
+        _money.Money("0.1"),
+        _money.Money("0.3"),
+        _money.Money(0),
+    ]
+    assert len(_money.MoneyArray().cumsum()) == 0
+
+
+def test_round():
+    values = ["1.005", "-1.015", "2.344"]
+    subject = _money.MoneyArray(values)
+
+    result = subject.round(2)
+
+    assert result.tolist() == [_money.Money(value).round(2) for value in values]
+    assert subject[0] == _money.Money("1.005")
+
+
+@_pytest.mark.parametrize(
+    "strategy",
+    [
+        _decimal.ROUND_HALF_EVEN,
+        _decimal.ROUND_HALF_UP,
+        _decimal.ROUND_HALF_DOWN,
+        _decimal.ROUND_UP,
+        _decimal.ROUND_DOWN,
+        _decimal.ROUND_CEILING,
+        _decimal.ROUND_FLOOR,
+    ],
+)
+def test_round_to_cents(strategy):
+    values = ["1.005", "-1.005", "2.015", "-2.3449", "0.001", "7"]
+    subject = _money.MoneyArray(values)
+
+    result = subject.round_to_cents(strategy)
+
+    assert [money.amount for money in result] == [
+        _Decimal(value).quantize(_Decimal("0.01"), rounding=strategy)
+        for value in values
+    ]
+
+
+def test_round_to_cents_default():
+    subject = _money.MoneyArray(["0.125", "0.135"])
+
+    assert subject.round_to_cents().tolist() == [
+        _money.Money("0.12"),
+        _money.Money("0.14"),
+    ]
+
+
+def test_round_to_cents_unknown_strategy():
+    with _pytest.raises(ValueError, match="Unknown rounding strategy"):
+        _money.MoneyArray([1]).round_to_cents("ROUND_05UP")
+
+
+def test_rounded_to_money_field_precision():
+    subject = _money.MoneyArray(["1.0000000000005", "2.1234567890125"])
+
+    assert subject.rounded_to_money_field_precision().tolist() == [
+        _money.Money("1.000000000000"),
+        _money.Money("2.123456789012"),
+    ]
+    assert subject.rounded_to_money_field_precision(4).tolist() == [
+        _money.Money("1.0000"),
+        _money.Money("2.1235"),
+    ]
+
+
+@_pytest.mark.parametrize(
+    "condition, expected_sum, expected_count",
+    [
+        (0, _money.Money("15.5"), 2),
+        (_money.Money(10), _money.Money("10.5"), 1),
+        (_Decimal("-5"), _money.Money("15"), 4),
+        (lambda money: money.amount < 0, _money.Money("-5.5"), 2),
+        ([True, False, False, True, False], _money.Money("15.5"), 2),
+        ((False,) * 5, _money.Money(0), 0),
+    ],
+)
+def test_sum_where(condition, expected_sum, expected_count):
+    subject = _money.MoneyArray(["5", "-0.5", "0", "10.5", "-5"])
+
+    assert subject.sum_where(condition) == expected_sum
+    assert subject.count_where(condition) == expected_count
+
+
+def test_sum_where_mask_length():
+    with _pytest.raises(ValueError, match="Mask length"):
+        _money.MoneyArray([1, 2]).sum_where([True])
+
+
+class _BoolArray:
+    # Like a NumPy bool array, which converts to a number if it has one element
+    def __init__(self, values):
+        self.values = values
+
+    def __array__(self):
+        return self.values
+
+    def __len__(self):
+        return len(self.values)
+
+    def __iter__(self):
+        return iter(self.values)
+
+    def __float__(self):
+        return float(self.values[0])
+
+
+def test_sum_where_single_element_mask():
+    subject = _money.MoneyArray(["0.5"])
+
+    assert subject.sum_where(_BoolArray([True])) == _money.Money("0.5")
+    assert subject.sum_where(_BoolArray([False])) == _money.Money(0)
+    assert subject.sum_where([True]) == _money.Money("0.5")
+
+
+@_pytest.mark.parametrize("condition", ["abc", [1, None], True, False])
+def test_sum_where_invalid(condition):
+    with _pytest.raises(TypeError):
+        _money.MoneyArray([1, 2]).sum_where(condition)
+
+
+def test_group_sum():
+    subject = _money.MoneyArray(["1.5", "2", "-0.5", "4", "0.25"])
+
+    result = subject.group_sum(["b", "a", "b", ("c", 1), "a"])
+
+    assert result == {
+        "b": _money.Money(1),
+        "a": _money.Money("2.25"),
+        ("c", 1): _money.Money(4),
+    }
+    assert list(result) == ["b", "a", ("c", 1)]
+
+
+@_pytest.mark.parametrize("labels", [["a"], ["a", "b", "c"], iter(["a"])])
+def test_group_sum_length_mismatch(labels):
+    with _pytest.raises(ValueError, match="Labels length"):
+        _money.MoneyArray([1, 2]).group_sum(labels)
+
+
+def test_currency():
+    subject = _money.MoneyArray([_money.Money(1, "EUR"), 2])
+    subject.append(_money.Money(3))
+    subject.extend(_money.MoneyArray(["0.5"], "EUR"))
+
+    assert subject.currency == _money.Currency.EUR
+    assert repr(subject) == "MoneyArray(['1', '2', '3', '0.5'], currency='EUR')"
+    assert subject.sum().currency == _money.Currency.EUR
+    assert subject[1].currency == _money.Currency.EUR
+    assert subject[1:].currency == _money.Currency.EUR
+    assert {money.currency for money in subject} == {_money.Currency.EUR}
+    assert subject.sum_where(1).currency == _money.Currency.EUR
+    assert subject.add(1).currency == _money.Currency.EUR
+    assert subject.cumsum().currency == _money.Currency.EUR
+    assert subject.round_to_cents().currency == _money.Currency.EUR
+    assert _money.MoneyArray([1], "USD").currency == _money.Currency.USD
+    assert _money.MoneyArray([1]).currency is None
+
+
+@_pytest.mark.parametrize(
+    "action",
+    [
+        lambda subject: subject.append(_money.Money(1, "USD")),
+        lambda subject: subject.extend([_money.Money(1, "USD")]),
+        lambda subject: subject.extend(_money.MoneyArray([1], "USD")),
+        lambda subject: subject.__setitem__(0, _money.Money(1, "USD")),
+        lambda subject: subject.add(_money.Money(1, "USD")),
+        lambda subject: subject.add(_money.MoneyArray([1], "USD")),
+        lambda subject: subject.sum_where(_money.Money(0, "USD")),
+    ],
+)
+def test_currency_mismatch(action):
+    subject = _money.MoneyArray([1], "EUR")
+
+    with _pytest.raises(_money.CurrencyMismatchError):
+        action(subject)
+    assert subject.tolist() == [_money.Money(1, "EUR")]
+
+
+def test_vat_array_init():
+    values = [_money.MoneyWithVAT(100, 19), _money.MoneyWithVAT(10, "0.7")]
+
+    subject = _money.MoneyWithVATArray(values)
+
+    assert len(subject) == 2
+    assert subject.tolist() == values
+    assert list(subject) == values
+    assert subject.net.tolist() == [_money.Money(100), _money.Money(10)]
+    assert subject.tax.tolist() == [_money.Money(19), _money.Money("0.7")]
+
+
+def test_vat_array_init_invalid():
+    with _pytest.raises(TypeError):
+        _money.MoneyWithVATArray([_money.Money(1)])
+
+
+def test_vat_array_repr():
+    subject = _money.MoneyWithVATArray([_money.MoneyWithVAT(100, 19)])
+
+    assert repr(subject) == "MoneyWithVATArray([('100', '19')])"
+
+
+def test_vat_array_indexing():
+    values = [_money.MoneyWithVAT(value, value) for value in range(5)]
+    subject = _money.MoneyWithVATArray(values)
+
+    assert subject[-1] == values[-1]
+    assert subject[1::2].tolist() == values[1::2]
+    with _pytest.raises(IndexError):
+        subject[5]
+
+
+def test_vat_array_append_extend():
+    subject = _money.MoneyWithVATArray()
+
+    subject.append(_money.MoneyWithVAT(1, 0))
+    subject.extend([_money.MoneyWithVAT(2, 0)])
+    subject.extend(_money.MoneyWithVATArray([_money.MoneyWithVAT(3, 0)]))
+
+    assert subject.net.tolist() == [_money.Money(value) for value in (1, 2, 3)]
+
+
+def test_vat_array_sum():
+    subject = _money.MoneyWithVATArray(
+        [_money.MoneyWithVAT(100, 19), _money.MoneyWithVAT("0.1", "0.007")]
+    )
+
+    result = subject.sum()
+
+    assert result.net == _money.Money("100.1")
+    assert result.tax == _money.Money("19.007")
+
+
+def test_vat_array_group_by_rate():
+    subject = _money.MoneyWithVATArray(
+        [
+            _money.MoneyWithVAT(100, 19),
+            _money.MoneyWithVAT(10, "0.7"),
+            _money.MoneyWithVAT(50, "9.5"),
+            _money.MoneyWithVAT(5, 0),
+        ]
+    )
+
+    result = subject.group_by_rate()
+
+    assert list(result) == [_Decimal("0.19"), _Decimal("0.07"), _Decimal("0")]
+    assert result[_Decimal("0.19")].net == _money.Money(150)
+    assert result[_Decimal("0.19")].tax == _money.Money("28.5")
+    assert result[_Decimal("0.07")].tax == _money.Money("0.7")
+    assert result[_Decimal("0")].net == _money.Money(5)
+
+
+def test_vat_array_arithmetic():
+    subject = _money.MoneyWithVATArray(
+        [_money.MoneyWithVAT(100, 19), _money.MoneyWithVAT(10, "0.7")]
+    )
+
+    added = subject.add(subject)
+    assert (added.net.tolist(), added.tax.tolist()) == (
+        [_money.Money(200), _money.Money(20)],
+        [_money.Money(38), _money.Money("1.4")],
+    )
+
+    shifted = subject.add(_money.MoneyWithVAT(1, 1))
+    assert shifted.tax.tolist() == [_money.Money(20), _money.Money("1.7")]
+
+    halved = subject.mul_scalar("0.5")
+    assert halved.net.tolist() == [_money.Money(50), _money.Money(5)]
+
+    totals = subject.cumsum()
+    assert (totals[-1].net, totals[-1].tax) == (subject.sum().net, subject.sum().tax)
+
+
+def test_vat_array_add_length_mismatch():
+    with _pytest.raises(ValueError, match="Array lengths differ"):
+        _money.MoneyWithVATArray([_money.MoneyWithVAT(1, 0)]).add(
+            _money.MoneyWithVATArray()
+        )
+
+
+def test_vat_array_currency():
+    subject = _money.MoneyWithVATArray([_money.MoneyWithVAT(100, 19, "CHF")])
+    subject.append(_money.MoneyWithVAT(10, 1))
+
+    assert subject.currency == _money.Currency.CHF
+    assert repr(subject) == (
+        "MoneyWithVATArray([('100', '19'), ('10', '1')], currency='CHF')"
+    )
+    assert subject.sum().currency == _money.Currency.CHF
+    assert subject[1].currency == _money.Currency.CHF
+    assert subject.net.currency == _money.Currency.CHF
+    assert subject.add(subject).currency == _money.Currency.CHF
+    assert [value.currency for value in subject.group_by_rate().values()] == [
+        _money.Currency.CHF,
+        _money.Currency.CHF,
+    ]
+
+    with _pytest.raises(_money.CurrencyMismatchError):
+        subject.append(_money.MoneyWithVAT(1, 0, "EUR"))
+    with _pytest.raises(_money.CurrencyMismatchError):
+        subject.extend(_money.MoneyWithVATArray(currency="EUR"))
+    with _pytest.raises(_money.CurrencyMismatchError):
+        subject.add(_money.MoneyWithVAT(1, 0, "EUR"))
+    assert len(subject) == 2
+
+
+def test_vat_array_extend_with_itself():
+    subject = _money.MoneyWithVATArray([_money.MoneyWithVAT(100, 19)])
+
+    subject.extend(subject)
+    subject.extend(value for value in subject)
+
+    assert len(subject) == 4
+    assert subject.sum().net == _money.Money(400)
+    assert subject.sum().tax == _money.Money(76)
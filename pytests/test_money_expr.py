@@ -0,0 +1,81 @@
+import decimal as _decimal
+import fractions as _fractions
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_materialize_without_operations():
+    assert _money.MoneyExpr(_money.Money("1.005")).materialize() == _money.Money("1.00")
+    assert _money.MoneyExpr(_money.Money("1.005")).materialize(3) == _money.Money(
+        "1.005"
+    )
+
+
+def test_chain_is_immutable():
+    start = _money.MoneyExpr(_money.Money(100))
+    chained = start.mul("1.05").sub(_money.Money(5))
+
+    assert len(start) == 0
+    assert len(chained) == 2
+    assert chained.materialize() == _money.Money(100)
+
+
+def test_single_final_rounding():
+    expr = (
+        _money.MoneyExpr(_money.Money(100))
+        .div(3)
+        .mul(_decimal.Decimal("1.035"))
+        .mul(3)
+        .mul(_fractions.Fraction(97, 100))
+    )
+    assert expr.materialize(4) == _money.Money("100.3950")
+
+
+def test_add_after_division_stays_exact():
+    expr = _money.MoneyExpr(_money.Money(1)).div(3).add(_money.Money(1)).mul(3)
+    assert expr.materialize(27) == _money.Money(4)
+
+
+def test_div_by_zero():
+    with _pytest.raises(ZeroDivisionError):
+        _money.MoneyExpr(_money.Money(1)).div(0)
+
+
+def test_invalid_operand():
+    with _pytest.raises(ValueError):
+        _money.MoneyExpr(_money.Money(1)).mul("abc")
+
+
+@_pytest.mark.parametrize(
+    "operation",
+    [
+        lambda expr: expr.mul(2),
+        lambda expr: expr.add(expr.materialize(0)),
+        lambda expr: expr.div(_decimal.Decimal("0.5")),
+    ],
+)
+def test_out_of_range(operation):
+    expr = _money.MoneyExpr(_money.Money("79228162514264337593543950335"))
+
+    with _pytest.raises(_money.InvalidAmountError, match="out of range"):
+        operation(expr).materialize()
+
+
+def test_currency():
+    eur = _money.Money(1, "EUR")
+    expr = _money.MoneyExpr(_money.Money(1)).add(eur).mul(3).div(2)
+
+    assert expr.materialize() == _money.Money(3, "EUR")
+    assert _money.MoneyExpr(eur).sub(1).materialize() == _money.Money(0, "EUR")
+    assert repr(_money.MoneyExpr(eur)) == "MoneyExpr(Money('1', 'EUR'), operations=0)"
+
+
+@_pytest.mark.parametrize("operation", ["add", "sub"])
+def test_currency_mismatch(operation):
+    expr = _money.MoneyExpr(_money.Money(1, "CHF"))
+
+    with _pytest.raises(
+        _money.CurrencyMismatchError, match="Can't combine CHF with EUR"
+    ):
+        getattr(expr, operation)(_money.Money(1, "EUR"))
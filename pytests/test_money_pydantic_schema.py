@@ -2,39 +2,50 @@ import alasco_money as _money
 import pydantic as _pydantic
 
 
+MONEY_DEF = {"example": "123.123456789012", "type": "string"}
+
+
 def test_money_schema():
     class Container(_pydantic.BaseModel):
         value: _money.Money
 
-    assert Container.model_json_schema()["properties"]["value"] == {
-        "example": "123.123456789012",
-        "title": "Value",
-        "type": "string",
-    }
+    schema = Container.model_json_schema()
+
+    assert schema["properties"]["value"] == {"$ref": "#/$defs/Money"}
+    assert schema["$defs"] == {"Money": MONEY_DEF}
 
 
 def test_money_vat_schema():
     class Container(_pydantic.BaseModel):
         value: _money.MoneyWithVAT
+        other: _money.Money
 
-    assert Container.model_json_schema()["properties"]["value"] == {
-        "properties": {
-            "net": {
-                "example": "123.123456789012",
-                "title": "Net amount",
-                "type": "string",
-            },
-            "tax": {
-                "example": "123.123456789012",
-                "title": "Tax amount",
-                "type": "string",
+    schema = Container.model_json_schema()
+
+    assert schema["properties"]["value"] == {"$ref": "#/$defs/MoneyWithVAT"}
+    assert schema["$defs"] == {
+        "Money": MONEY_DEF,
+        "MoneyWithVAT": {
+            "example": {"net": "123.123456789012", "tax": "23.456789012345"},
+            "properties": {
+                "net": {"$ref": "#/$defs/Money", "title": "Net amount"},
+                "tax": {"$ref": "#/$defs/Money", "title": "Tax amount"},
             },
+            "required": ["net", "tax"],
+            "type": "object",
         },
-        "title": "Value",
-        "type": "object",
     }
 
 
+def test_money_vat_schema_serialization():
+    class Container(_pydantic.BaseModel):
+        value: _money.MoneyWithVAT
+
+    assert Container.model_json_schema(mode="serialization")["$defs"] == (
+        Container.model_json_schema()["$defs"]
+    )
+
+
 def test_money_vat_ratio_schema():
     class Container(_pydantic.BaseModel):
         value: _money.MoneyWithVATRatio
@@ -0,0 +1,89 @@
+from decimal import Decimal as _Decimal
+
+import alasco_money as _money
+import pydantic as _pydantic
+import pytest as _pytest
+
+
+class Container(_pydantic.BaseModel):
+    money: _money.Money
+    money_with_vat: _money.MoneyWithVAT
+    ratio: _money.MoneyWithVATRatio
+
+
+def _container():
+    return Container(
+        money=_money.Money("1.5"),
+        money_with_vat=_money.MoneyWithVAT(100, 19),
+        ratio=_money.MoneyWithVATRatio(net_ratio="0.5", gross_ratio="0.25"),
+    )
+
+
+def test_json_mode():
+    container = _container()
+
+    assert container.model_dump(mode="json") == {
+        "money": "1.500000000000",
+        "money_with_vat": {"net": "100.000000000000", "tax": "19.000000000000"},
+        "ratio": {"net_ratio": "0.5", "gross_ratio": "0.25"},
+    }
+
+
+def test_python_mode_keeps_objects_by_default():
+    container = _container()
+
+    result = container.model_dump()
+
+    assert result["money"] is container.money
+    assert result["money_with_vat"] is container.money_with_vat
+    assert result["ratio"] is container.ratio
+
+
+def test_python_mode_serializes_when_requested():
+    container = _container()
+
+    _money.set_pydantic_serialization("always")
+    try:
+        result = container.model_dump()
+        json_result = container.model_dump(mode="json")
+    finally:
+        _money.set_pydantic_serialization("json")
+
+    assert result == {
+        "money": _Decimal("1.5"),
+        "money_with_vat": {"net": _Decimal(100), "tax": _Decimal(19)},
+        "ratio": {"net_ratio": _Decimal("0.5"), "gross_ratio": _Decimal("0.25")},
+    }
+    assert isinstance(result["money"], _Decimal)
+    assert json_result["money"] == "1.500000000000"
+
+
+def test_currency_round_trip():
+    container = Container(
+        money=_money.Money("1.5", "EUR"),
+        money_with_vat=_money.MoneyWithVAT(100, 19, "CHF"),
+        ratio=_money.MoneyWithVATRatio(net_ratio="0.5", gross_ratio="0.25"),
+    )
+
+    json_result = container.model_dump(mode="json")
+    assert json_result["money"] == {"amount": "1.500000000000", "currency": "EUR"}
+    assert json_result["money_with_vat"]["currency"] == "CHF"
+
+    result = Container.model_validate_json(container.model_dump_json())
+    assert result.money == _money.Money("1.5", "EUR")
+    assert result.money_with_vat.currency == _money.Currency.CHF
+
+    _money.set_pydantic_serialization("always")
+    try:
+        python_result = container.model_dump()
+    finally:
+        _money.set_pydantic_serialization("json")
+
+    assert python_result["money"] == {"amount": _Decimal("1.5"), "currency": "EUR"}
+    assert python_result["money_with_vat"]["currency"] == "CHF"
+    assert Container.model_validate(python_result) == container
+
+
+def test_unknown_mode():
+    with _pytest.raises(ValueError, match="Unknown pydantic serialization mode"):
+        _money.set_pydantic_serialization("python")
@@ -1,3 +1,5 @@
+import typing as _typing
+
 import alasco_money as _money
 import pydantic as _pydantic
 import pytest as _pytest
@@ -66,3 +68,89 @@ def test_money_vat_ratio_validator_ok():
 def test_money_vat_ratio_validator_fail(data):
     with _pytest.raises(_pydantic.ValidationError):
         _pydantic.TypeAdapter(_money.MoneyWithVATRatio).validate_python(data)
+
+
+class _ConstrainedModel(_pydantic.BaseModel):
+    positive: _typing.Annotated[_money.Money, _pydantic.Field(gt=0)] = _money.Money(1)
+    capped: _typing.Annotated[_money.Money, _pydantic.Field(ge=0, le=1000)] = (
+        _money.Money(0)
+    )
+    cents: _typing.Annotated[_money.Money, _pydantic.Field(multiple_of=0.05)] = (
+        _money.Money(0)
+    )
+    invoice: _typing.Annotated[_money.MoneyWithVAT, _pydantic.Field(lt=10000)] = (
+        _money.MoneyWithVAT()
+    )
+
+
+def test_money_field_constraints_ok():
+    result = _ConstrainedModel(
+        positive="0.01", capped=1000, cents="10.05", invoice={"net": 100, "tax": 19}
+    )
+
+    assert result.positive == _money.Money("0.01")
+    assert result.capped == _money.Money(1000)
+    assert result.cents == _money.Money("10.05")
+    assert result.invoice.gross == _money.Money(119)
+
+
+@_pytest.mark.parametrize(
+    "data, error_type",
+    [
+        ({"positive": 0}, "greater_than"),
+        ({"capped": "-0.01"}, "greater_than_equal"),
+        ({"capped": "1000.01"}, "less_than_equal"),
+        ({"cents": "10.07"}, "multiple_of"),
+        ({"invoice": {"net": 9000, "tax": 1710}}, "less_than"),
+    ],
+)
+def test_money_field_constraints_fail(data, error_type):
+    with _pytest.raises(_pydantic.ValidationError) as exc_info:
+        _ConstrainedModel(**data)
+
+    assert [error["type"] for error in exc_info.value.errors()] == [error_type]
+
+
+class _EuroInvoice(_pydantic.BaseModel):
+    total: _money.MoneyWithVAT["EUR"]
+    prepaid: _money.Money["EUR"]
+
+
+def test_currency_annotations():
+    eur = _money.Currency.EUR
+    money_type, constraint = _typing.get_args(_money.Money["EUR"])
+    assert money_type is _money.Money
+    assert constraint.currency == eur
+    assert repr(constraint) == "CurrencyConstraint('EUR')"
+    assert _typing.get_args(_money.MoneyWithVAT["CHF"])[0] is _money.MoneyWithVAT
+
+    invoice = _EuroInvoice(
+        total={"net": 100, "tax": 19, "currency": "EUR"}, prepaid=_money.Money(50, eur)
+    )
+    assert invoice.prepaid == _money.Money(50, eur)
+    assert invoice.total.currency == eur
+
+    invoice = _EuroInvoice(total={"net": 100, "tax": 19}, prepaid="50")
+    assert invoice.prepaid == _money.Money(50)
+
+
+@_pytest.mark.parametrize(
+    "data",
+    [
+        {"total": {"net": 100, "tax": 19}, "prepaid": _money.Money(50, "CHF")},
+        {
+            "total": {"net": 100, "tax": 19},
+            "prepaid": {"amount": "1", "currency": "CHF"},
+        },
+        {"total": {"net": 100, "tax": 19, "currency": "CHF"}, "prepaid": "50"},
+    ],
+)
+def test_currency_annotations_reject_other_currencies(data):
+    with _pytest.raises(_pydantic.ValidationError, match="Can't combine EUR with CHF"):
+        _EuroInvoice(**data)
+
+
+@_pytest.mark.parametrize("currency", ["eur", "EURO", "", "ABC"])
+def test_currency_annotations_invalid(currency):
+    with _pytest.raises(TypeError, match="Invalid currency"):
+        _money.Money[currency]
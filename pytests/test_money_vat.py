@@ -1,4 +1,8 @@
+import copy as _copy
+import dataclasses as _dataclasses
 import decimal as _decimal
+import fractions as _fractions
+import sqlite3 as _sqlite3
 
 import alasco_money as _money
 import pytest as _pytest
@@ -14,6 +18,50 @@ def test_default_init(subject):
     assert subject.gross.amount == 0
 
 
+@_pytest.mark.parametrize("value", [float("nan"), float("inf")])
+def test_init_non_finite(value):
+    with _pytest.raises(_money.InvalidAmountError):
+        _money.MoneyWithVAT(value, 0)
+    with _pytest.raises(_money.InvalidAmountError):
+        _money.MoneyWithVAT(0, value)
+
+
+@_pytest.mark.parametrize(
+    "subject, expected",
+    [
+        (_money.MoneyWithVAT(100, 19), "net 100.00 + VAT 19.00"),
+        (_money.MoneyWithVAT("4.444", "2.222"), "net 4.44 + VAT 2.22"),
+    ],
+)
+def test_str(subject, expected):
+    assert str(subject) == expected
+
+
+@_pytest.mark.parametrize(
+    "spec, expected",
+    [
+        ("", "net 1234.44 + VAT 234.56"),
+        (".2f", "1469.00"),
+        (",n", "1,469.00"),
+        ("net:,.3n", "1,234.444"),
+        ("tax:n", "234.56"),
+        ("gross:>10n", "   1469.00"),
+        ("net:", "1234.44"),
+    ],
+)
+def test_format_spec(spec, expected):
+    assert format(_money.MoneyWithVAT("1234.444", "234.556"), spec) == expected
+
+
+def test_format_spec_invalid():
+    with _pytest.raises(ValueError):
+        format(_money.MoneyWithVAT(1, 0), "net:x")
+
+
+def test_repr():
+    assert repr(_money.MoneyWithVAT(100, 19)) == "MoneyWithVAT(net='100', tax='19')"
+
+
 @_pytest.mark.parametrize(
     "subject, expected",
     [
@@ -31,6 +79,13 @@ def test_neg(subject, expected):
     assert -subject == expected
 
 
+def test_pos():
+    subject = +_money.MoneyWithVAT(_decimal.Decimal("-0.00"), _decimal.Decimal("-19"))
+
+    assert str(subject.net.amount) == "0.00"
+    assert subject.tax == _money.Money(-19)
+
+
 @_pytest.mark.parametrize(
     "subject, expected",
     [
@@ -57,6 +112,39 @@ def test_lt(subject, expected):
     assert subject < expected
 
 
+def test_compare_with_money_and_decimal():
+    invoice = _money.MoneyWithVAT(10000, 1900)
+
+    assert invoice > _money.Money("10000")
+    assert _money.Money("10000") < invoice
+    assert invoice >= _money.Money(11900)
+    assert invoice <= _decimal.Decimal(11900)
+    assert invoice > 0
+    assert 0 < invoice
+    assert _money.MoneyWithVAT(-1, 0) < 0.5
+
+
+@_pytest.mark.parametrize(
+    "other", [_money.Money(11900), _decimal.Decimal(11900), 11900]
+)
+def test_equality_only_with_money_with_vat(other):
+    invoice = _money.MoneyWithVAT(10000, 1900)
+
+    assert invoice != other
+    assert not invoice == other
+    assert invoice == _money.MoneyWithVAT(11000, 900)
+    assert hash(invoice) == hash(_money.MoneyWithVAT(11000, 900))
+
+
+@_pytest.mark.parametrize("other", [None, "11900", {}, object()])
+def test_compare_with_other_types(other):
+    invoice = _money.MoneyWithVAT(10000, 1900)
+
+    assert invoice != other
+    with _pytest.raises(TypeError):
+        invoice < other
+
+
 @_pytest.mark.parametrize(
     "first, second, result",
     [
@@ -114,6 +202,240 @@ def test_add_non_money_vat(other, should_fail):
         other - this
 
 
+def test_delta():
+    diff, abs_diff = _money.MoneyWithVAT(100, 10).delta(_money.MoneyWithVAT(90, 19))
+
+    assert (diff.net, diff.tax) == (_money.Money(10), _money.Money(-9))
+    assert (abs_diff.net, abs_diff.tax) == (_money.Money(10), _money.Money(9))
+
+
+@_pytest.mark.parametrize(
+    "method, other, expected",
+    [
+        ("add_opt", None, _money.MoneyWithVAT(3, 1)),
+        ("add_opt", _money.MoneyWithVAT(1, 1), _money.MoneyWithVAT(4, 2)),
+        ("sub_opt", None, _money.MoneyWithVAT(3, 1)),
+        ("sub_opt", _money.MoneyWithVAT(1, 1), _money.MoneyWithVAT(2, 0)),
+    ],
+)
+def test_add_sub_opt(method, other, expected):
+    result = getattr(_money.MoneyWithVAT(3, 1), method)(other)
+    assert result.net == expected.net
+    assert result.tax == expected.tax
+
+
+def test_add_opt_non_money_vat():
+    with _pytest.raises(TypeError):
+        _money.MoneyWithVAT(3, 1).add_opt(_money.Money(1))
+
+
+@_pytest.mark.parametrize(
+    "subject, money, expected",
+    [
+        (_money.MoneyWithVAT(100, 19), _money.Money(100), _money.MoneyWithVAT(200, 38)),
+        (_money.MoneyWithVAT(0, 0), _money.Money(100), _money.MoneyWithVAT(100, 0)),
+        (
+            _money.MoneyWithVAT(100, 19),
+            _money.Money(-50),
+            _money.MoneyWithVAT(50, "9.5"),
+        ),
+    ],
+)
+def test_add_to_net(subject, money, expected):
+    result = subject.add_to_net(money)
+    assert result.net == expected.net
+    assert result.tax == expected.tax
+
+
+def test_add_to_tax():
+    result = _money.MoneyWithVAT(100, 19).add_to_tax(_money.Money(1))
+    assert result.net == _money.Money(100)
+    assert result.tax == _money.Money(20)
+
+
+def test_add_net_only():
+    result = _money.MoneyWithVAT(100, 19).add_net_only(_money.Money(10))
+    assert result.net == _money.Money(110)
+    assert result.tax == _money.Money(19)
+
+
+@_pytest.mark.parametrize("method", ["add_to_net", "add_to_tax", "add_net_only"])
+def test_add_combinators_require_money(method):
+    with _pytest.raises(TypeError):
+        getattr(_money.MoneyWithVAT(100, 19), method)(_money.MoneyWithVAT(1, 1))
+
+
+@_pytest.mark.parametrize(
+    "subject, rate, expected",
+    [
+        (_money.MoneyWithVAT(100, 7), "0.19", _money.MoneyWithVAT(100, 19)),
+        (_money.MoneyWithVAT(100, 19), 0, _money.MoneyWithVAT(100, 0)),
+        (_money.MoneyWithVAT(-100, 0), "0.07", _money.MoneyWithVAT(-100, -7)),
+    ],
+)
+def test_with_tax_rate(subject, rate, expected):
+    result = subject.with_tax_rate(_decimal.Decimal(rate))
+    assert result.net == expected.net
+    assert result.tax == expected.tax
+
+
+@_pytest.mark.parametrize(
+    "subject, target, expected",
+    [
+        (_money.MoneyWithVAT(100, 19), "238", _money.MoneyWithVAT(200, 38)),
+        (_money.MoneyWithVAT(100, 19), "0", _money.MoneyWithVAT(0, 0)),
+        (_money.MoneyWithVAT(100, 19), "-119", _money.MoneyWithVAT(-100, -19)),
+    ],
+)
+def test_scale_to_gross(subject, target, expected):
+    result = subject.scale_to_gross(_money.Money(target))
+    assert result.net == expected.net
+    assert result.tax == expected.tax
+
+
+def test_scale_to_gross_is_exact():
+    result = _money.MoneyWithVAT(3, "0.57").scale_to_gross(_money.Money(1))
+    assert result.gross == _money.Money(1)
+    assert result.tax_rate.quantize(_decimal.Decimal("0.0001")) == _decimal.Decimal(
+        "0.19"
+    )
+
+
+def test_scale_to_gross_zero():
+    with _pytest.raises(ZeroDivisionError):
+        _money.MoneyWithVAT(100, -100).scale_to_gross(_money.Money(1))
+
+
+@_pytest.mark.parametrize(
+    "subject, ratios, expected",
+    [
+        (
+            _money.MoneyWithVAT(100, 19),
+            [1, 1, 1],
+            [("33.34", "6.33"), ("33.33", "6.34"), ("33.33", "6.33")],
+        ),
+        (
+            _money.MoneyWithVAT("10.01", "1.90"),
+            [1, 2],
+            [("3.34", "0.63"), ("6.67", "1.27")],
+        ),
+        (
+            _money.MoneyWithVAT(-100, -19),
+            [1, 1, 1],
+            [("-33.34", "-6.33"), ("-33.33", "-6.34"), ("-33.33", "-6.33")],
+        ),
+    ],
+)
+def test_allocate(subject, ratios, expected):
+    parts = subject.allocate(ratios)
+
+    assert [(str(part.net.amount), str(part.tax.amount)) for part in parts] == expected
+    assert sum(part.net.amount for part in parts) == subject.net.amount
+    assert sum(part.tax.amount for part in parts) == subject.tax.amount
+
+
+def test_allocate_keeps_currency():
+    parts = _money.MoneyWithVAT(1, 0, "EUR").allocate([1, 1])
+
+    assert all(part.currency == _money.Currency.EUR for part in parts)
+
+
+def test_allocate_invalid():
+    with _pytest.raises(ValueError, match="Ratios must not all be zero"):
+        _money.MoneyWithVAT(1, 0).allocate([0])
+
+
+@_pytest.mark.parametrize(
+    "subject, rate, expected",
+    [
+        (_money.MoneyWithVAT(100, 19), "0.07", _money.MoneyWithVAT("111.21", "7.79")),
+        (_money.MoneyWithVAT(100, 19), "0", _money.MoneyWithVAT(119, 0)),
+        (_money.MoneyWithVAT(107, 0), "0.07", _money.MoneyWithVAT(100, 7)),
+    ],
+)
+def test_redistribute(subject, rate, expected):
+    result = subject.redistribute(_decimal.Decimal(rate))
+    assert result.gross == subject.gross
+    assert result.rounded_to_cents().net == expected.net
+    assert result.rounded_to_cents().tax == expected.tax
+
+
+def test_redistribute_invalid_rate():
+    with _pytest.raises(ZeroDivisionError):
+        _money.MoneyWithVAT(100, 19).redistribute(-1)
+
+
+@_pytest.mark.parametrize(
+    "subject, rate, expected",
+    [
+        (_money.MoneyWithVAT(100, 7), "0.19", _money.MoneyWithVAT("89.92", "17.08")),
+        (
+            _money.MoneyWithVAT("84.03", "15.97"),
+            "0.07",
+            _money.MoneyWithVAT("93.46", "6.54"),
+        ),
+        (_money.MoneyWithVAT(-119, 0), "0.19", _money.MoneyWithVAT(-100, -19)),
+    ],
+)
+def test_with_tax_rate_stable_gross(subject, rate, expected):
+    result = subject.with_tax_rate(_decimal.Decimal(rate), stable="gross")
+    assert result.gross == subject.gross
+    assert result.rounded_to_cents().net == expected.net
+    assert result.rounded_to_cents().tax == expected.tax
+
+
+def test_with_tax_rate_unknown_stable():
+    with _pytest.raises(ValueError, match="Unknown stable amount 'tax'"):
+        _money.MoneyWithVAT(100, 19).with_tax_rate("0.07", stable="tax")
+
+
+def test_with_tax_rate_invalid():
+    with _pytest.raises(ValueError):
+        _money.MoneyWithVAT(100, 19).with_tax_rate("abc")
+
+
+@_pytest.mark.parametrize(
+    "net, rate, expected",
+    [
+        (100, "0.19", _money.MoneyWithVAT(100, 19)),
+        (
+            _money.Money("10.5"),
+            _decimal.Decimal("0.07"),
+            _money.MoneyWithVAT("10.5", "0.735"),
+        ),
+        ("-50", 0, _money.MoneyWithVAT(-50, 0)),
+    ],
+)
+def test_from_net_and_rate(net, rate, expected):
+    result = _money.MoneyWithVAT.from_net_and_rate(net, rate)
+    assert (result.net, result.tax) == (expected.net, expected.tax)
+
+
+@_pytest.mark.parametrize(
+    "gross, rate, net",
+    [
+        (119, "0.19", "100"),
+        ("100", "0.19", "84.03"),
+        (_money.Money(-107), "0.07", "-100"),
+    ],
+)
+def test_from_gross_and_rate(gross, rate, net):
+    result = _money.MoneyWithVAT.from_gross_and_rate(gross, rate)
+    assert result.gross == _money.Money(gross)
+    assert result.rounded_to_cents().net == _money.Money(net)
+
+
+def test_from_rate_keeps_currency():
+    net = _money.MoneyWithVAT.from_net_and_rate(_money.Money(1, "EUR"), "0.19")
+    gross = _money.MoneyWithVAT.from_gross_and_rate(_money.Money(1, "EUR"), "0.19")
+    assert net.currency == gross.currency == _money.Currency.EUR
+
+
+def test_from_gross_and_rate_invalid_rate():
+    with _pytest.raises(ZeroDivisionError):
+        _money.MoneyWithVAT.from_gross_and_rate(100, -1)
+
+
 @_pytest.mark.parametrize(
     "first, second, result",
     [
@@ -187,7 +509,17 @@ def test_truediv(original, divisor, expected):
     assert (original / divisor) == expected
 
 
-@_pytest.mark.parametrize("zero", [0, _decimal.Decimal(0)])
+def test_mul_fraction():
+    subject = _money.MoneyWithVAT(300, 57) * _fractions.Fraction(1, 3)
+    assert subject == _money.MoneyWithVAT(100, 19)
+
+
+def test_truediv_fraction():
+    subject = _money.MoneyWithVAT(100, 19) / _fractions.Fraction(1, 3)
+    assert subject == _money.MoneyWithVAT(300, 57)
+
+
+@_pytest.mark.parametrize("zero", [0, _decimal.Decimal(0), _fractions.Fraction(0)])
 def test_truediv_zero(zero):
     with _pytest.raises(ZeroDivisionError):
         _money.MoneyWithVAT() / zero
@@ -201,6 +533,61 @@ def test_truediv_no_money():
         _money.MoneyWithVAT() / _money.MoneyWithVAT()
 
 
+def test_from_rows():
+    rows = [("100.10", "19.02"), (_decimal.Decimal(5), 0.5), [-1, _money.Money(0)]]
+    result = _money.MoneyWithVAT.from_rows(iter(rows))
+
+    assert [(value.net, value.tax) for value in result] == [
+        (_money.Money("100.10"), _money.Money("19.02")),
+        (_money.Money(5), _money.Money("0.5")),
+        (_money.Money(-1), _money.Money(0)),
+    ]
+
+
+def test_from_rows_cursor():
+    connection = _sqlite3.connect(":memory:")
+    cursor = connection.execute("SELECT '1.10', '0.21' UNION ALL SELECT '-2', '0'")
+
+    result = _money.MoneyWithVAT.from_rows(cursor)
+
+    assert [(value.net, value.tax) for value in result] == [
+        (_money.Money("1.10"), _money.Money("0.21")),
+        (_money.Money(-2), _money.Money(0)),
+    ]
+
+
+def test_from_rows_currency():
+    result = _money.MoneyWithVAT.from_rows([(_money.Money(1, "CHF"), 0)])
+    assert result[0].currency == _money.Currency.CHF
+
+    with _pytest.raises(_money.CurrencyMismatchError):
+        _money.MoneyWithVAT.from_rows(
+            [(_money.Money(1, "CHF"), _money.Money(0, "EUR"))]
+        )
+
+
+@_pytest.mark.parametrize("rows", [[(1,)], [(1, 2, 3)], [[1, 2, 3]]])
+def test_from_rows_invalid_length(rows):
+    with _pytest.raises(ValueError, match="Expected \\(net, tax\\) rows"):
+        _money.MoneyWithVAT.from_rows(rows)
+
+
+def test_from_rows_invalid_amount():
+    with _pytest.raises(ValueError):
+        _money.MoneyWithVAT.from_rows([("abc", 0)])
+
+
+def test_as_decimals():
+    result = _money.MoneyWithVAT("100.10", "19.019").as_decimals()
+
+    assert all(type(value) is _decimal.Decimal for value in result)
+    assert [value.as_tuple() for value in result] == [
+        _decimal.Decimal("100.10").as_tuple(),
+        _decimal.Decimal("19.019").as_tuple(),
+        _decimal.Decimal("119.119").as_tuple(),
+    ]
+
+
 @_pytest.mark.parametrize(
     "net,tax,rate",
     [
@@ -215,6 +602,35 @@ def test_tax_rate(net, tax, rate):
     assert _money.MoneyWithVAT(net, tax).tax_rate == _decimal.Decimal(rate)
 
 
+@_pytest.mark.parametrize(
+    "net,tax,rate",
+    [
+        (100, 19, "0.1596638655462184873949579832"),
+        (100, 25, "0.2"),
+        (0, 0, 0),
+        (100, -100, 0),
+    ],
+)
+def test_tax_rate_of_gross(net, tax, rate):
+    assert _money.MoneyWithVAT(net, tax).tax_rate_of_gross == _decimal.Decimal(rate)
+
+
+@_pytest.mark.parametrize(
+    "net,tax,net_share,tax_share",
+    [
+        (100, 25, "0.8", "0.2"),
+        (100, 0, 1, 0),
+        (0, 0, 0, 0),
+        (100, -100, 0, 0),
+        (-100, -25, "0.8", "0.2"),
+    ],
+)
+def test_shares(net, tax, net_share, tax_share):
+    value = _money.MoneyWithVAT(net, tax)
+    assert value.net_share == _decimal.Decimal(net_share)
+    assert value.tax_share == _decimal.Decimal(tax_share)
+
+
 @_pytest.mark.parametrize(
     "net,tax,rate",
     [
@@ -226,6 +642,8 @@ def test_tax_rate(net, tax, rate):
         (100, 0, 0),
         (-100, -19, "0.19"),
         (300, 30, "0.10"),
+        # An exact match wins over lower rates within the tolerance of small amounts
+        ("0.5", "0.035", "0.07"),
     ],
 )
 def test_tax_rate_for_display(net, tax, rate):
@@ -276,6 +694,23 @@ def test_max(items):
     assert money_max.gross.amount == _decimal.Decimal(str(gross[-1]))
 
 
+def test_max_and_min_skip_none():
+    items = [None, _money.MoneyWithVAT(100, 19), None, _money.MoneyWithVAT(50, 30)]
+
+    money_max = _money.MoneyWithVAT.max(items)
+    money_min = _money.MoneyWithVAT.min(*items)
+    assert (money_max.net, money_max.gross) == (_money.Money(100), _money.Money(119))
+    assert (money_min.net, money_min.gross) == (_money.Money(50), _money.Money(80))
+
+
+@_pytest.mark.parametrize("items", [[], [None, None]])
+def test_max_and_min_default(items):
+    assert _money.MoneyWithVAT.max(items, default=None) is None
+    assert _money.MoneyWithVAT.min(items, default=0) == 0
+    with _pytest.raises(ValueError, match="Insufficient arguments"):
+        _money.MoneyWithVAT.max(items)
+
+
 def test_ratio_mul():
     money_a = _money.MoneyWithVAT(100, 19)
     money_b = _money.MoneyWithVAT(200, 14)
@@ -493,6 +928,52 @@ def test_from_json():
     assert result.tax_rate == _decimal.Decimal("0.11")
 
 
+def test_from_json_flat_shape():
+    result = _money.MoneyWithVAT.from_json({"net": "100.00", "tax": 19})
+    assert (result.net, result.tax) == (_money.Money(100), _money.Money(19))
+
+
+def test_for_json_round_trip_with_schema_version():
+    money_with_vat = _money.MoneyWithVAT(100, 19)
+    json = money_with_vat.for_json(with_schema_version=True)
+
+    assert json["schema_version"] == 2
+    assert "schema_version" not in money_with_vat.for_json()
+
+    result = _money.MoneyWithVAT.from_json(json)
+    assert (result.net, result.tax) == (money_with_vat.net, money_with_vat.tax)
+
+
+def test_for_json_round_trip_with_currency():
+    money_with_vat = _money.MoneyWithVAT(100, 19, "CHF")
+    json = money_with_vat.for_json(with_schema_version=True)
+
+    assert json["currency"] == "CHF"
+    assert "currency" not in _money.MoneyWithVAT(100, 19).for_json()
+
+    result = _money.MoneyWithVAT.from_json(json)
+    assert (result.net, result.tax) == (money_with_vat.net, money_with_vat.tax)
+    assert result.currency == _money.Currency.CHF
+
+
+def test_from_json_newer_schema_version():
+    with _pytest.raises(ValueError, match="Unsupported schema_version 3"):
+        _money.MoneyWithVAT.from_json({"net": 100, "tax": 19, "schema_version": 3})
+
+
+@_pytest.mark.parametrize("version", ["abc", "2", -1, 0, 1.0, None])
+def test_from_json_invalid_schema_version(version):
+    with _pytest.raises(ValueError, match="Unsupported schema_version"):
+        _money.MoneyWithVAT.from_json(
+            {"net": 100, "tax": 19, "schema_version": version}
+        )
+
+
+def test_from_json_flat_shape_invalid_amount():
+    with _pytest.raises(ValueError):
+        _money.MoneyWithVAT.from_json({"net": "not a number", "tax": 19})
+
+
 @_pytest.mark.parametrize(
     "operands, result",
     [
@@ -541,6 +1022,103 @@ def test_fast_sum_with_none(operands, result):
     assert _money.MoneyWithVAT.fast_sum_with_none(operands) == result
 
 
+def test_fast_sum_large():
+    operands = [_money.MoneyWithVAT(i, "0.01") for i in range(100_000)] + [None]
+
+    result = _money.MoneyWithVAT.fast_sum(operands)
+
+    assert (result.net, result.tax) == (_money.Money(4_999_950_000), _money.Money(1000))
+
+
+class _Position:
+    def __init__(self, amount_with_vat):
+        self.amount_with_vat = amount_with_vat
+
+
+@_pytest.mark.parametrize(
+    "key", ["amount_with_vat", lambda position: position.amount_with_vat]
+)
+def test_fast_sum_key(key):
+    operands = [
+        _Position(_money.MoneyWithVAT(1, 1)),
+        None,
+        _Position(None),
+        _Position(_money.MoneyWithVAT(2, 2)),
+    ]
+
+    empty = [None, _Position(None)]
+
+    assert _money.MoneyWithVAT.fast_sum(operands, key=key) == _money.MoneyWithVAT(3, 3)
+    assert _money.MoneyWithVAT.fast_sum_with_none(empty, key=key) is None
+
+
+def test_fast_sum_key_missing_attribute():
+    with _pytest.raises(AttributeError):
+        _money.MoneyWithVAT.fast_sum([_Position(None)], key="unknown")
+
+
+def test_fast_sum_with_breakdown():
+    positions = [
+        _money.MoneyWithVAT(100, 19),
+        None,
+        _money.MoneyWithVAT(50, "3.5"),
+        _money.MoneyWithVAT("10.01", "1.90"),
+        _money.MoneyWithVAT(20, 0),
+    ]
+
+    breakdown = _money.MoneyWithVAT.fast_sum_with_breakdown(positions)
+
+    assert breakdown.rates == [
+        _decimal.Decimal("0.19"),
+        _decimal.Decimal("0.07"),
+        _decimal.Decimal(0),
+    ]
+    assert len(breakdown) == 3
+    assert {
+        rate: (bucket.net, bucket.tax) for rate, bucket in breakdown.buckets.items()
+    } == {
+        _decimal.Decimal("0.19"): (_money.Money("110.01"), _money.Money("20.90")),
+        _decimal.Decimal("0.07"): (_money.Money(50), _money.Money("3.5")),
+        _decimal.Decimal(0): (_money.Money(20), _money.Money(0)),
+    }
+    total = _money.MoneyWithVAT.fast_sum(positions)
+    assert (breakdown.total.net, breakdown.total.tax) == (total.net, total.tax)
+    assert repr(breakdown) == (
+        "TaxBreakdown(net='180.01', tax='24.40', rates=['0.19', '0.07', '0.00'])"
+    )
+
+
+def test_fast_sum_with_breakdown_key_and_currency():
+    positions = [
+        _Position(_money.MoneyWithVAT(100, "7.7", "CHF")),
+        _Position(None),
+        _Position(_money.MoneyWithVAT(10, "0.77", "CHF")),
+    ]
+
+    breakdown = _money.MoneyWithVAT.fast_sum_with_breakdown(
+        positions, key="amount_with_vat"
+    )
+
+    assert breakdown.total.currency == _money.Currency.CHF
+    assert [bucket.currency for bucket in breakdown.buckets.values()] == [
+        _money.Currency.CHF
+    ]
+    with _pytest.raises(_money.CurrencyMismatchError):
+        _money.MoneyWithVAT.fast_sum_with_breakdown(
+            [_money.MoneyWithVAT(1, 0, "EUR"), _money.MoneyWithVAT(1, 0, "USD")]
+        )
+
+
+def test_fast_sum_with_breakdown_empty():
+    breakdown = _money.MoneyWithVAT.fast_sum_with_breakdown([None])
+
+    assert breakdown.buckets == {}
+    assert (breakdown.total.net, breakdown.total.tax) == (
+        _money.Money(0),
+        _money.Money(0),
+    )
+
+
 def _slow_money_vat_sum(operands):
     return sum(
         (operand for operand in operands if operand is not None),
@@ -583,3 +1161,281 @@ def test_rounded_to_cents(net, tax, expected_net, expected_tax):
         == value.rounded_to_cents().net + value.rounded_to_cents().tax
     )
     assert value.gross.round(2) == value.rounded_to_cents().gross
+
+
+@_pytest.mark.parametrize(
+    "stable, expected_net, expected_tax",
+    [
+        ("gross", "4.44", "2.23"),
+        ("tax", "4.45", "2.22"),
+        ("net", "4.44", "2.22"),
+    ],
+)
+def test_rounded_to_cents_stable(stable, expected_net, expected_tax):
+    value = _money.MoneyWithVAT("4.444", "2.222").rounded_to_cents(stable=stable)
+
+    assert (value.net, value.tax) == (
+        _money.Money(expected_net),
+        _money.Money(expected_tax),
+    )
+
+
+@_pytest.mark.parametrize(
+    "strategy, expected_net, expected_tax",
+    [
+        ("half_even", "4.44", "2.34"),
+        ("half_up", "4.45", "2.33"),
+        ("ROUND_HALF_DOWN", "4.44", "2.34"),
+        ("down", "4.44", "2.34"),
+        ("up", "4.45", "2.33"),
+    ],
+)
+def test_rounded_to_cents_strategy(strategy, expected_net, expected_tax):
+    value = _money.MoneyWithVAT("4.445", "2.335").rounded_to_cents(strategy=strategy)
+
+    assert (value.net, value.tax) == (
+        _money.Money(expected_net),
+        _money.Money(expected_tax),
+    )
+
+
+def test_rounded_to_cents_unknown_strategy():
+    with _pytest.raises(ValueError, match="Unknown rounding strategy half_odd"):
+        _money.MoneyWithVAT(1, 0).rounded_to_cents(strategy="half_odd")
+
+
+def test_rounded_to_cents_unknown_stable():
+    with _pytest.raises(ValueError, match="Unknown stable amount"):
+        _money.MoneyWithVAT(1, 0).rounded_to_cents(stable="ratio")
+
+
+@_pytest.mark.parametrize(
+    "round_before_sum, expected_net, expected_tax",
+    [
+        (False, "4.44", "2.23"),
+        (True, "4.44", "2.22"),
+    ],
+)
+def test_convert(round_before_sum, expected_net, expected_tax):
+    value = _money.MoneyWithVAT("2.222", "1.111")
+    result = value.convert(2, round_before_sum=round_before_sum)
+
+    assert result.net == _money.Money(expected_net)
+    assert result.tax == _money.Money(expected_tax)
+
+
+def test_convert_keeps_exact_rate():
+    result = _money.MoneyWithVAT(100, 19).convert(_decimal.Decimal("1.0825"))
+    assert result.net == _money.Money("108.25")
+    assert result.tax == _money.Money("20.57")
+
+
+@_pytest.mark.parametrize(
+    "currency, round_before_sum, expected_net, expected_tax",
+    [
+        ("JPY", False, "16012", "3043"),
+        ("JPY", True, "16012", "3042"),
+        ("CHF", False, "94.23", "17.90"),
+    ],
+)
+def test_convert_to_currency(currency, round_before_sum, expected_net, expected_tax):
+    value = _money.MoneyWithVAT(100, 19, "EUR")
+    rate = {"JPY": "160.123", "CHF": "0.9423"}[currency]
+    result = value.convert(
+        _decimal.Decimal(rate), round_before_sum=round_before_sum, currency=currency
+    )
+
+    assert result.currency == _money.Currency.from_code(currency)
+    assert result.net == _money.Money(expected_net, currency)
+    assert result.tax == _money.Money(expected_tax, currency)
+
+
+@_pytest.mark.parametrize("rate", [0, -1])
+def test_convert_invalid_rate(rate):
+    with _pytest.raises(ValueError):
+        _money.MoneyWithVAT(100, 19).convert(rate)
+
+
+@_pytest.mark.parametrize(
+    "value, input_tax, expected",
+    [
+        (_money.MoneyWithVAT(100, 19), True, ("119,00", "S", "9")),
+        (_money.MoneyWithVAT(100, 19), False, ("119,00", "S", "3")),
+        (_money.MoneyWithVAT(-100, -7), True, ("107,00", "H", "8")),
+        (_money.MoneyWithVAT(100, 0), False, ("100,00", "S", "")),
+    ],
+)
+def test_to_datev_booking(value, input_tax, expected):
+    assert value.to_datev_booking(input_tax=input_tax) == {
+        "Umsatz": expected[0],
+        "Soll/Haben-Kennzeichen": expected[1],
+        "BU-Schlüssel": expected[2],
+    }
+
+
+def test_to_datev_booking_unknown_rate():
+    with _pytest.raises(ValueError):
+        _money.MoneyWithVAT(100, 20).to_datev_booking()
+
+
+def test_validate_locale():
+    data = {"net": "1.000,00", "tax": "190,00"}
+
+    result = _money.MoneyWithVAT.validate(data, locale="de")
+
+    assert result == _money.MoneyWithVAT(1000, 190)
+    with _pytest.raises(ValueError, match="Validation error"):
+        _money.MoneyWithVAT.validate(data)
+
+
+@_pytest.mark.parametrize(
+    "net, tax",
+    [
+        ("1.005", "0.19"),
+        (_money.Money("1.005"), _money.Money("0.19")),
+        (_money.Money("1.005"), "0.19"),
+    ],
+)
+def test_validate_dict_with_strings_and_money(net, tax):
+    result = _money.MoneyWithVAT.validate({"net": net, "tax": tax})
+
+    assert (result.net.amount, result.tax.amount) == (
+        _decimal.Decimal("1.005"),
+        _decimal.Decimal("0.19"),
+    )
+
+
+def test_validate_dict_with_money_keeps_currency():
+    chf = _money.Currency.CHF
+    data = {"net": _money.Money(1, "CHF"), "tax": "0.19"}
+
+    assert _money.MoneyWithVAT.validate(data).currency == chf
+    assert _money.MoneyWithVAT.validate({**data, "currency": "CHF"}).currency == chf
+
+
+@_pytest.mark.parametrize(
+    "data",
+    [
+        {"net": _money.Money(1, "CHF"), "tax": _money.Money("0.19", "EUR")},
+        {"net": _money.Money(1, "CHF"), "tax": "0.19", "currency": "EUR"},
+    ],
+)
+def test_validate_dict_with_money_currency_mismatch(data):
+    with _pytest.raises(_money.CurrencyMismatchError):
+        _money.MoneyWithVAT.validate(data)
+
+
+def test_validate_dict_with_invalid_string():
+    with _pytest.raises(ValueError, match="Validation error"):
+        _money.MoneyWithVAT.validate({"net": "1,5", "tax": "0"})
+
+
+def test_sort_key():
+    subjects = [
+        _money.MoneyWithVAT(100, 19),
+        _money.MoneyWithVAT(-10, "-1.9"),
+        _money.MoneyWithVAT(110, 0),
+        _money.MoneyWithVAT(0, 0),
+    ]
+
+    result = sorted(subjects, key=_money.MoneyWithVAT.sort_key)
+
+    assert [value.gross for value in result] == sorted(
+        value.gross for value in subjects
+    )
+    assert (
+        _money.MoneyWithVAT(100, 19).sort_key()
+        == _money.MoneyWithVAT(119, 0).sort_key()
+    )
+
+
+def test_stable_hash():
+    assert (
+        _money.MoneyWithVAT(100, 19).stable_hash()
+        == _money.MoneyWithVAT("119.00", 0).stable_hash()
+        == _money.Money(119).stable_hash()
+    )
+    assert (
+        _money.MoneyWithVAT(100, 19).stable_hash()
+        != _money.MoneyWithVAT(100, 7).stable_hash()
+    )
+
+
+@_dataclasses.dataclass
+class _Snapshot:
+    total: _money.MoneyWithVAT
+    ratio: _money.MoneyWithVATRatio
+    items: list[_money.MoneyWithVAT]
+
+
+def test_dataclasses_asdict_and_astuple():
+    snapshot = _Snapshot(
+        total=_money.MoneyWithVAT(100, 19),
+        ratio=_money.MoneyWithVATRatio("0.5", "0.6"),
+        items=[_money.MoneyWithVAT(60, "11.4"), _money.MoneyWithVAT(40, "7.6")],
+    )
+
+    as_dict = _dataclasses.asdict(snapshot)
+    assert as_dict == {
+        "total": _money.MoneyWithVAT(100, 19),
+        "ratio": _money.MoneyWithVATRatio("0.5", "0.6"),
+        "items": [_money.MoneyWithVAT(60, "11.4"), _money.MoneyWithVAT(40, "7.6")],
+    }
+    assert as_dict["total"] is not snapshot.total
+    assert as_dict["ratio"] is not snapshot.ratio
+
+    assert _dataclasses.astuple(snapshot) == (
+        _money.MoneyWithVAT(100, 19),
+        _money.MoneyWithVATRatio("0.5", "0.6"),
+        [_money.MoneyWithVAT(60, "11.4"), _money.MoneyWithVAT(40, "7.6")],
+    )
+
+
+def test_replace():
+    money_with_vat = _money.MoneyWithVAT(100, 19)
+
+    replaced = money_with_vat.__replace__(net=_money.Money(10))
+    assert (replaced.net, replaced.tax) == (_money.Money(10), _money.Money(19))
+    replaced = money_with_vat.__replace__(tax="7")
+    assert (replaced.net, replaced.tax) == (_money.Money(100), _money.Money(7))
+
+    ratio = _money.MoneyWithVATRatio("0.5", "0.6")
+    assert ratio.__replace__(gross_ratio="0.7") == _money.MoneyWithVATRatio(
+        "0.5", "0.7"
+    )
+
+    with _pytest.raises(TypeError):
+        money_with_vat.__replace__(gross=1)
+
+
+@_pytest.mark.skipif(not hasattr(_copy, "replace"), reason="Python 3.13+")
+def test_copy_replace():
+    money_with_vat = _copy.replace(_money.MoneyWithVAT(100, 19), net=_money.Money(10))
+    assert money_with_vat.gross == _money.Money(29)
+    assert _copy.replace(_money.Money(1), amount=2) == _money.Money(2)
+
+
+@_pytest.mark.parametrize(
+    "precision, expected_net, expected_tax",
+    [
+        (None, "1.123456789012", "0.213456789012"),
+        (6, "1.123457", "0.213457"),
+        (4, "1.1235", "0.2135"),
+    ],
+)
+def test_rounded_to_money_field_precision(precision, expected_net, expected_tax):
+    value = _money.MoneyWithVAT("1.1234567890123", "0.2134567890123")
+
+    result = value.rounded_to_money_field_precision(precision)
+    assert str(result.net.amount) == expected_net
+    assert str(result.tax.amount) == expected_tax
+
+
+def test_variance():
+    actual = _money.MoneyWithVAT(90, "17.1")
+    budget = _money.MoneyWithVAT(100, 19)
+
+    variance = _money.MoneyWithVAT.variance(actual, budget)
+    assert (variance.net, variance.tax) == (_money.Money(-10), _money.Money("-1.9"))
+    assert _money.MoneyWithVAT.variance_pct(actual, budget) == _decimal.Decimal(-10)
+    assert _money.MoneyWithVAT.variance_pct(actual, _money.MoneyWithVAT()) is None
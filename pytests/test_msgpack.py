@@ -0,0 +1,67 @@
+import decimal as _decimal
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def test_money_to_msgpack_bytes():
+    data = _money.Money("1.50").to_msgpack_bytes()
+
+    assert data == b"\x82\xa6amount\xa41.50\xa8currency\xc0"
+
+
+@_pytest.mark.parametrize(
+    "value",
+    [
+        _money.Money("1.50"),
+        _money.Money("-0.0000000000000000000000000001"),
+        _money.Money("79228162514264337593543950335"),
+        _money.Money("12.3", "CHF"),
+        _money.Money("-0"),
+        _money.Money("-0.00", "EUR"),
+    ],
+)
+def test_money_msgpack_roundtrip(value):
+    result = _money.Money.from_msgpack_bytes(value.to_msgpack_bytes())
+
+    assert result.amount.as_tuple() == value.amount.as_tuple()
+    assert result.currency == value.currency
+
+
+@_pytest.mark.parametrize(
+    "value",
+    [
+        _money.MoneyWithVAT("100.10", "19.019"),
+        _money.MoneyWithVAT("-1", "0", "EUR"),
+        _money.MoneyWithVAT("-0", "-0.00"),
+    ],
+)
+def test_money_with_vat_msgpack_roundtrip(value):
+    result = _money.MoneyWithVAT.from_msgpack_bytes(value.to_msgpack_bytes())
+
+    assert result.net.amount.as_tuple() == value.net.amount.as_tuple()
+    assert result.tax.amount.as_tuple() == value.tax.amount.as_tuple()
+    assert result.currency == value.currency
+
+
+def test_money_from_msgpack_bytes_unknown_currency():
+    data = b"\x82\xa6amount\xa11\xa8currency\xa3XYZ"
+
+    with _pytest.raises(ValueError, match="Unknown currency 'XYZ'"):
+        _money.Money.from_msgpack_bytes(data)
+
+
+@_pytest.mark.parametrize("data", [b"", b"\xc0", b"\x81\xa6amount\xa3abc"])
+def test_money_from_msgpack_bytes_invalid(data):
+    with _pytest.raises(ValueError, match="Invalid msgpack"):
+        _money.Money.from_msgpack_bytes(data)
+
+
+def test_money_with_vat_from_msgpack_bytes_invalid():
+    with _pytest.raises(ValueError, match="Invalid msgpack"):
+        _money.MoneyWithVAT.from_msgpack_bytes(_money.Money(1).to_msgpack_bytes())
+
+
+def test_msgpack_amount_is_exact_string():
+    data = _money.Money(_decimal.Decimal("0.1")).to_msgpack_bytes()
+    assert b"\xa30.1" in data
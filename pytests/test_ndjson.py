@@ -0,0 +1,78 @@
+import io as _io
+import json as _json
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+def _export(*records):
+    return "\n".join(
+        _json.dumps(_money.MoneyWithVAT(*record).for_json()) for record in records
+    )
+
+
+def test_sum_ndjson_bytes_and_str():
+    export = _export((100, 19), ("0.10", "0.02"))
+
+    for source in [export, export.encode()]:
+        total, count = _money.sum_ndjson(source)
+        assert (total.net, total.tax, count) == (
+            _money.Money("100.10"),
+            _money.Money("19.02"),
+            2,
+        )
+
+
+def test_sum_ndjson_file_objects():
+    export = _export((1, 2), (3, 4)) + "\n\n"
+
+    for source in [_io.StringIO(export), _io.BytesIO(export.encode())]:
+        total, count = _money.sum_ndjson(source)
+        assert (total.net, total.tax, count) == (_money.Money(4), _money.Money(6), 2)
+
+
+def test_sum_ndjson_keeps_precision_of_numbers():
+    total, count = _money.sum_ndjson(
+        b'{"net": 0.1000000000001, "tax": 1E-12}\n{"net": "0.2", "tax": 0}'
+    )
+    assert (total.net, total.tax, count) == (
+        _money.Money("0.3000000000001"),
+        _money.Money("1E-12"),
+        2,
+    )
+
+
+def test_sum_ndjson_empty():
+    total, count = _money.sum_ndjson(b"")
+    assert (total.net, total.tax, count) == (_money.Money(0), _money.Money(0), 0)
+
+
+def test_sum_ndjson_currency():
+    total, count = _money.sum_ndjson(
+        '{"net": "1", "tax": "0", "currency": "EUR"}\n{"net": "2", "tax": "1"}'
+    )
+    assert (total.net, total.tax, count) == (
+        _money.Money(3, "EUR"),
+        _money.Money(1, "EUR"),
+        2,
+    )
+
+
+def test_sum_ndjson_currency_mismatch():
+    with _pytest.raises(_money.CurrencyMismatchError, match="line 2"):
+        _money.sum_ndjson(
+            '{"net": 1, "tax": 0, "currency": "EUR"}\n'
+            '{"net": 1, "tax": 0, "currency": "USD"}'
+        )
+    with _pytest.raises(ValueError, match="Invalid record on line 1"):
+        _money.sum_ndjson('{"net": 1, "tax": 0, "currency": "XXX"}')
+
+
+def test_sum_ndjson_invalid_record():
+    with _pytest.raises(ValueError, match="Invalid record on line 2"):
+        _money.sum_ndjson('{"net": 1, "tax": 0}\n{"net": 1}\n')
+
+
+def test_sum_ndjson_unsupported_source():
+    with _pytest.raises(TypeError, match="Unsupported source"):
+        _money.sum_ndjson(42)
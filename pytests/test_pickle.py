@@ -0,0 +1,65 @@
+import pickle as _pickle
+
+import alasco_money as _money
+import pytest as _pytest
+
+
+class _Subclass(_money.Money):
+    pass
+
+
+@_pytest.mark.parametrize("protocol", range(_pickle.HIGHEST_PROTOCOL + 1))
+@_pytest.mark.parametrize(
+    "subject",
+    [
+        _money.Money("1.50"),
+        _money.Money("-0.00"),
+        _money.Money("0.1234567890123456789012345678"),
+        _money.Money("12", "EUR"),
+    ],
+)
+def test_money(subject, protocol):
+    result = _pickle.loads(_pickle.dumps(subject, protocol=protocol))
+
+    assert type(result) is _money.Money
+    assert str(result.amount) == str(subject.amount)
+    assert result.currency == subject.currency
+
+
+def test_money_subclass():
+    result = _pickle.loads(_pickle.dumps(_Subclass("1.50")))
+
+    assert type(result) is _Subclass
+    assert result == _money.Money("1.50")
+
+
+@_pytest.mark.parametrize(
+    "subject",
+    [_money.MoneyWithVAT("100.00", "19.00"), _money.MoneyWithVAT(1, "0.07", "USD")],
+)
+def test_money_with_vat(subject):
+    result = _pickle.loads(_pickle.dumps(subject))
+
+    assert type(result) is _money.MoneyWithVAT
+    assert str(result.net.amount) == str(subject.net.amount)
+    assert str(result.tax.amount) == str(subject.tax.amount)
+    assert result.currency == subject.currency
+
+
+def test_money_with_vat_ratio():
+    subject = _money.MoneyWithVATRatio("0.5", "0.25")
+    result = _pickle.loads(_pickle.dumps(subject))
+
+    assert type(result) is _money.MoneyWithVATRatio
+    assert (result.net_ratio, result.gross_ratio) == (
+        subject.net_ratio,
+        subject.gross_ratio,
+    )
+
+
+def test_nested_containers():
+    subject = {"total": [_money.Money(1), _money.MoneyWithVAT(1, 1)]}
+    result = _pickle.loads(_pickle.dumps(subject))
+
+    assert result["total"][0] == _money.Money(1)
+    assert result["total"][1] == _money.MoneyWithVAT(1, 1)
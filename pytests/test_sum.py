@@ -19,3 +19,89 @@ import pytest as _pytest
 )
 def test_sum_(operands, expected):
     assert _money.sum_(operands) == expected
+
+
+@_pytest.mark.parametrize(
+    "operands, expected",
+    [
+        ([], None),
+        ([None, None], None),
+        ([_money.Money(100), None, _money.Money(200)], _money.Money(150)),
+        ((x for x in [_money.Money(1), _money.Money(2)]), _money.Money("1.5")),
+        (
+            [_money.MoneyWithVAT(100, 19), None, _money.MoneyWithVAT(200, 0)],
+            _money.MoneyWithVAT(150, "9.5"),
+        ),
+    ],
+)
+def test_average_(operands, expected):
+    result = _money.average_(operands)
+
+    if expected is None:
+        assert result is None
+    else:
+        assert type(result) is type(expected)
+        assert result == expected
+
+
+@_pytest.mark.parametrize(
+    "operands",
+    [
+        [_money.Money(1), _money.MoneyWithVAT(1, 1)],
+        [_money.Money(1), 1],
+    ],
+)
+def test_average_invalid(operands):
+    with _pytest.raises(TypeError):
+        _money.average_(operands)
+
+
+@_pytest.mark.parametrize(
+    "operands, expected",
+    [
+        ([], []),
+        ([_money.Money(100)], []),
+        (
+            [_money.Money(100), _money.Money(150), _money.Money(120)],
+            [_money.Money(50), _money.Money(-30)],
+        ),
+        ((x for x in [_money.Money(1), _money.Money(1)]), [_money.Money(0)]),
+    ],
+)
+def test_diffs(operands, expected):
+    assert _money.diffs(operands) == expected
+
+
+def test_diffs_invalid():
+    with _pytest.raises(TypeError):
+        _money.diffs([_money.Money(1), None])
+
+
+def test_bucketize():
+    operands = [
+        _money.Money(-5),
+        _money.Money(0),
+        None,
+        _money.Money(50),
+        _money.Money(100),
+        _money.Money(1000),
+        _money.Money(20),
+    ]
+    boundaries = [_money.Money(0), _money.Money(100)]
+
+    assert _money.bucketize(operands, boundaries) == [
+        (1, _money.Money(-5)),
+        (3, _money.Money(70)),
+        (2, _money.Money(1100)),
+    ]
+
+
+def test_bucketize_without_boundaries():
+    assert _money.bucketize([_money.Money(1), _money.Money(2)], []) == [
+        (2, _money.Money(3))
+    ]
+
+
+def test_bucketize_unsorted_boundaries():
+    with _pytest.raises(ValueError):
+        _money.bucketize([], [_money.Money(10), _money.Money(10)])
@@ -0,0 +1,183 @@
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+
+use crate::currency::{combined_currency, currency_extract, Currency};
+use crate::decimals::*;
+use crate::money::Money;
+use crate::money_vat::MoneyWithVAT;
+
+/// Mutable counterpart of Money for incremental construction, e.g. in parsers.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct MoneyBuilder {
+    amount: Decimal,
+    currency: Option<Currency>,
+}
+
+impl MoneyBuilder {
+    // Extracts an amount (Money or anything Money accepts) in the currency of the builder
+    fn amount_extract(&mut self, amount: Bound<PyAny>) -> PyResult<Decimal> {
+        let money = Money::new(Some(amount), None)?;
+        self.currency = combined_currency(self.currency, money.currency)?;
+        Ok(money.amount)
+    }
+}
+
+#[pymethods]
+impl MoneyBuilder {
+    #[new]
+    #[pyo3(signature = (amount=None, currency=None))]
+    fn new(amount: Option<Bound<PyAny>>, currency: Option<Bound<PyAny>>) -> PyResult<Self> {
+        let money = Money::new(amount, currency)?;
+        Ok(Self {
+            amount: money.amount,
+            currency: money.currency,
+        })
+    }
+
+    #[getter(amount)]
+    fn get_amount(&self) -> Decimal {
+        self.amount
+    }
+
+    #[setter(amount)]
+    fn set_amount(&mut self, amount: Bound<PyAny>) -> PyResult<()> {
+        self.amount = self.amount_extract(amount)?;
+        Ok(())
+    }
+
+    #[getter(currency)]
+    fn get_currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    /// Adds an amount in place and returns the builder for chaining.
+    fn add<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        amount: Bound<PyAny>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let amount = slf.amount_extract(amount)?;
+        slf.amount = decimal_add(slf.amount, amount);
+        Ok(slf)
+    }
+
+    fn build(&self) -> Money {
+        Money {
+            amount: self.amount,
+            currency: self.currency,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        match self.currency {
+            Some(currency) => format!(
+                "MoneyBuilder(amount='{}', currency='{}')",
+                self.amount,
+                currency.get_code()
+            ),
+            None => format!("MoneyBuilder(amount='{}')", self.amount),
+        }
+    }
+}
+
+/// Mutable counterpart of MoneyWithVAT for incremental construction, e.g. in parsers.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct MoneyWithVATBuilder {
+    net: Decimal,
+    tax: Decimal,
+    currency: Option<Currency>,
+}
+
+impl MoneyWithVATBuilder {
+    // Extracts an amount (Money or anything Money accepts) in the currency of the builder
+    fn amount_extract(&mut self, amount: Bound<PyAny>) -> PyResult<Decimal> {
+        let money = Money::new(Some(amount), None)?;
+        self.currency = combined_currency(self.currency, money.currency)?;
+        Ok(money.amount)
+    }
+}
+
+#[pymethods]
+impl MoneyWithVATBuilder {
+    #[new]
+    #[pyo3(signature = (net=None, tax=None, currency=None))]
+    fn new(
+        net: Option<Bound<PyAny>>,
+        tax: Option<Bound<PyAny>>,
+        currency: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        let mut result = Self {
+            net: Decimal::new(0, 0),
+            tax: Decimal::new(0, 0),
+            currency: currency.map(|obj| currency_extract(&obj)).transpose()?,
+        };
+        if let Some(net) = net {
+            result.net = result.amount_extract(net)?;
+        }
+        if let Some(tax) = tax {
+            result.tax = result.amount_extract(tax)?;
+        }
+        Ok(result)
+    }
+
+    #[getter(net)]
+    fn get_net(&self) -> Money {
+        Money {
+            amount: self.net,
+            currency: self.currency,
+        }
+    }
+
+    #[setter(net)]
+    fn set_net(&mut self, net: Bound<PyAny>) -> PyResult<()> {
+        self.net = self.amount_extract(net)?;
+        Ok(())
+    }
+
+    #[getter(tax)]
+    fn get_tax(&self) -> Money {
+        Money {
+            amount: self.tax,
+            currency: self.currency,
+        }
+    }
+
+    #[setter(tax)]
+    fn set_tax(&mut self, tax: Bound<PyAny>) -> PyResult<()> {
+        self.tax = self.amount_extract(tax)?;
+        Ok(())
+    }
+
+    #[getter(currency)]
+    fn get_currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    /// Adds a MoneyWithVAT in place and returns the builder for chaining.
+    fn add(mut slf: PyRefMut<Self>, value: MoneyWithVAT) -> PyResult<PyRefMut<Self>> {
+        slf.currency = combined_currency(slf.currency, value.get_currency())?;
+        slf.net = decimal_add(slf.net, value.net.amount);
+        slf.tax = decimal_add(slf.tax, value.tax.amount);
+        Ok(slf)
+    }
+
+    fn build(&self) -> MoneyWithVAT {
+        MoneyWithVAT::in_currency(self.net, self.tax, self.currency)
+    }
+
+    fn __repr__(&self) -> String {
+        match self.currency {
+            Some(currency) => format!(
+                "MoneyWithVATBuilder(net='{}', tax='{}', currency='{}')",
+                self.net,
+                self.tax,
+                currency.get_code()
+            ),
+            None => format!(
+                "MoneyWithVATBuilder(net='{}', tax='{}')",
+                self.net, self.tax
+            ),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyCFunction, PyDict, PyTuple, PyType};
+
+use crate::money::Money;
+use crate::money_vat::MoneyWithVAT;
+use crate::money_vat_ratio::MoneyWithVATRatio;
+
+#[pyfunction]
+/// Converter for attrs fields (`field(converter=as_money)`), accepting the same input as
+/// pydantic validation of Money does.
+pub fn as_money(value: Bound<PyAny>) -> PyResult<Money> {
+    Money::validate(value, None, None)
+}
+
+#[pyfunction]
+/// Converter for attrs fields (`field(converter=as_money_with_vat)`), accepting the same
+/// input as pydantic validation of MoneyWithVAT does, e.g. `{"net": 100, "tax": 19}`.
+pub fn as_money_with_vat(value: Bound<PyAny>) -> PyResult<MoneyWithVAT> {
+    MoneyWithVAT::validate(value, None, None)
+}
+
+#[pyfunction]
+/// Validator for attrs fields (`field(validator=validate_money)`), raising for values
+/// pydantic validation of Money rejects.
+pub fn validate_money(
+    _instance: Bound<PyAny>,
+    _attribute: Bound<PyAny>,
+    value: Bound<PyAny>,
+) -> PyResult<()> {
+    Money::validate(value, None, None).map(|_| ())
+}
+
+#[pyfunction]
+/// Validator for attrs fields (`field(validator=validate_money_with_vat)`), raising for
+/// values pydantic validation of MoneyWithVAT rejects.
+pub fn validate_money_with_vat(
+    _instance: Bound<PyAny>,
+    _attribute: Bound<PyAny>,
+    value: Bound<PyAny>,
+) -> PyResult<()> {
+    MoneyWithVAT::validate(value, None, None).map(|_| ())
+}
+
+fn register_hooks(
+    converter: &Bound<PyAny>,
+    cls: Bound<PyType>,
+    structure: Bound<PyCFunction>,
+    unstructure: Bound<PyCFunction>,
+) -> PyResult<()> {
+    converter.call_method1("register_structure_hook", (&cls, structure))?;
+    converter.call_method1("register_unstructure_hook", (&cls, unstructure))?;
+    Ok(())
+}
+
+#[pyfunction]
+/// Installs structure and unstructure hooks for Money, MoneyWithVAT and MoneyWithVATRatio
+/// on a cattrs converter. Structuring validates like pydantic, unstructuring produces the
+/// same shapes as pydantic's JSON serialization.
+pub fn register_cattrs_hooks(converter: Bound<PyAny>, py: Python) -> PyResult<()> {
+    register_hooks(
+        &converter,
+        py.get_type_bound::<Money>(),
+        PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<Money> {
+                Money::validate(args.get_item(0)?, None, None)
+            },
+        )?,
+        PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
+                args.get_item(0)?.extract::<Money>()?.for_json()
+            },
+        )?,
+    )?;
+
+    register_hooks(
+        &converter,
+        py.get_type_bound::<MoneyWithVAT>(),
+        PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<MoneyWithVAT> {
+                MoneyWithVAT::validate(args.get_item(0)?, None, None)
+            },
+        )?,
+        PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
+                args.get_item(0)?.extract::<MoneyWithVAT>()?.for_json(false)
+            },
+        )?,
+    )?;
+
+    register_hooks(
+        &converter,
+        py.get_type_bound::<MoneyWithVATRatio>(),
+        PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<MoneyWithVATRatio> {
+                MoneyWithVATRatio::validate(args.get_item(0)?, None)
+            },
+        )?,
+        PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
+                args.get_item(0)?.extract::<MoneyWithVATRatio>()?.for_json()
+            },
+        )?,
+    )
+}
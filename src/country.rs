@@ -0,0 +1,197 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+
+use crate::decimals::decimal_extract;
+
+/// Countries (ISO 3166 alpha-2) with their VAT rates as of January 2025
+#[pyclass(eq, eq_int, hash, frozen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Country {
+    AT,
+    BE,
+    BG,
+    CH,
+    CY,
+    CZ,
+    DE,
+    DK,
+    EE,
+    ES,
+    FI,
+    FR,
+    GB,
+    GR,
+    HR,
+    HU,
+    IE,
+    IT,
+    LT,
+    LU,
+    LV,
+    MT,
+    NL,
+    NO,
+    PL,
+    PT,
+    RO,
+    SE,
+    SI,
+    SK,
+}
+
+// (country, code, EU member, currency, standard rate, reduced rates), rates in tenths of percent
+type CountryData = (
+    Country,
+    &'static str,
+    bool,
+    &'static str,
+    i64,
+    &'static [i64],
+);
+
+const COUNTRIES: [CountryData; 30] = [
+    (Country::AT, "AT", true, "EUR", 200, &[100, 130]),
+    (Country::BE, "BE", true, "EUR", 210, &[60, 120]),
+    (Country::BG, "BG", true, "BGN", 200, &[90]),
+    (Country::CH, "CH", false, "CHF", 81, &[26, 38]),
+    (Country::CY, "CY", true, "EUR", 190, &[50, 90]),
+    (Country::CZ, "CZ", true, "CZK", 210, &[120]),
+    (Country::DE, "DE", true, "EUR", 190, &[70]),
+    (Country::DK, "DK", true, "DKK", 250, &[]),
+    (Country::EE, "EE", true, "EUR", 220, &[90]),
+    (Country::ES, "ES", true, "EUR", 210, &[40, 100]),
+    (Country::FI, "FI", true, "EUR", 255, &[100, 140]),
+    (Country::FR, "FR", true, "EUR", 200, &[21, 55, 100]),
+    (Country::GB, "GB", false, "GBP", 200, &[50]),
+    (Country::GR, "GR", true, "EUR", 240, &[60, 130]),
+    (Country::HR, "HR", true, "EUR", 250, &[50, 130]),
+    (Country::HU, "HU", true, "HUF", 270, &[50, 180]),
+    (Country::IE, "IE", true, "EUR", 230, &[48, 90, 135]),
+    (Country::IT, "IT", true, "EUR", 220, &[40, 50, 100]),
+    (Country::LT, "LT", true, "EUR", 210, &[50, 90]),
+    (Country::LU, "LU", true, "EUR", 170, &[30, 80, 140]),
+    (Country::LV, "LV", true, "EUR", 210, &[50, 120]),
+    (Country::MT, "MT", true, "EUR", 180, &[50, 70]),
+    (Country::NL, "NL", true, "EUR", 210, &[90]),
+    (Country::NO, "NO", false, "NOK", 250, &[120, 150]),
+    (Country::PL, "PL", true, "PLN", 230, &[50, 80]),
+    (Country::PT, "PT", true, "EUR", 230, &[60, 130]),
+    (Country::RO, "RO", true, "RON", 190, &[50, 90]),
+    (Country::SE, "SE", true, "SEK", 250, &[60, 120]),
+    (Country::SI, "SI", true, "EUR", 220, &[50, 95]),
+    (Country::SK, "SK", true, "EUR", 230, &[50, 190]),
+];
+
+fn rate(tenths_of_percent: i64) -> Decimal {
+    Decimal::new(tenths_of_percent, 3).normalize()
+}
+
+impl Country {
+    fn data(&self) -> &'static CountryData {
+        COUNTRIES
+            .iter()
+            .find(|data| data.0 == *self)
+            .expect("Every country has data")
+    }
+}
+
+#[pymethods]
+impl Country {
+    /// Looks up a country by its ISO 3166 alpha-2 code, ignoring case.
+    #[staticmethod]
+    pub fn from_code(code: &str) -> PyResult<Self> {
+        let code = code.trim().to_uppercase();
+        COUNTRIES
+            .iter()
+            .find(|data| data.1 == code)
+            .map(|data| data.0)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown country '{}'", code)))
+    }
+
+    /// All known countries ordered by code.
+    #[staticmethod]
+    fn all() -> Vec<Self> {
+        COUNTRIES.iter().map(|data| data.0).collect()
+    }
+
+    #[getter(code)]
+    pub fn get_code(&self) -> &'static str {
+        self.data().1
+    }
+
+    #[getter(is_eu)]
+    pub fn get_is_eu(&self) -> bool {
+        self.data().2
+    }
+
+    /// ISO 4217 code of the currency
+    #[getter(currency)]
+    fn get_currency(&self) -> &'static str {
+        self.data().3
+    }
+
+    #[getter(standard_rate)]
+    pub fn get_standard_rate(&self) -> Decimal {
+        rate(self.data().4)
+    }
+
+    /// Reduced (including super-reduced) rates in ascending order
+    #[getter(reduced_rates)]
+    pub fn get_reduced_rates(&self) -> Vec<Decimal> {
+        self.data().5.iter().map(|n| rate(*n)).collect()
+    }
+
+    /// Classifies a rate as "standard", "reduced", "zero" or "unknown" for this country.
+    /// Rates above 1 are taken as percentages, so both 0.19 and 19 are standard in DE.
+    pub fn classify_rate(&self, rate: Bound<PyAny>) -> PyResult<&'static str> {
+        let mut rate = decimal_extract(rate)?;
+        if rate > Decimal::ONE {
+            rate /= Decimal::ONE_HUNDRED;
+        }
+
+        Ok(if rate.is_zero() {
+            "zero"
+        } else if rate == self.get_standard_rate() {
+            "standard"
+        } else if self.get_reduced_rates().contains(&rate) {
+            "reduced"
+        } else {
+            "unknown"
+        })
+    }
+
+    /// All rates of the country in ascending order, starting with the zero rate.
+    pub fn vat_rates(&self) -> Vec<Decimal> {
+        let mut rates = vec![Decimal::new(0, 0)];
+        rates.extend(self.get_reduced_rates());
+        rates.push(self.get_standard_rate());
+        rates
+    }
+}
+
+#[pyfunction]
+/// Returns the standard VAT rate of the country a VAT ID belongs to, together with
+/// whether that country is an EU member, e.g. `"ATU12345678" ==> (Decimal('0.2'), True)`.
+/// Knows the `EL` prefix of Greek VAT IDs. Raises ValueError for unknown prefixes.
+pub fn default_vat_rate_for_vat_id(vat_id: &str) -> PyResult<(Decimal, bool)> {
+    let normalized: String = vat_id
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+
+    let prefix = match normalized.get(..2) {
+        Some("EL") => "GR",
+        Some(prefix) if prefix.chars().all(|c| c.is_ascii_alphabetic()) => prefix,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Invalid VAT ID '{}'",
+                vat_id
+            )))
+        }
+    };
+
+    let country = Country::from_code(prefix)?;
+    Ok((country.get_standard_rate(), country.get_is_eu()))
+}
@@ -0,0 +1,136 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::errors::CurrencyMismatchError;
+
+/// Currencies (ISO 4217) of the supported countries plus USD and JPY
+#[allow(clippy::upper_case_acronyms)]
+#[pyclass(eq, eq_int, hash, frozen)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Currency {
+    BGN,
+    CHF,
+    CZK,
+    DKK,
+    EUR,
+    GBP,
+    HUF,
+    JPY,
+    NOK,
+    PLN,
+    RON,
+    SEK,
+    USD,
+}
+
+// (currency, code, minor unit exponent, symbol)
+type CurrencyData = (Currency, &'static str, u32, &'static str);
+
+const CURRENCIES: [CurrencyData; 13] = [
+    (Currency::BGN, "BGN", 2, "лв"),
+    (Currency::CHF, "CHF", 2, "CHF"),
+    (Currency::CZK, "CZK", 2, "Kč"),
+    (Currency::DKK, "DKK", 2, "kr."),
+    (Currency::EUR, "EUR", 2, "€"),
+    (Currency::GBP, "GBP", 2, "£"),
+    (Currency::HUF, "HUF", 2, "Ft"),
+    (Currency::JPY, "JPY", 0, "¥"),
+    (Currency::NOK, "NOK", 2, "kr"),
+    (Currency::PLN, "PLN", 2, "zł"),
+    (Currency::RON, "RON", 2, "lei"),
+    (Currency::SEK, "SEK", 2, "kr"),
+    (Currency::USD, "USD", 2, "$"),
+];
+
+impl Currency {
+    fn data(&self) -> &'static CurrencyData {
+        CURRENCIES
+            .iter()
+            .find(|data| data.0 == *self)
+            .expect("Every currency has data")
+    }
+}
+
+#[pymethods]
+impl Currency {
+    /// Looks up a currency by its ISO 4217 code, ignoring case.
+    #[staticmethod]
+    pub fn from_code(code: &str) -> PyResult<Self> {
+        let code = code.trim().to_uppercase();
+        CURRENCIES
+            .iter()
+            .find(|data| data.1 == code)
+            .map(|data| data.0)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown currency '{}'", code)))
+    }
+
+    /// All known currencies ordered by code.
+    #[staticmethod]
+    fn all() -> Vec<Self> {
+        CURRENCIES.iter().map(|data| data.0).collect()
+    }
+
+    #[getter(code)]
+    pub fn get_code(&self) -> &'static str {
+        self.data().1
+    }
+
+    /// Number of decimals of the minor unit, e.g. 2 for cents and 0 for JPY
+    #[getter(exponent)]
+    pub fn get_exponent(&self) -> u32 {
+        self.data().2
+    }
+
+    #[getter(symbol)]
+    pub fn get_symbol(&self) -> &'static str {
+        self.data().3
+    }
+}
+
+// Accepts a Currency or its code
+pub fn currency_extract(obj: &Bound<PyAny>) -> PyResult<Currency> {
+    match obj.extract::<Currency>() {
+        Ok(currency) => Ok(currency),
+        Err(_) => Currency::from_code(obj.extract::<&str>()?),
+    }
+}
+
+// Looks up a currency by its code or, if unambiguous, its symbol, e.g. "€" or "CHF"
+pub fn currency_from_token(token: &str) -> PyResult<Currency> {
+    if let Ok(currency) = Currency::from_code(token) {
+        return Ok(currency);
+    }
+    let matches: Vec<&CurrencyData> = CURRENCIES.iter().filter(|data| data.3 == token).collect();
+    match matches.as_slice() {
+        [data] => Ok(data.0),
+        [] => Err(PyValueError::new_err(format!(
+            "Unknown currency '{}'",
+            token
+        ))),
+        _ => Err(PyValueError::new_err(format!(
+            "Ambiguous currency symbol '{}', use one of {}",
+            token,
+            matches
+                .iter()
+                .map(|data| data.1)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+// Currency of the result of combining two amounts, amounts without currency adopt the
+// currency of the other one
+pub fn combined_currency(
+    left: Option<Currency>,
+    right: Option<Currency>,
+) -> PyResult<Option<Currency>> {
+    match (left, right) {
+        (Some(left), Some(right)) if left != right => Err(CurrencyMismatchError::new_err(format!(
+            "Can't combine {} with {}",
+            left.get_code(),
+            right.get_code()
+        ))),
+        _ => Ok(left.or(right)),
+    }
+}
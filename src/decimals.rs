@@ -1,32 +1,276 @@
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyFloat, PyLong, PyString, PyType};
 use regex::Regex;
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::{Decimal, RoundingStrategy};
+use std::cmp;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
+use crate::errors::InvalidAmountError;
 use crate::money::Money;
+use crate::stats::{count, Counter};
+
+// Maximum scale supported by rust_decimal
+const MAX_SCALE: i64 = 28;
+
+/// Whether negative zeros are normalized to positive zeros instead of emulating Python
+static NORMALIZE_NEGATIVE_ZERO: AtomicBool = AtomicBool::new(false);
+
+pub fn set_normalize_negative_zero(normalize: bool) {
+    NORMALIZE_NEGATIVE_ZERO.store(normalize, Ordering::Relaxed);
+}
+
+pub fn normalizes_negative_zero() -> bool {
+    NORMALIZE_NEGATIVE_ZERO.load(Ordering::Relaxed)
+}
+
+// Applies the configured negative zero policy
+pub fn zero_policy(value: Decimal) -> Decimal {
+    if normalizes_negative_zero() {
+        decimal_pos(value)
+    } else {
+        value
+    }
+}
+
+static DECIMAL_CLS: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static FRACTION_CLS: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+// Regexes are compiled on first use only
+static SCIENTIFIC_RE: OnceLock<Regex> = OnceLock::new();
+static EN_LOCALIZED_RE: OnceLock<Regex> = OnceLock::new();
+static DE_LOCALIZED_RE: OnceLock<Regex> = OnceLock::new();
+
+// Imports a Python class once and caches it for the lifetime of the interpreter
+fn cached_class<'py>(
+    cell: &'static GILOnceCell<Py<PyType>>,
+    py: Python<'py>,
+    module: &str,
+    name: &str,
+) -> PyResult<&'py Bound<'py, PyType>> {
+    cell.get_or_try_init(py, || -> PyResult<Py<PyType>> {
+        let cls = py.import_bound(module)?.getattr(name)?;
+        Ok(cls.downcast_into::<PyType>()?.unbind())
+    })
+    .map(|cls| cls.bind(py))
+}
+
+// Parses a decimal.Decimal from its string representation with a single str() call,
+// keeping the sign of negative zeros
+fn python_decimal_extract(obj: &Bound<PyAny>) -> PyResult<Option<Decimal>> {
+    let py_str = obj.str()?;
+    let s = py_str.to_cow()?;
+    let parsed = Decimal::from_str(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .ok()
+        .or_else(|| scientific_extract(&s));
+
+    Ok(parsed.map(|mut amount| {
+        if s.starts_with('-') {
+            amount.set_sign_negative(true);
+        }
+        amount
+    }))
+}
+
+// Calls the `__decimal__()` or `as_decimal()` conversion method of wrapper types
+fn decimal_protocol_extract<'py>(
+    obj: &Bound<'py, PyAny>,
+    decimal_cls: &Bound<'py, PyType>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    for method in ["__decimal__", "as_decimal"] {
+        if let Ok(convert) = obj.getattr(method) {
+            let value = convert.call0()?;
+            if !value.is_instance(decimal_cls)? {
+                return Err(PyTypeError::new_err(format!(
+                    "{}() must return a Decimal",
+                    method
+                )));
+            }
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+// Recognizes the zeros `sum()` and aggregations keep adding (int 0 and Decimal zeros)
+// with cheap type checks instead of the full extraction
+pub fn zero_operand_extract(obj: &Bound<PyAny>) -> PyResult<Option<Decimal>> {
+    if obj.is_exact_instance_of::<PyLong>() {
+        return Ok(if obj.is_truthy()? {
+            None
+        } else {
+            Some(Decimal::new(0, 0))
+        });
+    }
+
+    let decimal_cls = cached_class(&DECIMAL_CLS, obj.py(), "decimal", "Decimal")?;
+    if obj.is_exact_instance(decimal_cls) && !obj.is_truthy()? {
+        return Ok(python_decimal_extract(obj)?.map(zero_policy));
+    }
+
+    Ok(None)
+}
 
 pub fn decimal_extract(obj: Bound<PyAny>) -> PyResult<Decimal> {
-    if let Ok(_) = obj.extract::<Money>() {
-        Err(PyValueError::new_err("Invalid decimal"))
+    let decimal_cls = cached_class(&DECIMAL_CLS, obj.py(), "decimal", "Decimal")?;
+
+    // Fast path for the most common operand
+    if obj.is_exact_instance(decimal_cls) {
+        if let Some(amount) = python_decimal_extract(&obj)? {
+            return Ok(zero_policy(amount));
+        }
+    } else if obj.is_instance(decimal_cls)? {
+        // Subclasses might override __str__, so convert them to plain decimals first
+        return decimal_extract(decimal_cls.call1((obj,))?);
+    }
+
+    // Plain ints, strs and floats skip the generic conversion (and its second str() call)
+    if obj.is_exact_instance_of::<PyLong>() {
+        if let Ok(value) = obj.extract::<i64>() {
+            return Ok(Decimal::new(value, 0));
+        }
+    } else if obj.is_exact_instance_of::<PyString>() || obj.is_exact_instance_of::<PyFloat>() {
+        if let Some(amount) = python_decimal_extract(&obj)? {
+            return Ok(zero_policy(amount));
+        }
+    }
+
+    let invalid = || match obj.repr() {
+        Ok(repr) => InvalidAmountError::new_err(format!("Invalid amount {}", repr)),
+        Err(err) => err,
+    };
+    if obj.is_instance_of::<Money>() {
+        Err(invalid())
     } else if let Ok(mut amount) = obj.extract::<Decimal>() {
         if obj.to_string().trim_start().starts_with("-") {
             // Hack for minus zero
             amount.set_sign_negative(true);
         };
-        Ok(amount)
+        Ok(zero_policy(amount))
+    } else if let Some(value) = decimal_protocol_extract(&obj, decimal_cls)? {
+        decimal_extract(value)
     } else if let Ok(f) = obj.extract::<f64>() {
-        Ok(Decimal::from_f64(f).unwrap())
+        if !f.is_finite() {
+            return Err(InvalidAmountError::new_err(format!(
+                "Invalid amount {}: NaN and Infinity are not supported",
+                obj.repr()?
+            )));
+        }
+        Decimal::from_f64(f).ok_or_else(|| {
+            InvalidAmountError::new_err(format!("Invalid amount {}: out of range", f))
+        })
     } else if let Ok(s) = obj.extract::<&str>() {
-        let re = Regex::new(r"^0(\.0+)?[eE][+-]\d+$").unwrap();
-        if re.is_match(s) {
-            Ok(Decimal::new(0, 0))
-        } else {
-            Err(PyValueError::new_err("Invalid decimal"))
+        match scientific_extract(s) {
+            Some(amount) => Ok(zero_policy(amount)),
+            None => Err(invalid()),
+        }
+    } else {
+        Err(invalid())
+    }
+}
+
+// Parses scientific notation exactly, e.g. "1.5e3" or "-2E-2"
+fn scientific_extract(s: &str) -> Option<Decimal> {
+    let re = SCIENTIFIC_RE
+        .get_or_init(|| Regex::new(r"^([+-]?(?:\d+\.?\d*|\.\d+))[eE]([+-]?\d+)$").unwrap());
+    let captures = re.captures(s.trim())?;
+    let mut mantissa = Decimal::from_str(&captures[1]).ok()?;
+    let exponent = captures[2].parse::<i64>().ok()?;
+
+    if mantissa.is_zero() {
+        // Python keeps any exponent on zeros, we only keep the sign
+        let mut zero = Decimal::new(0, 0);
+        zero.set_sign_negative(captures[1].starts_with('-'));
+        return Some(zero);
+    }
+
+    let mut scale = mantissa.scale() as i64 - exponent;
+    if scale > MAX_SCALE {
+        // Trailing zeros might get us back into range
+        mantissa = mantissa.normalize();
+        scale = mantissa.scale() as i64 - exponent;
+        if scale > MAX_SCALE {
+            return None;
         }
+    }
+
+    if scale >= 0 {
+        mantissa.set_scale(scale as u32).ok()?;
+        Some(mantissa)
     } else {
-        Err(PyValueError::new_err("Invalid decimal"))
+        mantissa.set_scale(0).ok()?;
+        (0..-scale).try_fold(mantissa, |value, _| value.checked_mul(Decimal::TEN))
+    }
+}
+
+// Parses amounts with thousands separators and the decimal mark of the given locale
+pub fn localized_extract(s: &str, locale: &str) -> PyResult<Option<Decimal>> {
+    let (thousands, decimal, cell) = match locale {
+        "en" => (',', '.', &EN_LOCALIZED_RE),
+        "de" => ('.', ',', &DE_LOCALIZED_RE),
+        _ => return Err(PyValueError::new_err(format!("Unknown locale {}", locale))),
+    };
+
+    let re = cell.get_or_init(|| {
+        Regex::new(&format!(
+            r"^[+-]?(?:\d{{1,3}}(?:[{t} ]\d{{3}})+|\d+)(?:{d}\d+)?$",
+            t = regex::escape(&thousands.to_string()),
+            d = regex::escape(&decimal.to_string()),
+        ))
+        .unwrap()
+    });
+
+    let s = s.trim();
+    if !re.is_match(s) {
+        return Ok(None);
+    }
+
+    let cleaned: String = s
+        .chars()
+        .filter(|c| *c != thousands && *c != ' ')
+        .map(|c| if c == decimal { '.' } else { c })
+        .collect();
+    Ok(Decimal::from_str(&cleaned).ok())
+}
+
+// Extracts a decimal for validation, honoring the given locale for strings
+pub fn validation_extract(value: &Bound<PyAny>, locale: Option<&str>) -> PyResult<Option<Decimal>> {
+    match (locale, value.extract::<&str>()) {
+        (Some(locale), Ok(s)) => localized_extract(s, locale),
+        _ => Ok(value.extract::<Decimal>().ok()),
+    }
+}
+
+// Extracts numerator and denominator of a fractions.Fraction without going through float
+pub fn fraction_extract(obj: &Bound<PyAny>) -> Option<(Decimal, Decimal)> {
+    let fraction_cls = cached_class(&FRACTION_CLS, obj.py(), "fractions", "Fraction").ok()?;
+
+    if !obj.is_instance(fraction_cls).ok()? {
+        return None;
     }
+
+    let numerator = obj.getattr("numerator").ok()?.extract::<Decimal>().ok()?;
+    let denominator = obj.getattr("denominator").ok()?.extract::<Decimal>().ok()?;
+    Some((numerator, denominator))
+}
+
+// Multiplies a decimal with a fraction, dividing only at the very end
+pub fn decimal_mult_fraction(value: Decimal, numerator: Decimal, denominator: Decimal) -> Decimal {
+    decimal_div(decimal_mult(value, numerator), denominator)
+}
+
+// Applies unary plus to decimals the way of Python (normalizes negative zero)
+pub fn decimal_pos(right: Decimal) -> Decimal {
+    let mut result = right;
+    if result.is_zero() {
+        result.set_sign_positive(true);
+    }
+    result
 }
 
 // Negates decimals the way of Python
@@ -34,15 +278,71 @@ pub fn decimal_neg(right: Decimal) -> Decimal {
     if right == Decimal::new(-0, 0) {
         Decimal::new(0, 0)
     } else {
-        -right
+        zero_policy(-right)
     }
 }
 
+// Percentage by which actual deviates from budget, relative to the budget's magnitude
+// so that overspending is positive for costs and credits alike, None for a zero budget
+pub fn decimal_variance_pct(actual: Decimal, budget: Decimal) -> Option<Decimal> {
+    if budget.is_zero() {
+        return None;
+    }
+
+    let delta = decimal_add(actual, decimal_neg(budget));
+    Some(decimal_mult(
+        decimal_div(delta, budget.abs()),
+        Decimal::ONE_HUNDRED,
+    ))
+}
+
+// Compares decimals like Python's Decimal.compare_total, so 1.00 sorts before 1.0
+pub fn decimal_compare_total(left: Decimal, right: Decimal) -> cmp::Ordering {
+    match (left.is_sign_negative(), right.is_sign_negative()) {
+        (true, false) => cmp::Ordering::Less,
+        (false, true) => cmp::Ordering::Greater,
+        (negative, _) => left.cmp(&right).then_with(|| {
+            // Equal values order by exponent, which is reversed for negative values
+            if negative {
+                left.scale().cmp(&right.scale())
+            } else {
+                right.scale().cmp(&left.scale())
+            }
+        }),
+    }
+}
+
+// Splits a decimal into its floor and the remaining fraction scaled to the maximum scale,
+// giving integer tuples that order like the decimals and ignore trailing zeros
+pub fn decimal_sort_key(value: Decimal) -> (i128, i128) {
+    let floor = value.floor();
+    let fraction = value - floor;
+
+    (
+        floor.mantissa() / 10i128.pow(floor.scale()),
+        fraction.mantissa() * 10i128.pow(MAX_SCALE as u32 - fraction.scale()),
+    )
+}
+
+// Hashes the canonical representation of a decimal (trailing zeros stripped, no negative
+// zero) with 64-bit FNV-1a, which doesn't depend on the process or the Rust version
+pub fn decimal_stable_hash(value: Decimal) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    decimal_pos(value.normalize())
+        .to_string()
+        .bytes()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        })
+}
+
 // Adds decimals the way of Python
 pub fn decimal_add(left: Decimal, right: Decimal) -> Decimal {
     let zero = Decimal::new(0, 0);
 
-    if left.abs() == zero && right.abs() == zero {
+    zero_policy(if left.abs() == zero && right.abs() == zero {
         if left.is_sign_negative() && right.is_sign_negative() {
             -zero
         } else {
@@ -50,14 +350,14 @@ pub fn decimal_add(left: Decimal, right: Decimal) -> Decimal {
         }
     } else {
         left + right
-    }
+    })
 }
 
 // Multiplies decimals the way of Python
 pub fn decimal_mult(left: Decimal, right: Decimal) -> Decimal {
     let zero = Decimal::new(0, 0);
 
-    if left.abs() == zero || right.abs() == zero {
+    zero_policy(if left.abs() == zero || right.abs() == zero {
         if left.is_sign_negative() == right.is_sign_negative() {
             zero
         } else {
@@ -65,14 +365,14 @@ pub fn decimal_mult(left: Decimal, right: Decimal) -> Decimal {
         }
     } else {
         left * right
-    }
+    })
 }
 
 // Divides decimals the way of Python
 pub fn decimal_div(left: Decimal, right: Decimal) -> Decimal {
     let zero = Decimal::new(0, 0);
 
-    if left.abs() == zero && right.abs() != zero {
+    zero_policy(if left.abs() == zero && right.abs() != zero {
         if left.is_sign_negative() == right.is_sign_negative() {
             zero
         } else {
@@ -80,15 +380,161 @@ pub fn decimal_div(left: Decimal, right: Decimal) -> Decimal {
         }
     } else {
         left / right
+    })
+}
+
+// Remainder with the sign of the left operand like Decimal's `%`
+pub fn decimal_rem(left: Decimal, right: Decimal) -> PyResult<Decimal> {
+    left.checked_rem(right).map(zero_policy).ok_or_else(|| {
+        InvalidAmountError::new_err(format!("Invalid amount {} % {}: out of range", left, right))
+    })
+}
+
+// Integer part of the quotient like Decimal's `//`, truncating towards zero
+pub fn decimal_floordiv(left: Decimal, right: Decimal) -> PyResult<Decimal> {
+    let quotient = decimal_rem(left, right)
+        .ok()
+        .and_then(|remainder| left.checked_sub(remainder))
+        .and_then(|multiple| multiple.checked_div(right))
+        .ok_or_else(|| {
+            InvalidAmountError::new_err(format!(
+                "Invalid amount {} // {}: out of range",
+                left, right
+            ))
+        })?
+        .trunc();
+
+    Ok(zero_policy(if quotient.is_zero() {
+        if left.is_sign_negative() == right.is_sign_negative() {
+            Decimal::ZERO
+        } else {
+            -Decimal::ZERO
+        }
+    } else {
+        quotient
+    }))
+}
+
+// Splits `amount` into parts of the given scale proportional to `ratios` with the
+// largest remainder method, so that the parts add up to the amount exactly. Ties in the
+// remainders go to the earlier parts.
+pub fn decimal_allocate(amount: Decimal, ratios: &[Decimal], scale: u32) -> PyResult<Vec<Decimal>> {
+    if ratios.is_empty() {
+        return Err(PyValueError::new_err("At least one ratio is required"));
+    }
+    if ratios
+        .iter()
+        .any(|ratio| ratio.is_sign_negative() && !ratio.is_zero())
+    {
+        return Err(PyValueError::new_err("Ratios must not be negative"));
+    }
+
+    let out_of_range = || PyValueError::new_err("The allocation is out of range");
+    let ratio_scale = ratios.iter().map(Decimal::scale).max().unwrap_or_default();
+    let weights = ratios
+        .iter()
+        .map(|ratio| {
+            let mut weight = ratio.abs();
+            weight.rescale(ratio_scale);
+            weight.mantissa()
+        })
+        .collect::<Vec<_>>();
+    let total = weights
+        .iter()
+        .try_fold(0_i128, |total, weight| total.checked_add(*weight))
+        .ok_or_else(out_of_range)?;
+    if total == 0 {
+        return Err(PyValueError::new_err("Ratios must not all be zero"));
+    }
+
+    let mut scaled = amount;
+    scaled.rescale(scale);
+    if scaled.scale() != scale {
+        return Err(out_of_range());
+    }
+    let units = scaled.mantissa().abs();
+
+    let mut shares = Vec::with_capacity(weights.len());
+    for weight in &weights {
+        let product = units.checked_mul(*weight).ok_or_else(out_of_range)?;
+        shares.push((product / total, product % total));
+    }
+
+    let allocated: i128 = shares.iter().map(|(share, _)| share).sum();
+    let mut order = (0..shares.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| cmp::Reverse(shares[*index].1));
+    for index in order.into_iter().take((units - allocated) as usize) {
+        shares[index].0 += 1;
+    }
+
+    Ok(shares
+        .into_iter()
+        .map(|(share, _)| {
+            let mut part = Decimal::from_i128_with_scale(share, scale);
+            part.set_sign_negative(amount.is_sign_negative());
+            zero_policy(part)
+        })
+        .collect())
+}
+
+/// Rounding used by `round` (like Python's built-in round)
+pub const DEFAULT_ROUNDING: &str = "ROUND_HALF_EVEN";
+
+// Maps the rounding constants of Python's decimal module, e.g. decimal.ROUND_HALF_UP,
+// also accepting their short lowercase names like "half_up"
+pub fn rounding_strategy_extract(name: &str) -> PyResult<RoundingStrategy> {
+    let upper = name.to_uppercase();
+    let full = match upper.starts_with("ROUND_") {
+        true => upper,
+        false => format!("ROUND_{}", upper),
+    };
+    match full.as_str() {
+        "ROUND_HALF_EVEN" => Ok(RoundingStrategy::MidpointNearestEven),
+        "ROUND_HALF_UP" => Ok(RoundingStrategy::MidpointAwayFromZero),
+        "ROUND_HALF_DOWN" => Ok(RoundingStrategy::MidpointTowardZero),
+        "ROUND_UP" => Ok(RoundingStrategy::AwayFromZero),
+        "ROUND_DOWN" => Ok(RoundingStrategy::ToZero),
+        "ROUND_CEILING" => Ok(RoundingStrategy::ToPositiveInfinity),
+        "ROUND_FLOOR" => Ok(RoundingStrategy::ToNegativeInfinity),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown rounding strategy {}",
+            name
+        ))),
     }
 }
 
+// Formats like `format(Decimal(...), spec)`, additionally accepting the money type "n":
+// fixed-point like "f" with `precision` decimals unless the spec sets them, e.g. ",n"
+pub fn decimal_format(py: Python, value: Decimal, spec: &str, precision: u32) -> PyResult<String> {
+    let spec = match spec.strip_suffix('n') {
+        Some(rest) if rest.contains('.') => format!("{}f", rest),
+        Some(rest) => format!("{}.{}f", rest, precision),
+        None => spec.to_string(),
+    };
+    value
+        .into_py(py)
+        .call_method1(py, "__format__", (spec,))?
+        .extract(py)
+}
+
 // Rounds decimals the way of Python
 pub fn decimal_round(value: Decimal, scale: i32) -> Decimal {
+    decimal_round_with_strategy(value, scale, RoundingStrategy::MidpointNearestEven)
+}
+
+pub fn decimal_round_with_strategy(
+    value: Decimal,
+    scale: i32,
+    strategy: RoundingStrategy,
+) -> Decimal {
+    count(Counter::Rounding);
     if scale >= 0 {
-        return value.round_dp_with_strategy(scale as u32, RoundingStrategy::MidpointNearestEven);
+        return zero_policy(value.round_dp_with_strategy(scale as u32, strategy));
     }
 
     let factor = Decimal::new(10_i64.pow((-scale) as u32), 0);
-    decimal_mult(decimal_div(value, factor).round(), factor)
+    decimal_mult(
+        decimal_div(value, factor).round_dp_with_strategy(0, strategy),
+        factor,
+    )
 }
@@ -0,0 +1,62 @@
+use pyo3::create_exception;
+use pyo3::exceptions::{PyValueError, PyZeroDivisionError};
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyDict, PyType};
+
+create_exception!(
+    alasco_money,
+    MoneyError,
+    PyValueError,
+    "Base class of the errors raised by alasco_money."
+);
+
+create_exception!(
+    alasco_money,
+    InvalidAmountError,
+    MoneyError,
+    "Value can't be used as an amount (e.g. NaN or Infinity)."
+);
+
+create_exception!(
+    alasco_money,
+    CurrencyMismatchError,
+    MoneyError,
+    "Amounts in different currencies can't be combined."
+);
+
+create_exception!(
+    alasco_money,
+    SepaAmountError,
+    MoneyError,
+    "Amount violates the SEPA (pain.001) constraints."
+);
+
+static DIVISION_BY_ZERO: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+// Subclass of both MoneyError and ZeroDivisionError (like decimal.DivisionByZero), which
+// create_exception! can't declare as it only takes one base
+pub fn division_by_zero_type<'py>(py: Python<'py>) -> PyResult<&'py Bound<'py, PyType>> {
+    DIVISION_BY_ZERO
+        .get_or_try_init(py, || {
+            let bases = (
+                py.get_type_bound::<MoneyError>(),
+                py.get_type_bound::<PyZeroDivisionError>(),
+            );
+            let namespace = PyDict::new_bound(py);
+            namespace.set_item("__module__", "alasco_money")?;
+            namespace.set_item("__doc__", "Amount or factor divided by zero.")?;
+            let cls = py
+                .get_type_bound::<PyType>()
+                .call1(("DivisionByZero", bases, namespace))?;
+            Ok::<_, PyErr>(cls.downcast_into::<PyType>()?.unbind())
+        })
+        .map(|cls| cls.bind(py))
+}
+
+pub fn division_by_zero() -> PyErr {
+    Python::with_gil(|py| match division_by_zero_type(py) {
+        Ok(cls) => PyErr::from_type_bound(cls.clone(), "Division by zero"),
+        Err(err) => err,
+    })
+}
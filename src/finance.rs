@@ -0,0 +1,518 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyIterator};
+use regex::Regex;
+use rust_decimal::{Decimal, MathematicalOps};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::currency::{combined_currency, Currency};
+use crate::decimals::*;
+use crate::money::Money;
+
+static ECB_DAY_RE: OnceLock<Regex> = OnceLock::new();
+static ECB_RATE_RE: OnceLock<Regex> = OnceLock::new();
+
+// Python's date.toordinal() of 1970-01-01
+const UNIX_EPOCH_ORDINAL: i64 = 719_163;
+
+/// German base rate (Basiszinssatz, § 247 BGB) as (year, month, day, rate in hundredths of
+/// a percent), each valid from its date until the next one. The last rate keeps applying,
+/// pass the newer rates explicitly until this table is extended.
+const GERMAN_BASE_RATES: [(i64, i64, i64, i64); 8] = [
+    (2015, 1, 1, -83),
+    (2016, 7, 1, -88),
+    (2023, 1, 1, 162),
+    (2023, 7, 1, 312),
+    (2024, 1, 1, 362),
+    (2024, 7, 1, 337),
+    (2025, 1, 1, 227),
+    (2025, 7, 1, 127),
+];
+
+// Converts a civil date into Python's proleptic ordinal (days_from_civil by H. Hinnant)
+fn ordinal_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468 + UNIX_EPOCH_ORDINAL
+}
+
+// Converts Python's proleptic ordinal into (year, month, day)
+fn civil_from_ordinal(ordinal: i64) -> (i64, i64, i64) {
+    let z = ordinal - UNIX_EPOCH_ORDINAL + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (
+        if month <= 2 {
+            yoe + era * 400 + 1
+        } else {
+            yoe + era * 400
+        },
+        month,
+        day,
+    )
+}
+
+// Days of date-like objects (anything with `toordinal()`, e.g. datetime.date)
+pub fn ordinal_extract(obj: &Bound<PyAny>) -> PyResult<i64> {
+    obj.call_method0("toordinal")?.extract::<i64>()
+}
+
+fn date_from_ordinal(py: Python, ordinal: i64) -> PyResult<PyObject> {
+    Ok(py
+        .import_bound("datetime")?
+        .getattr("date")?
+        .call_method1("fromordinal", (ordinal,))?
+        .unbind())
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DayCount {
+    /// Actual days over 365 days per year
+    Act365,
+    /// Actual days over 360 days per year
+    Act360,
+    /// 30 days per month, 360 days per year (30E/360, "deutsche Zinsmethode")
+    Thirty360,
+}
+
+pub fn day_count_extract(name: &str) -> PyResult<DayCount> {
+    match name {
+        "act/365" => Ok(DayCount::Act365),
+        "act/360" => Ok(DayCount::Act360),
+        "30/360" => Ok(DayCount::Thirty360),
+        _ => Err(PyValueError::new_err(format!(
+            "Unknown day count convention '{}'",
+            name
+        ))),
+    }
+}
+
+impl DayCount {
+    /// Days between two ordinals, counting the start but not the end day
+    pub fn days(&self, start: i64, end: i64) -> i64 {
+        match self {
+            DayCount::Act365 | DayCount::Act360 => end - start,
+            DayCount::Thirty360 => {
+                let (start_year, start_month, start_day) = civil_from_ordinal(start);
+                let (end_year, end_month, end_day) = civil_from_ordinal(end);
+                360 * (end_year - start_year)
+                    + 30 * (end_month - start_month)
+                    + (end_day.min(30) - start_day.min(30))
+            }
+        }
+    }
+
+    pub fn days_per_year(&self) -> i64 {
+        match self {
+            DayCount::Act365 => 365,
+            DayCount::Act360 | DayCount::Thirty360 => 360,
+        }
+    }
+
+    pub fn year_fraction(&self, start: i64, end: i64) -> Decimal {
+        decimal_div(
+            Decimal::from(self.days(start, end)),
+            Decimal::from(self.days_per_year()),
+        )
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (principal, start, end, spread=None, base_rates=None, day_count="act/365"))]
+/// Statutory default interest (Verzugszinsen, § 288 BGB) on `principal` from `start` until
+/// `end` (exclusive) at the base rate plus `spread` percentage points: 5 (default) when a
+/// consumer is involved, 9 between businesses. `base_rates` replaces the built-in schedule
+/// of the Bundesbank base rate with (valid from date, percent) pairs.
+/// Returns the itemized periods of constant rate, as dicts with start, end, days, rate
+/// (percent) and interest rounded to cents, and their total.
+pub fn late_payment_interest(
+    py: Python,
+    principal: Money,
+    start: Bound<PyAny>,
+    end: Bound<PyAny>,
+    spread: Option<Bound<PyAny>>,
+    base_rates: Option<Bound<PyAny>>,
+    day_count: &str,
+) -> PyResult<(Vec<PyObject>, Money)> {
+    let day_count = day_count_extract(day_count)?;
+    let spread = match spread {
+        Some(spread) => decimal_extract(spread)?,
+        None => Decimal::new(5, 0),
+    };
+    let (start, end) = (ordinal_extract(&start)?, ordinal_extract(&end)?);
+    if end < start {
+        return Err(PyValueError::new_err("The end lies before the start"));
+    }
+
+    let mut schedule: Vec<(i64, Decimal)> = match base_rates {
+        Some(base_rates) => PyIterator::from_bound_object(&base_rates)?
+            .map(|item| {
+                let (valid_from, rate) = item?.extract::<(Bound<PyAny>, Bound<PyAny>)>()?;
+                Ok((ordinal_extract(&valid_from)?, decimal_extract(rate)?))
+            })
+            .collect::<PyResult<_>>()?,
+        None => GERMAN_BASE_RATES
+            .iter()
+            .map(|(year, month, day, rate)| {
+                (
+                    ordinal_from_civil(*year, *month, *day),
+                    Decimal::new(*rate, 2),
+                )
+            })
+            .collect(),
+    };
+    schedule.sort_by_key(|(valid_from, _)| *valid_from);
+
+    if schedule
+        .first()
+        .is_none_or(|(valid_from, _)| start < *valid_from)
+    {
+        return Err(PyValueError::new_err("No base rate known for the start"));
+    }
+
+    let mut items = Vec::new();
+    let mut total = Decimal::new(0, 0);
+
+    for (index, (valid_from, base_rate)) in schedule.iter().enumerate() {
+        let valid_until = schedule.get(index + 1).map_or(i64::MAX, |next| next.0);
+        let (item_start, item_end) = (start.max(*valid_from), end.min(valid_until));
+        if item_start >= item_end {
+            continue;
+        }
+
+        let rate = decimal_add(*base_rate, spread);
+        let interest = decimal_round(
+            decimal_mult(
+                decimal_mult(principal.amount, decimal_div(rate, Decimal::ONE_HUNDRED)),
+                day_count.year_fraction(item_start, item_end),
+            ),
+            2,
+        );
+        total = decimal_add(total, interest);
+
+        let item = PyDict::new_bound(py);
+        item.set_item("start", date_from_ordinal(py, item_start)?)?;
+        item.set_item("end", date_from_ordinal(py, item_end)?)?;
+        item.set_item("days", day_count.days(item_start, item_end))?;
+        item.set_item("rate", rate)?;
+        item.set_item(
+            "interest",
+            Money {
+                amount: interest,
+                currency: principal.currency,
+            }
+            .into_py(py),
+        )?;
+        items.push(item.into_any().unbind());
+    }
+
+    Ok((
+        items,
+        Money {
+            amount: total,
+            currency: principal.currency,
+        },
+    ))
+}
+
+// Divides by (1 + rate)^periods, exact for whole periods and via exp/ln for fractional ones
+fn discounted(amount: Decimal, rate: Decimal, periods: Decimal) -> PyResult<Decimal> {
+    if rate <= Decimal::NEGATIVE_ONE {
+        return Err(PyValueError::new_err("The rate must be above -100%"));
+    }
+
+    let growth = decimal_add(Decimal::ONE, rate).checked_powd(periods);
+    growth
+        .filter(|growth| !growth.is_zero())
+        .and_then(|growth| amount.checked_div(growth))
+        .map(zero_policy)
+        .ok_or_else(|| PyValueError::new_err("The discount factor is out of range"))
+}
+
+#[pyfunction]
+/// Present value of `money` due in `periods` (may be fractional) at `rate` per period,
+/// given as fraction (0.05 for 5%). The result is not rounded.
+pub fn discount(money: Money, rate: Bound<PyAny>, periods: Bound<PyAny>) -> PyResult<Money> {
+    Ok(Money {
+        amount: discounted(
+            money.amount,
+            decimal_extract(rate)?,
+            decimal_extract(periods)?,
+        )?,
+        currency: money.currency,
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (rate, cash_flows, day_count="act/365"))]
+/// Net present value of (period, money) or (date, money) pairs at `rate` per period,
+/// given as fraction (0.05 for 5%). Dates are discounted by their year fraction since the
+/// earliest date under `day_count`, with `rate` per year. The result is not rounded.
+pub fn npv(rate: Bound<PyAny>, cash_flows: Bound<PyAny>, day_count: &str) -> PyResult<Money> {
+    let rate = decimal_extract(rate)?;
+    let day_count = day_count_extract(day_count)?;
+
+    let mut periods = Vec::new();
+    let mut dates = Vec::new();
+    let mut currency = None;
+    for item in PyIterator::from_bound_object(&cash_flows)? {
+        let (when, money) = item?.extract::<(Bound<PyAny>, Money)>()?;
+        currency = combined_currency(currency, money.currency)?;
+        if when.hasattr("toordinal")? {
+            dates.push((ordinal_extract(&when)?, money.amount));
+        } else {
+            periods.push((decimal_extract(when)?, money.amount));
+        }
+    }
+
+    if !periods.is_empty() && !dates.is_empty() {
+        return Err(PyValueError::new_err("Cash flows mix dates and periods"));
+    }
+    if let Some(first) = dates.iter().map(|(date, _)| *date).min() {
+        periods = dates
+            .into_iter()
+            .map(|(date, amount)| (day_count.year_fraction(first, date), amount))
+            .collect();
+    }
+
+    let mut total = Decimal::new(0, 0);
+    for (when, amount) in periods {
+        total = decimal_add(total, discounted(amount, rate, when)?);
+    }
+    Ok(Money {
+        amount: total,
+        currency,
+    })
+}
+
+// Payment per period paying off `principal` in `periods` equal installments, not rounded
+fn annuity_payment(principal: Decimal, rate: Decimal, periods: u32) -> PyResult<Decimal> {
+    if periods == 0 {
+        return Err(PyValueError::new_err(
+            "The term must be at least one period",
+        ));
+    }
+    if rate <= Decimal::NEGATIVE_ONE {
+        return Err(PyValueError::new_err("The rate must be above -100%"));
+    }
+    if rate.is_zero() {
+        return Ok(decimal_div(principal, Decimal::from(periods)));
+    }
+
+    decimal_add(Decimal::ONE, rate)
+        .checked_powu(periods.into())
+        .and_then(|growth| Decimal::ONE.checked_div(growth))
+        .and_then(|discount| Decimal::ONE.checked_sub(discount))
+        .filter(|divisor| !divisor.is_zero())
+        .and_then(|divisor| principal.checked_mul(rate)?.checked_div(divisor))
+        .map(zero_policy)
+        .ok_or_else(|| PyValueError::new_err("The annuity is out of range"))
+}
+
+#[pyfunction]
+/// Installment paying off `principal` with interest at `rate` per period, given as
+/// fraction (0.05 for 5%), in `periods` equal payments, rounded to cents.
+pub fn annuity(principal: Money, rate: Bound<PyAny>, periods: u32) -> PyResult<Money> {
+    Ok(Money {
+        amount: decimal_round(
+            annuity_payment(principal.amount, decimal_extract(rate)?, periods)?,
+            2,
+        ),
+        currency: principal.currency,
+    })
+}
+
+#[pyfunction]
+/// Amortization schedule of an annuity loan (see `annuity`) as dicts with period
+/// (starting at 1), payment, interest, principal and the remaining balance, all rounded
+/// to cents. The last payment absorbs the rounding differences, so the balance ends at 0.
+pub fn amortization_schedule(
+    py: Python,
+    principal: Money,
+    rate: Bound<PyAny>,
+    periods: u32,
+) -> PyResult<Vec<PyObject>> {
+    let rate = decimal_extract(rate)?;
+    let payment = decimal_round(annuity_payment(principal.amount, rate, periods)?, 2);
+
+    let mut rows = Vec::new();
+    let mut balance = principal.amount;
+
+    for period in 1..=periods {
+        let interest = decimal_round(decimal_mult(balance, rate), 2);
+        let repayment = if period == periods {
+            balance
+        } else {
+            decimal_add(payment, decimal_neg(interest))
+        };
+        balance = decimal_add(balance, decimal_neg(repayment));
+
+        let row = PyDict::new_bound(py);
+        row.set_item("period", period)?;
+        row.set_item(
+            "payment",
+            Money {
+                amount: decimal_add(interest, repayment),
+                currency: principal.currency,
+            }
+            .into_py(py),
+        )?;
+        row.set_item(
+            "interest",
+            Money {
+                amount: interest,
+                currency: principal.currency,
+            }
+            .into_py(py),
+        )?;
+        row.set_item(
+            "principal",
+            Money {
+                amount: repayment,
+                currency: principal.currency,
+            }
+            .into_py(py),
+        )?;
+        row.set_item(
+            "balance",
+            Money {
+                amount: balance,
+                currency: principal.currency,
+            }
+            .into_py(py),
+        )?;
+        rows.push(row.into_any().unbind());
+    }
+
+    Ok(rows)
+}
+
+#[pyfunction]
+/// Realized exchange gain or loss of `original`, a foreign currency amount (positive for
+/// receivables, negative for payables), that was booked at `booked_rate` and settled at
+/// `settlement_rate` (both in local currency per foreign unit). Both local amounts are
+/// rounded to cents before taking the difference. Returns the signed difference with
+/// "gain", "loss" or "none" as direction. The difference has no currency as the local
+/// one is unknown.
+pub fn fx_gain_loss(
+    original: Money,
+    booked_rate: Bound<PyAny>,
+    settlement_rate: Bound<PyAny>,
+) -> PyResult<(Money, &'static str)> {
+    let (booked_rate, settlement_rate) = (
+        decimal_extract(booked_rate)?,
+        decimal_extract(settlement_rate)?,
+    );
+    if booked_rate <= Decimal::new(0, 0) || settlement_rate <= Decimal::new(0, 0) {
+        return Err(PyValueError::new_err("Exchange rate must be positive"));
+    }
+
+    let booked = decimal_round(decimal_mult(original.amount, booked_rate), 2);
+    let settled = decimal_round(decimal_mult(original.amount, settlement_rate), 2);
+    let difference = decimal_add(settled, decimal_neg(booked));
+
+    let direction = if difference.is_zero() {
+        "none"
+    } else if difference.is_sign_positive() {
+        "gain"
+    } else {
+        "loss"
+    };
+    Ok((
+        Money {
+            amount: difference,
+            currency: None,
+        },
+        direction,
+    ))
+}
+
+// (code, rate) pairs of the first day in an ECB eurofxref XML file, the newest one
+fn ecb_xml_rates(text: &str) -> Vec<(&str, &str)> {
+    let day_re =
+        ECB_DAY_RE.get_or_init(|| Regex::new(r#"<Cube\s+time=['"][^'"]*['"]\s*>"#).unwrap());
+    let rate_re = ECB_RATE_RE.get_or_init(|| {
+        Regex::new(r#"<Cube\s+currency=['"]([A-Za-z]{3})['"]\s+rate=['"]([^'"]*)['"]\s*/>"#)
+            .unwrap()
+    });
+
+    let start = day_re.find(text).map_or(0, |day| day.end());
+    let end = day_re
+        .find_at(text, start)
+        .map_or(text.len(), |day| day.start());
+    rate_re
+        .captures_iter(&text[start..end])
+        .map(|captures| {
+            let (_, [code, rate]) = captures.extract();
+            (code, rate)
+        })
+        .collect()
+}
+
+// (code, rate) pairs of the first row in an ECB eurofxref CSV file, the newest one
+fn ecb_csv_rates(text: &str) -> Vec<(&str, &str)> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    match (lines.next(), lines.next()) {
+        (Some(header), Some(row)) => header
+            .split(',')
+            .zip(row.split(','))
+            .skip(1)
+            .map(|(code, rate)| (code.trim(), rate.trim()))
+            .filter(|(code, _)| !code.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[pyfunction]
+/// Reads the ECB euro foreign exchange reference rates from the downloaded XML or CSV
+/// file (daily or history, the newest day is used) as `{Currency: rate}`, the rate being
+/// the amount of the currency per euro as `MoneyWithVAT.convert` expects it. Currencies
+/// unknown to Currency and missing rates ("N/A") are skipped.
+pub fn ecb_reference_rates(data: Bound<PyAny>, py: Python) -> PyResult<PyObject> {
+    let text = match data.downcast::<PyBytes>() {
+        Ok(bytes) => std::str::from_utf8(bytes.as_bytes())
+            .map_err(|_| PyValueError::new_err("ECB reference rates must be UTF-8"))?
+            .to_string(),
+        Err(_) => data.extract::<String>()?,
+    };
+    let pairs = if text.trim_start().starts_with('<') {
+        ecb_xml_rates(&text)
+    } else {
+        ecb_csv_rates(&text)
+    };
+
+    let rates = PyDict::new_bound(py);
+    for (code, rate) in pairs {
+        let Ok(currency) = Currency::from_code(code) else {
+            continue;
+        };
+        if rate.is_empty() || rate == "N/A" {
+            continue;
+        }
+        match Decimal::from_str(rate) {
+            Ok(rate) if rate > Decimal::new(0, 0) => rates.set_item(currency.into_py(py), rate)?,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid ECB reference rate '{}' for {}",
+                    rate, code
+                )))
+            }
+        }
+    }
+
+    if rates.is_empty() {
+        return Err(PyValueError::new_err("No ECB reference rates found"));
+    }
+    Ok(rates.into())
+}
@@ -0,0 +1,256 @@
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use crate::currency::{combined_currency, Currency};
+use crate::decimals::*;
+use crate::errors::{division_by_zero, InvalidAmountError};
+use crate::money::Money;
+
+// Deepest nesting of parentheses and unary signs, deeper formulas would exhaust the stack
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, Copy)]
+enum Value {
+    Money(Decimal, Option<Currency>),
+    Number(Decimal),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Decimal),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+}
+
+fn tokenize(formula: &str) -> PyResult<Vec<Token>> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            let literal: String = chars[start..pos].iter().collect();
+            let number = Decimal::from_str(&literal)
+                .map_err(|_| PyValueError::new_err(format!("Invalid number '{}'", literal)))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+        } else if "+-*/".contains(c) {
+            tokens.push(Token::Op(c));
+            pos += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            pos += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            pos += 1;
+        } else {
+            return Err(PyValueError::new_err(format!(
+                "Unexpected character '{}' at position {}",
+                c, pos
+            )));
+        }
+    }
+
+    Ok(tokens)
+}
+
+// Computes `left op right` like decimal_add/_mult/_div, raising instead of panicking
+// when the result doesn't fit a decimal
+fn checked(op: char, left: Decimal, right: Decimal) -> PyResult<Decimal> {
+    let result = match op {
+        '+' => left.checked_add(right).map(|_| decimal_add(left, right)),
+        '*' => left.checked_mul(right).map(|_| decimal_mult(left, right)),
+        _ => left.checked_div(right).map(|_| decimal_div(left, right)),
+    };
+    result.ok_or_else(|| {
+        InvalidAmountError::new_err(format!(
+            "Invalid amount {} {} {}: out of range",
+            left, op, right
+        ))
+    })
+}
+
+// Applies the operators with the same semantics as the Money dunder methods
+fn apply(op: char, left: Value, right: Value) -> PyResult<Value> {
+    let zero = Decimal::new(0, 0);
+
+    match (op, left, right) {
+        ('+', Value::Number(l), Value::Number(r)) => Ok(Value::Number(checked(op, l, r)?)),
+        ('+', Value::Money(l, left_currency), Value::Money(r, right_currency)) => Ok(Value::Money(
+            checked(op, l, r)?,
+            combined_currency(left_currency, right_currency)?,
+        )),
+        ('+', Value::Money(l, currency), Value::Number(r))
+        | ('+', Value::Number(l), Value::Money(r, currency)) => {
+            Ok(Value::Money(checked(op, l, r)?, currency))
+        }
+        ('-', l, r) => apply('+', l, negate(r)),
+        ('*', Value::Money(..), Value::Money(..)) => {
+            Err(PyTypeError::new_err("Unsupported operand"))
+        }
+        ('*', Value::Number(l), Value::Number(r)) => Ok(Value::Number(checked(op, l, r)?)),
+        ('*', Value::Money(l, currency), Value::Number(r))
+        | ('*', Value::Number(l), Value::Money(r, currency)) => {
+            Ok(Value::Money(checked(op, l, r)?, currency))
+        }
+        ('/', _, Value::Money(r, _) | Value::Number(r)) if r == zero => Err(division_by_zero()),
+        ('/', Value::Money(l, left_currency), Value::Money(r, right_currency)) => {
+            combined_currency(left_currency, right_currency)?;
+            Ok(Value::Number(checked(op, l, r)?))
+        }
+        ('/', Value::Number(l), Value::Number(r)) => Ok(Value::Number(checked(op, l, r)?)),
+        ('/', Value::Money(l, currency), Value::Number(r))
+        | ('/', Value::Number(l), Value::Money(r, currency)) => {
+            Ok(Value::Money(checked(op, l, r)?, currency))
+        }
+        _ => Err(PyValueError::new_err(format!("Unknown operator '{}'", op))),
+    }
+}
+
+fn negate(value: Value) -> Value {
+    match value {
+        Value::Money(amount, currency) => Value::Money(decimal_neg(amount), currency),
+        Value::Number(amount) => Value::Number(decimal_neg(amount)),
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+    variables: &'a [(String, Value)],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    // expression := term (("+" | "-") term)*
+    fn expression(&mut self) -> PyResult<Value> {
+        let mut value = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            value = apply(op, value, self.term()?)?;
+        }
+        Ok(value)
+    }
+
+    // term := factor (("*" | "/") factor)*
+    fn term(&mut self) -> PyResult<Value> {
+        let mut value = self.factor()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek().cloned() {
+            self.pos += 1;
+            value = apply(op, value, self.factor()?)?;
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> PyResult<Value> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(PyValueError::new_err(format!(
+                "Formula is nested deeper than {} levels",
+                MAX_DEPTH
+            )));
+        }
+        let value = self.signed_factor();
+        self.depth -= 1;
+        value
+    }
+
+    // factor := ("+" | "-") factor | number | variable | "(" expression ")"
+    fn signed_factor(&mut self) -> PyResult<Value> {
+        match self.next() {
+            Some(Token::Op('-')) => Ok(negate(self.factor()?)),
+            Some(Token::Op('+')) => self.factor(),
+            Some(Token::Number(number)) => Ok(Value::Number(number)),
+            Some(Token::Ident(name)) => self
+                .variables
+                .iter()
+                .find(|(known, _)| *known == name)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown variable '{}'", name))),
+            Some(Token::LParen) => {
+                let value = self.expression()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(value),
+                    _ => Err(PyValueError::new_err("Missing closing parenthesis")),
+                }
+            }
+            Some(token) => Err(PyValueError::new_err(format!(
+                "Unexpected token {:?}",
+                token
+            ))),
+            None => Err(PyValueError::new_err("Unexpected end of formula")),
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (formula, variables=None))]
+/// Evaluates an arithmetic formula (+, -, *, /, parentheses) over Money and decimal variables
+/// with the same semantics as the Money operators. Returns Money or Decimal.
+pub fn evaluate(py: Python, formula: &str, variables: Option<Bound<PyDict>>) -> PyResult<PyObject> {
+    let mut values: Vec<(String, Value)> = Vec::new();
+
+    if let Some(dict) = variables {
+        for (key, item) in dict.iter() {
+            let name = key.extract::<String>()?;
+            let value = if let Ok(money) = item.extract::<Money>() {
+                Value::Money(money.amount, money.currency)
+            } else if let Ok(decimal) = decimal_extract(item) {
+                Value::Number(decimal)
+            } else {
+                return Err(PyTypeError::new_err(format!(
+                    "Unsupported value for variable '{}'",
+                    name
+                )));
+            };
+            values.push((name, value));
+        }
+    }
+
+    let mut parser = Parser {
+        tokens: tokenize(formula)?,
+        pos: 0,
+        depth: 0,
+        variables: &values,
+    };
+    let result = parser.expression()?;
+
+    if let Some(token) = parser.peek() {
+        return Err(PyValueError::new_err(format!(
+            "Unexpected token {:?}",
+            token
+        )));
+    }
+
+    Ok(match result {
+        Value::Money(amount, currency) => Money { amount, currency }.into_py(py),
+        Value::Number(amount) => amount.into_py(py),
+    })
+}
@@ -0,0 +1,299 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyIterator};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::currency::combined_currency;
+use crate::decimals::*;
+use crate::money::Money;
+use crate::money_vat::MoneyWithVAT;
+use crate::stats::{count, Counter};
+
+// Rounds to cents the way EN 16931 validators do (half away from zero)
+fn round_commercial(value: Decimal) -> Decimal {
+    count(Counter::Rounding);
+    value.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RoundingLevel {
+    Line,
+    Category,
+    Document,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RoundingAdjustment {
+    Net,
+    Tax,
+    Gross,
+}
+
+/// Where VAT gets rounded to cents when totalling documents, ERPs differ in this:
+///     "line": every line is rounded, the document is the sum of the rounded lines
+///     "category": line nets are rounded, the tax is calculated and rounded per tax
+///         category (rate) like EN 16931 requires
+///     "document": lines are summed unrounded, only the totals are rounded
+/// `adjust` names the amount absorbing the rounding difference: with "gross" net and tax
+/// are rounded individually, with "tax" (or "net") net (or tax) and gross are rounded and
+/// the remaining amount is their difference.
+#[pyclass(frozen)]
+#[derive(Debug, Clone)]
+pub struct VATRoundingPolicy {
+    level: RoundingLevel,
+    adjust: RoundingAdjustment,
+}
+
+impl Default for VATRoundingPolicy {
+    fn default() -> Self {
+        Self {
+            level: RoundingLevel::Category,
+            adjust: RoundingAdjustment::Gross,
+        }
+    }
+}
+
+impl VATRoundingPolicy {
+    fn round(&self, net: Decimal, tax: Decimal) -> (Decimal, Decimal) {
+        match self.adjust {
+            RoundingAdjustment::Gross => (round_commercial(net), round_commercial(tax)),
+            RoundingAdjustment::Tax => {
+                let rounded_net = round_commercial(net);
+                let gross = round_commercial(decimal_add(net, tax));
+                (rounded_net, decimal_add(gross, decimal_neg(rounded_net)))
+            }
+            RoundingAdjustment::Net => {
+                let rounded_tax = round_commercial(tax);
+                let gross = round_commercial(decimal_add(net, tax));
+                (decimal_add(gross, decimal_neg(rounded_tax)), rounded_tax)
+            }
+        }
+    }
+
+    /// Rounded document total and tax category subtotals of the lines
+    pub fn totals(
+        &self,
+        lines: &[MoneyWithVAT],
+    ) -> PyResult<(MoneyWithVAT, Vec<(Decimal, MoneyWithVAT)>)> {
+        let mut subtotals: Vec<(Decimal, MoneyWithVAT)> = Vec::new();
+        let mut exact_net = Decimal::new(0, 0);
+        let mut exact_tax = Decimal::new(0, 0);
+        let currency = lines.iter().try_fold(None, |currency, line| {
+            combined_currency(currency, line.get_currency())
+        })?;
+
+        for line in lines {
+            let rate = line.get_tax_rate_for_display();
+            let (net, tax) = match self.level {
+                RoundingLevel::Line => self.round(line.net.amount, line.tax.amount),
+                // The category tax is calculated from the category net below
+                RoundingLevel::Category => (round_commercial(line.net.amount), Decimal::new(0, 0)),
+                RoundingLevel::Document => (line.net.amount, line.tax.amount),
+            };
+            exact_net = decimal_add(exact_net, line.net.amount);
+            exact_tax = decimal_add(exact_tax, line.tax.amount);
+
+            match subtotals.iter_mut().find(|(known, _)| *known == rate) {
+                Some((_, subtotal)) => {
+                    subtotal.net.amount = decimal_add(subtotal.net.amount, net);
+                    subtotal.tax.amount = decimal_add(subtotal.tax.amount, tax);
+                }
+                None => subtotals.push((rate, MoneyWithVAT::in_currency(net, tax, currency))),
+            }
+        }
+
+        for (rate, subtotal) in subtotals.iter_mut() {
+            let rounded = match self.level {
+                RoundingLevel::Line => None,
+                // BR-CO-17: the category tax is the category taxable amount times its rate
+                RoundingLevel::Category => Some(self.round(
+                    subtotal.net.amount,
+                    decimal_mult(subtotal.net.amount, *rate),
+                )),
+                RoundingLevel::Document => {
+                    Some(self.round(subtotal.net.amount, subtotal.tax.amount))
+                }
+            };
+            if let Some((net, tax)) = rounded {
+                subtotal.net.amount = net;
+                subtotal.tax.amount = tax;
+            }
+        }
+
+        let mut net_total = Decimal::new(0, 0);
+        let mut tax_total = Decimal::new(0, 0);
+
+        for (_, subtotal) in subtotals.iter() {
+            net_total = decimal_add(net_total, subtotal.net.amount);
+            tax_total = decimal_add(tax_total, subtotal.tax.amount);
+        }
+
+        // Rounding the document total can differ from the sum of the rounded categories,
+        // the difference lands on the largest category so that the subtotals add up
+        if self.level == RoundingLevel::Document {
+            let (net, tax) = self.round(exact_net, exact_tax);
+            let largest = subtotals
+                .iter_mut()
+                .max_by_key(|(_, subtotal)| subtotal.get_gross().amount.abs());
+            if let Some((_, subtotal)) = largest {
+                subtotal.net.amount = decimal_add(
+                    subtotal.net.amount,
+                    decimal_add(net, decimal_neg(net_total)),
+                );
+                subtotal.tax.amount = decimal_add(
+                    subtotal.tax.amount,
+                    decimal_add(tax, decimal_neg(tax_total)),
+                );
+            }
+            net_total = net;
+            tax_total = tax;
+        }
+
+        Ok((
+            MoneyWithVAT::in_currency(net_total, tax_total, currency),
+            subtotals,
+        ))
+    }
+}
+
+#[pymethods]
+impl VATRoundingPolicy {
+    #[new]
+    #[pyo3(signature = (level="category", adjust="gross"))]
+    fn new(level: &str, adjust: &str) -> PyResult<Self> {
+        let level = match level {
+            "line" => RoundingLevel::Line,
+            "category" => RoundingLevel::Category,
+            "document" => RoundingLevel::Document,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown VAT rounding level '{}'",
+                    level
+                )))
+            }
+        };
+        let adjust = match adjust {
+            "net" => RoundingAdjustment::Net,
+            "tax" => RoundingAdjustment::Tax,
+            "gross" => RoundingAdjustment::Gross,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown VAT rounding adjustment '{}'",
+                    adjust
+                )))
+            }
+        };
+
+        Ok(Self { level, adjust })
+    }
+
+    #[getter(level)]
+    fn get_level(&self) -> &'static str {
+        match self.level {
+            RoundingLevel::Line => "line",
+            RoundingLevel::Category => "category",
+            RoundingLevel::Document => "document",
+        }
+    }
+
+    #[getter(adjust)]
+    fn get_adjust(&self) -> &'static str {
+        match self.adjust {
+            RoundingAdjustment::Net => "net",
+            RoundingAdjustment::Tax => "tax",
+            RoundingAdjustment::Gross => "gross",
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VATRoundingPolicy(level='{}', adjust='{}')",
+            self.get_level(),
+            self.get_adjust()
+        )
+    }
+}
+
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct InvoiceTotals {
+    /// Sum of net (BT-109) and tax (BT-110) of the document
+    #[pyo3(get)]
+    pub total: MoneyWithVAT,
+
+    /// Already paid amount (BT-113)
+    #[pyo3(get)]
+    pub prepaid: Money,
+
+    /// Tax category subtotals (BG-23) as (rate, taxable amount and tax)
+    pub subtotals: Vec<(Decimal, MoneyWithVAT)>,
+}
+
+#[pymethods]
+impl InvoiceTotals {
+    /// Totals a document following the EN 16931 (XRechnung/ZUGFeRD) rounding rules:
+    ///     (a) every line net is rounded to cents
+    ///     (b) line nets are summed per tax category (rate), the category tax is
+    ///         calculated on that sum and rounded to cents
+    ///     (c) document tax is the sum of the category taxes, payable is gross minus prepaid
+    /// The rate of a line is its `tax_rate_for_display`, which names its category.
+    #[staticmethod]
+    #[pyo3(signature = (lines, prepaid=None))]
+    fn en16931(lines: Bound<PyAny>, prepaid: Option<Money>) -> PyResult<Self> {
+        Self::from_lines(lines, None, prepaid)
+    }
+
+    /// Totals a document rounding according to `policy`, by default like `en16931`.
+    #[staticmethod]
+    #[pyo3(signature = (lines, policy=None, prepaid=None))]
+    fn from_lines(
+        lines: Bound<PyAny>,
+        policy: Option<VATRoundingPolicy>,
+        prepaid: Option<Money>,
+    ) -> PyResult<Self> {
+        let lines = PyIterator::from_bound_object(&lines)?
+            .map(|item| item?.extract::<MoneyWithVAT>())
+            .collect::<PyResult<Vec<_>>>()?;
+        let (total, subtotals) = policy.unwrap_or_default().totals(&lines)?;
+        let prepaid = prepaid.unwrap_or(Money {
+            amount: Decimal::new(0, 0),
+            currency: None,
+        });
+        combined_currency(total.get_currency(), prepaid.currency)?;
+
+        Ok(Self {
+            total,
+            prepaid,
+            subtotals,
+        })
+    }
+
+    /// Amount due for payment (BT-115)
+    #[getter(payable)]
+    fn get_payable(&self) -> Money {
+        Money {
+            amount: decimal_add(
+                self.total.get_gross().amount,
+                decimal_neg(self.prepaid.amount),
+            ),
+            currency: self.total.get_currency().or(self.prepaid.currency),
+        }
+    }
+
+    #[getter(subtotals)]
+    fn get_subtotals(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new_bound(py);
+        for (rate, subtotal) in &self.subtotals {
+            dict.set_item(*rate, subtotal.clone().into_py(py))?;
+        }
+
+        Ok(dict.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "InvoiceTotals(net='{}', tax='{}', prepaid='{}')",
+            self.total.net.amount, self.total.tax.amount, self.prepaid.amount
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// An amount read from a JSON string or number
+#[derive(Deserialize)]
+#[serde(transparent)]
+pub struct JsonAmount(#[serde(with = "rust_decimal::serde::arbitrary_precision")] pub Decimal);
+
+// The shape of `Money.for_json`, the amount alone or together with the currency code
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MoneyRecord<T> {
+    Amount(T),
+    WithCurrency { amount: T, currency: String },
+}
+
+// The flat shape of `MoneyWithVAT.for_json`, written with string amounts and read with
+// JsonAmount ones. The currency code is optional.
+#[derive(Serialize, Deserialize)]
+pub struct MoneyWithVATRecord<T> {
+    pub net: T,
+    pub tax: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+}
+
+// The shape of `MoneyWithVATRatio.for_json`
+#[derive(Serialize, Deserialize)]
+pub struct MoneyWithVATRatioRecord<T> {
+    pub net_ratio: T,
+    pub gross_ratio: T,
+}
+
+pub fn json_dumps<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("Records of strings are valid JSON")
+}
+
+pub fn json_loads<'a, T: Deserialize<'a>>(text: &'a str) -> PyResult<T> {
+    serde_json::from_str(text)
+        .map_err(|err| PyValueError::new_err(format!("Invalid JSON: {}", err)))
+}
@@ -1,30 +1,193 @@
+// pyo3's generated wrappers trip this lint on every `PyResult` return type
+#![allow(clippy::useless_conversion)]
+
 use pyo3::prelude::*;
+use pyo3::types::PyTuple;
 
+mod builders;
+mod converters;
+mod country;
+mod currency;
 mod decimals;
+mod errors;
+mod finance;
+mod formula;
+mod invoice;
+mod json;
+mod locale;
 mod money;
+mod money_array;
+mod money_bag;
+mod money_expr;
 mod money_vat;
 mod money_vat_ratio;
+mod msgpack;
+mod ndjson;
+mod stats;
+mod tax_breakdown;
 
 #[pymodule]
 mod alasco_money {
     use super::*;
     use env_logger;
 
+    #[pymodule_export]
+    use crate::builders::MoneyBuilder;
+
+    #[pymodule_export]
+    use crate::builders::MoneyWithVATBuilder;
+
+    #[pymodule_export]
+    use crate::converters::as_money;
+
+    #[pymodule_export]
+    use crate::converters::as_money_with_vat;
+
+    #[pymodule_export]
+    use crate::converters::register_cattrs_hooks;
+
+    #[pymodule_export]
+    use crate::converters::validate_money;
+
+    #[pymodule_export]
+    use crate::converters::validate_money_with_vat;
+
+    #[pymodule_export]
+    use crate::country::Country;
+
+    #[pymodule_export]
+    use crate::country::default_vat_rate_for_vat_id;
+
+    #[pymodule_export]
+    use crate::currency::Currency;
+
     #[pymodule_export]
     use crate::money::Money;
 
+    #[pymodule_export]
+    use crate::money::CurrencyConstraint;
+
+    #[pymodule_export]
+    use crate::money_array::MoneyArray;
+
+    #[pymodule_export]
+    use crate::money_array::MoneyWithVATArray;
+
+    #[pymodule_export]
+    use crate::money_bag::MoneyBag;
+
+    #[pymodule_export]
+    use crate::money_expr::MoneyExpr;
+
     #[pymodule_export]
     use crate::money_vat::MoneyWithVAT;
 
     #[pymodule_export]
     use crate::money_vat_ratio::MoneyWithVATRatio;
 
+    #[pymodule_export]
+    use crate::errors::CurrencyMismatchError;
+
+    #[pymodule_export]
+    use crate::errors::InvalidAmountError;
+
+    #[pymodule_export]
+    use crate::errors::MoneyError;
+
+    #[pymodule_export]
+    use crate::finance::amortization_schedule;
+
+    #[pymodule_export]
+    use crate::finance::annuity;
+
+    #[pymodule_export]
+    use crate::finance::discount;
+
+    #[pymodule_export]
+    use crate::finance::ecb_reference_rates;
+
+    #[pymodule_export]
+    use crate::finance::fx_gain_loss;
+
+    #[pymodule_export]
+    use crate::finance::late_payment_interest;
+
+    #[pymodule_export]
+    use crate::finance::npv;
+
+    #[pymodule_export]
+    use crate::formula::evaluate;
+
+    #[pymodule_export]
+    use crate::invoice::InvoiceTotals;
+
+    #[pymodule_export]
+    use crate::tax_breakdown::TaxBreakdown;
+
+    #[pymodule_export]
+    use crate::invoice::VATRoundingPolicy;
+
     #[pymodule_export]
     use crate::money::sum_;
 
+    #[pymodule_export]
+    use crate::ndjson::sum_ndjson;
+
+    #[pymodule_export]
+    use crate::money::average_;
+
+    #[pymodule_export]
+    use crate::money::diffs;
+
+    #[pymodule_export]
+    use crate::money::bucketize;
+
+    #[pymodule_export]
+    use crate::money::set_money_division;
+
+    #[pymodule_export]
+    use crate::money::set_negative_zero_policy;
+
+    #[pymodule_export]
+    use crate::money::set_pydantic_serialization;
+
+    #[pymodule_export]
+    use crate::money::get_config;
+
+    #[pymodule_export]
+    use crate::errors::SepaAmountError;
+
+    #[pymodule_export]
+    use crate::stats::set_stats;
+
+    #[pymodule_export]
+    use crate::stats::get_stats;
+
+    #[pymodule_export]
+    use crate::stats::reset_stats;
+
     #[pymodule_init]
-    fn init(_m: &Bound<'_, PyModule>) -> PyResult<()> {
+    fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
         env_logger::init(); // Initialize logging
+
+        let py = m.py();
+        m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+        m.add("MONEY_PRECISION", crate::money::MONEY_PRECISION)?;
+        m.add("DEFAULT_ROUNDING", crate::decimals::DEFAULT_ROUNDING)?;
+        m.add("DivisionByZero", crate::errors::division_by_zero_type(py)?)?;
+        m.add(
+            "KNOWN_VAT_RATES",
+            PyTuple::new_bound(py, crate::money_vat::MoneyWithVAT::known_vat_rates(None)),
+        )?;
+        m.add(
+            "GERMAN_VAT_RATES",
+            PyTuple::new_bound(py, crate::money_vat::MoneyWithVAT::german_vat_rates()),
+        )?;
+
+        // Lets libraries gating on `isinstance(x, numbers.Number)` accept Money
+        py.import_bound("numbers")?
+            .getattr("Number")?
+            .call_method1("register", (py.get_type_bound::<crate::money::Money>(),))?;
         Ok(())
     }
 }
@@ -0,0 +1,248 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use crate::currency::{currency_from_token, Currency};
+use crate::decimals::decimal_round;
+use crate::errors::InvalidAmountError;
+
+/// Number and currency conventions of a locale (CLDR)
+pub struct Locale {
+    pub code: &'static str,
+    pub thousands: char,
+    pub decimal: char,
+    pub currency: Currency,
+    // Whether the symbol precedes the number and whether a space separates them
+    pub symbol_first: bool,
+    pub symbol_space: bool,
+}
+
+const LOCALES: [Locale; 10] = [
+    locale("de_AT", '.', ',', Currency::EUR, true, true),
+    locale("de_CH", '\u{2019}', '.', Currency::CHF, true, true),
+    locale("de_DE", '.', ',', Currency::EUR, false, true),
+    locale("en_GB", ',', '.', Currency::GBP, true, false),
+    locale("en_IE", ',', '.', Currency::EUR, true, false),
+    locale("en_US", ',', '.', Currency::USD, true, false),
+    locale("fr_CH", ' ', ',', Currency::CHF, false, true),
+    locale("fr_FR", ' ', ',', Currency::EUR, false, true),
+    locale("it_IT", '.', ',', Currency::EUR, false, true),
+    locale("nl_NL", '.', ',', Currency::EUR, true, true),
+];
+
+const fn locale(
+    code: &'static str,
+    thousands: char,
+    decimal: char,
+    currency: Currency,
+    symbol_first: bool,
+    symbol_space: bool,
+) -> Locale {
+    Locale {
+        code,
+        thousands,
+        decimal,
+        currency,
+        symbol_first,
+        symbol_space,
+    }
+}
+
+// Looks up a locale like "de_DE" (or "de-DE")
+pub fn locale_extract(code: &str) -> PyResult<&'static Locale> {
+    let normalized = code.trim().replace('-', "_");
+    LOCALES
+        .iter()
+        .find(|locale| locale.code.eq_ignore_ascii_case(&normalized))
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown locale '{}'", code)))
+}
+
+impl Locale {
+    /// Formats the amount rounded to `decimals` with grouped thousands and, if given,
+    /// the currency symbol placed like the locale does
+    pub fn format(&self, amount: Decimal, decimals: u32, symbol: Option<&str>) -> String {
+        let rounded = decimal_round(amount, decimals as i32);
+        let digits = format!("{:.prec$}", rounded.abs(), prec = decimals as usize);
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+
+        let mut number = String::new();
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index) % 3 == 0 {
+                number.push(self.thousands);
+            }
+            number.push(digit);
+        }
+        if !fraction.is_empty() {
+            number.push(self.decimal);
+            number.push_str(fraction);
+        }
+
+        let sign = if rounded.is_sign_negative() && !rounded.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        let space = if self.symbol_space { " " } else { "" };
+
+        match symbol {
+            Some(symbol) if self.symbol_first => format!("{sign}{symbol}{space}{number}"),
+            Some(symbol) => format!("{sign}{number}{space}{symbol}"),
+            None => format!("{sign}{number}"),
+        }
+    }
+}
+
+// Spaces used to group thousands: regular, no-break and narrow no-break
+const SPACES: [char; 3] = [' ', '\u{a0}', '\u{202f}'];
+
+// Apostrophes used to group thousands: typed and typographic (right single quotation mark)
+const APOSTROPHES: [char; 2] = ['\'', '\u{2019}'];
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '.' | ',') || SPACES.contains(&c) || APOSTROPHES.contains(&c)
+}
+
+// Whether `c` groups thousands like `thousands`, counting look-alike spaces and
+// apostrophes as the same
+fn is_thousands(c: char, thousands: char) -> bool {
+    c == thousands
+        || [&SPACES[..], &APOSTROPHES[..]]
+            .iter()
+            .any(|group| group.contains(&c) && group.contains(&thousands))
+}
+
+/// Parses text like `1.234,56 €`, `-$1,234.56` or `CHF 1’234.50` into the amount and the
+/// currency it names, if any. Without a locale the separators are inferred.
+pub fn parse_money(text: &str, locale: Option<&Locale>) -> PyResult<(Decimal, Option<Currency>)> {
+    let invalid = || InvalidAmountError::new_err(format!("Invalid amount '{}'", text));
+    let trimmed = text.trim();
+    let first = trimmed
+        .find(|c: char| c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let last = trimmed
+        .rfind(|c: char| c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+
+    // A leading decimal separator like in ".5" belongs to the number, not the symbol
+    let mut start = first;
+    if trimmed[..first].ends_with(['.', ','])
+        && !trimmed[..first - 1].ends_with(char::is_alphabetic)
+    {
+        start -= 1;
+    }
+    let (prefix, number, suffix) = (
+        &trimmed[..start],
+        &trimmed[start..=last],
+        &trimmed[last + 1..],
+    );
+
+    let mut prefix = prefix.trim();
+    let mut negative = false;
+    for sign in ['-', '+'] {
+        if let Some(rest) = prefix
+            .strip_prefix(sign)
+            .or_else(|| prefix.strip_suffix(sign))
+        {
+            if negative || rest.contains(['-', '+']) {
+                return Err(invalid());
+            }
+            negative = sign == '-';
+            prefix = rest.trim();
+        }
+    }
+    let currency = match (prefix, suffix.trim()) {
+        ("", "") => None,
+        (token, "") | ("", token) if token.chars().all(is_separator) => return Err(invalid()),
+        (token, "") | ("", token) => Some(currency_from_token(token)?),
+        _ => {
+            return Err(InvalidAmountError::new_err(format!(
+                "Amount '{}' names more than one currency",
+                text
+            )))
+        }
+    };
+
+    let amount = parse_number(text, number, locale)?;
+    Ok((if negative { -amount } else { amount }, currency))
+}
+
+fn parse_number(text: &str, number: &str, locale: Option<&Locale>) -> PyResult<Decimal> {
+    let invalid = || InvalidAmountError::new_err(format!("Invalid amount '{}'", text));
+    if !number
+        .chars()
+        .all(|c| c.is_ascii_digit() || is_separator(c))
+    {
+        return Err(invalid());
+    }
+
+    let decimal = match locale {
+        Some(locale) => {
+            let unexpected = number.chars().find(|&c| {
+                is_separator(c) && c != locale.decimal && !is_thousands(c, locale.thousands)
+            });
+            if let Some(c) = unexpected {
+                return Err(InvalidAmountError::new_err(format!(
+                    "Unexpected '{}' in amount '{}' for locale {}",
+                    c, text, locale.code
+                )));
+            }
+            number.contains(locale.decimal).then_some(locale.decimal)
+        }
+        None => {
+            let dots = number.matches('.').count();
+            let commas = number.matches(',').count();
+            let mark = number
+                .rfind(['.', ','])
+                .and_then(|index| number[index..].chars().next());
+            match (dots, commas) {
+                (0, 0) => None,
+                (1, 0) | (0, 1) => {
+                    let (integer, fraction) = number.split_once(['.', ',']).ok_or_else(invalid)?;
+                    if fraction.len() == 3
+                        && (1..=3).contains(&integer.len())
+                        && !integer.starts_with('0')
+                        && integer.chars().all(|c| c.is_ascii_digit())
+                    {
+                        return Err(InvalidAmountError::new_err(format!(
+                            "Ambiguous amount '{}', pass a locale to tell the thousands from \
+                             the decimal separator",
+                            text
+                        )));
+                    }
+                    mark
+                }
+                (_, 0) | (0, _) => None,
+                _ => mark,
+            }
+        }
+    };
+
+    let (integer, fraction) = match decimal {
+        Some(decimal) => number.split_once(decimal).ok_or_else(invalid)?,
+        None => (number, ""),
+    };
+    if decimal.is_some() && (fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit())) {
+        return Err(invalid());
+    }
+    let groups: Vec<&str> = integer.split(is_separator).collect();
+    let grouped = groups.len() == 1
+        || groups.iter().enumerate().all(|(index, group)| match index {
+            0 => (1..=3).contains(&group.len()),
+            _ => group.len() == 3,
+        });
+    if !grouped {
+        return Err(InvalidAmountError::new_err(format!(
+            "Misplaced thousands separator in amount '{}'",
+            text
+        )));
+    }
+
+    let digits: String = groups.concat();
+    let digits = if digits.is_empty() { "0" } else { &digits };
+    let value = match fraction {
+        "" => digits.to_string(),
+        fraction => format!("{}.{}", digits, fraction),
+    };
+    Decimal::from_str(&value).map_err(|_| invalid())
+}
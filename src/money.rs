@@ -1,83 +1,301 @@
 use pyo3::basic::CompareOp;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyCFunction, PyDict, PyIterator, PyTuple};
-use rust_decimal::Decimal;
+use pyo3::types::{PyBytes, PyCFunction, PyDict, PyIterator, PyString, PyTuple, PyType};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::cmp;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use crate::currency::{combined_currency, currency_extract, Currency};
 use crate::decimals::*;
+use crate::errors::{division_by_zero, InvalidAmountError, SepaAmountError};
+use crate::finance::{day_count_extract, ordinal_extract};
+use crate::json::{json_dumps, json_loads, JsonAmount, MoneyRecord};
+use crate::locale::{locale_extract, parse_money};
+use crate::money_bag::CurrencyTotals;
+use crate::money_vat::MoneyWithVAT;
+use crate::msgpack::{message_currency, msgpack_dumps, msgpack_loads, MoneyMessage};
+use crate::stats::{count, stats_enabled, Counter};
 
 pub const MONEY_PRECISION: Option<i32> = Some(12);
 
-#[pyclass(subclass)]
+/// Whether `Money / Money` raises instead of returning a Decimal
+static MONEY_DIVISION_RAISES: AtomicBool = AtomicBool::new(false);
+
+/// Whether pydantic's python mode (e.g. `model_dump()`) serializes into Decimals as well
+static PYDANTIC_SERIALIZES_PYTHON: AtomicBool = AtomicBool::new(false);
+
+/// Largest amount allowed in SEPA payment files (pain.001)
+const SEPA_MAX_AMOUNT: Decimal = Decimal::from_parts(1215752191, 23, 0, false, 2);
+
+/// Money is frozen, attributes can't be set from Python so that hashes stay valid inside
+/// sets and dicts. Use MoneyBuilder to construct values incrementally.
+#[pyclass(subclass, frozen, module = "alasco_money")]
 #[derive(Debug, Clone)]
 pub struct Money {
     #[pyo3(get)]
     pub amount: Decimal,
+    #[pyo3(get)]
+    pub currency: Option<Currency>,
+}
+
+impl Money {
+    pub fn round(&self, n: Option<i32>) -> Self {
+        self.round_with_strategy(n.unwrap_or_default(), RoundingStrategy::MidpointNearestEven)
+    }
+
+    pub fn round_with_strategy(&self, n: i32, strategy: RoundingStrategy) -> Self {
+        Self {
+            amount: decimal_round_with_strategy(self.amount, n, strategy),
+            currency: self.currency,
+        }
+    }
+
+    // The amount of `for_json`, rounded to MONEY_PRECISION with all decimals written
+    pub fn json_amount(&self) -> String {
+        format!(
+            "{number:.prec$}",
+            number = self.round(MONEY_PRECISION).amount,
+            prec = MONEY_PRECISION.unwrap() as usize
+        )
+    }
+
+    fn json_record(&self) -> MoneyRecord<String> {
+        match self.currency {
+            Some(currency) => MoneyRecord::WithCurrency {
+                amount: self.json_amount(),
+                currency: currency.get_code().to_string(),
+            },
+            None => MoneyRecord::Amount(self.json_amount()),
+        }
+    }
+
+    fn from_json_record(record: MoneyRecord<JsonAmount>) -> PyResult<Self> {
+        Ok(match record {
+            MoneyRecord::Amount(JsonAmount(amount)) => Self {
+                amount,
+                currency: None,
+            },
+            MoneyRecord::WithCurrency {
+                amount: JsonAmount(amount),
+                currency,
+            } => Self {
+                amount,
+                currency: Some(Currency::from_code(&currency)?),
+            },
+        })
+    }
+
+    // Reads the `{"amount": ..., "currency": ...}` dict of `for_json`
+    fn from_json_dict(dict: &Bound<PyDict>, locale: Option<&str>) -> PyResult<Option<Self>> {
+        let (Some(amount), Some(currency)) = (dict.get_item("amount")?, dict.get_item("currency")?)
+        else {
+            return Ok(None);
+        };
+        match (
+            validation_extract(&amount, locale)?,
+            currency_extract(&currency),
+        ) {
+            (Some(amount), Ok(currency)) => Ok(Some(Self {
+                amount,
+                currency: Some(currency),
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    // Decimals of the parts in `allocate`, the minor unit unless the amount is more precise
+    pub fn allocation_scale(&self) -> u32 {
+        self.amount
+            .scale()
+            .max(self.currency.map_or(2, |currency| currency.get_exponent()))
+    }
+
+    // Divisor of `%` and `//` with the currency of the result, rejecting zero
+    fn divisor_extract(&self, other: Bound<PyAny>) -> PyResult<(Decimal, Option<Currency>)> {
+        let (divisor, currency) = if let Ok(other_money) = other.extract::<Self>() {
+            (
+                other_money.amount,
+                combined_currency(self.currency, other_money.currency)?,
+            )
+        } else if let Ok(other_decimal) = decimal_extract(other) {
+            (other_decimal, self.currency)
+        } else {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "Unsupported operand",
+            ));
+        };
+
+        if divisor.is_zero() {
+            return Err(division_by_zero());
+        }
+
+        Ok((divisor, currency))
+    }
+
+    fn extreme(
+        args: &Bound<PyTuple>,
+        default: Option<Bound<PyAny>>,
+        pick: fn(Decimal, Decimal) -> Decimal,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        let mut extreme: Option<Decimal> = None;
+        let mut currency = None;
+        for item in variadic_items(args)? {
+            let item = item?;
+            if item.is_none() {
+                continue;
+            }
+            let money = item.extract::<Self>()?;
+            currency = combined_currency(currency, money.currency)?;
+            extreme = Some(extreme.map_or(money.amount, |extreme| pick(extreme, money.amount)));
+        }
+
+        match (extreme, default) {
+            (Some(amount), _) => Ok(Self { amount, currency }.into_py(py)),
+            (None, Some(default)) => Ok(default.unbind()),
+            (None, None) => Err(PyValueError::new_err("Insufficient arguments")),
+        }
+    }
 }
 
 #[pymethods]
 impl Money {
+    /// `currency` is an optional ISO 4217 code or Currency, e.g. `Money("1.50", "EUR")`.
     #[new]
-    #[pyo3(signature = (amount=None))]
-    pub fn new(amount: Option<Bound<PyAny>>) -> PyResult<Self> {
+    #[pyo3(signature = (amount=None, currency=None))]
+    pub fn new(amount: Option<Bound<PyAny>>, currency: Option<Bound<PyAny>>) -> PyResult<Self> {
+        count(Counter::Construction);
+        let currency = currency.map(|obj| currency_extract(&obj)).transpose()?;
         if let Some(obj) = amount {
+            let py = obj.py();
             if let Ok(money) = obj.extract::<Self>() {
-                Ok(money)
-            } else if let Ok(decimal) = decimal_extract(obj) {
-                Ok(Self { amount: decimal })
+                Ok(Self {
+                    amount: money.amount,
+                    currency: combined_currency(money.currency, currency)?,
+                })
             } else {
-                Err(PyValueError::new_err("Invalid type"))
+                match decimal_extract(obj.clone()) {
+                    Ok(decimal) => Ok(Self {
+                        amount: decimal,
+                        currency,
+                    }),
+                    Err(err) if err.is_instance_of::<InvalidAmountError>(py) => Err(err),
+                    Err(_) => Err(InvalidAmountError::new_err(format!(
+                        "Invalid amount {}",
+                        obj.repr()?
+                    ))),
+                }
             }
         } else {
             Ok(Self {
                 amount: Decimal::new(0, 0),
+                currency,
             })
         }
     }
 
-    #[pyo3(signature = (n=None))]
-    pub fn round(&self, n: Option<i32>) -> Self {
-        Self {
-            amount: decimal_round(self.amount, if let Some(true_n) = n { true_n } else { 0 }),
+    /// Rounds to `n` decimals, by default half to even (banker's rounding) like Python's
+    /// `round`. Pass e.g. `strategy="half_up"` for commercial rounding.
+    #[pyo3(name = "round", signature = (n=None, strategy=DEFAULT_ROUNDING))]
+    fn round_with(&self, n: Option<i32>, strategy: &str) -> PyResult<Self> {
+        Ok(self.round_with_strategy(n.unwrap_or_default(), rounding_strategy_extract(strategy)?))
+    }
+
+    /// Human friendly representation rounded to cents, e.g. `123.45`.
+    /// Use `repr()` for the exact technical representation.
+    pub fn __str__(&self) -> String {
+        format!("{:.2}", self.round(Some(2)).amount)
+    }
+
+    /// Formats the amount like a decimal, e.g. `f"{price:.2f}"`, plus the type "n" for
+    /// cents (the currency's minor unit) with optional grouping, e.g. `f"{price:,n}"`.
+    pub fn __format__(&self, spec: &str, py: Python) -> PyResult<String> {
+        if spec.is_empty() {
+            return Ok(self.__str__());
         }
+        let precision = self.currency.map_or(2, |currency| currency.get_exponent());
+        decimal_format(py, self.amount, spec, precision)
     }
 
-    fn __str__(&self) -> String {
-        self.__repr__()
+    pub fn __repr__(&self) -> String {
+        match self.currency {
+            Some(currency) => format!("Money('{}', '{}')", self.amount, currency.get_code()),
+            None => format!("Money('{}')", self.amount),
+        }
     }
 
-    fn __repr__(&self) -> String {
-        format!("Money('{}')", self.amount)
+    /// Locale aware representation, e.g. `1.234,57 €` for de_DE or `€1,234.57` for en_IE.
+    /// Without a currency the locale's own currency symbol is used, `decimals` defaults to
+    /// the minor unit of the currency (0 for JPY).
+    #[pyo3(signature = (locale="de_DE", symbol=true, decimals=None))]
+    fn format(&self, locale: &str, symbol: bool, decimals: Option<u32>) -> PyResult<String> {
+        let locale = locale_extract(locale)?;
+        let currency = self.currency.unwrap_or(locale.currency);
+        let symbol = symbol.then(|| currency.get_symbol());
+        let decimals = decimals.unwrap_or_else(|| currency.get_exponent());
+        Ok(locale.format(self.amount, decimals, symbol))
     }
 
     fn __hash__(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.amount.hash(&mut hasher);
+        self.currency.hash(&mut hasher);
         hasher.finish()
     }
 
+    /// Returns a hash that is stable across processes and versions, unlike `hash()`.
+    /// It is the 64-bit FNV-1a hash of the amount without trailing zeros, so equal amounts
+    /// like 1.0 and 1.00 share it.
+    pub fn stable_hash(&self) -> u64 {
+        decimal_stable_hash(self.amount)
+    }
+
     pub fn __neg__(&self) -> Self {
+        count(Counter::Operation);
         Self {
             amount: decimal_neg(self.amount),
+            currency: self.currency,
+        }
+    }
+
+    pub fn __pos__(&self) -> Self {
+        count(Counter::Operation);
+        Self {
+            amount: decimal_pos(self.amount),
+            currency: self.currency,
         }
     }
 
     fn __abs__(&self) -> Self {
+        count(Counter::Operation);
         Self {
             amount: self.amount.abs(),
+            currency: self.currency,
         }
     }
 
     fn __add__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        if let Ok(other_money) = other.extract::<Self>() {
+        count(Counter::Operation);
+        if let Ok(other_money) = other.downcast::<Self>() {
+            let other_money = other_money.get();
             Ok(Self {
                 amount: decimal_add(self.amount, other_money.amount),
+                currency: combined_currency(self.currency, other_money.currency)?,
+            })
+        } else if let Some(zero) = zero_operand_extract(&other)? {
+            Ok(Self {
+                amount: decimal_add(self.amount, zero),
+                currency: self.currency,
             })
         } else if let Ok(other_decimal) = decimal_extract(other) {
             Ok(Self {
                 amount: decimal_add(self.amount, other_decimal),
+                currency: self.currency,
             })
         } else {
             Err(pyo3::exceptions::PyTypeError::new_err(
@@ -91,13 +309,22 @@ impl Money {
     }
 
     fn __sub__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        if let Ok(other_money) = other.extract::<Self>() {
+        count(Counter::Operation);
+        if let Ok(other_money) = other.downcast::<Self>() {
+            let other_money = other_money.get();
             Ok(Self {
                 amount: decimal_add(self.amount, -other_money.amount),
+                currency: combined_currency(self.currency, other_money.currency)?,
+            })
+        } else if let Some(zero) = zero_operand_extract(&other)? {
+            Ok(Self {
+                amount: decimal_add(self.amount, -zero),
+                currency: self.currency,
             })
         } else if let Ok(other_decimal) = decimal_extract(other) {
             Ok(Self {
                 amount: decimal_add(self.amount, -other_decimal),
+                currency: self.currency,
             })
         } else {
             Err(pyo3::exceptions::PyTypeError::new_err(
@@ -107,13 +334,154 @@ impl Money {
     }
 
     fn __rsub__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        self.__neg__().__add__(other)
+        Self {
+            amount: decimal_neg(self.amount),
+            currency: self.currency,
+        }
+        .__add__(other)
+    }
+
+    /// Returns the difference `self - other` together with its absolute value.
+    fn abs_diff(&self, other: Self) -> PyResult<(Self, Self)> {
+        let diff = decimal_add(self.amount, decimal_neg(other.amount));
+        let currency = combined_currency(self.currency, other.currency)?;
+        Ok((
+            Self {
+                amount: diff,
+                currency,
+            },
+            Self {
+                amount: diff.abs(),
+                currency,
+            },
+        ))
+    }
+
+    /// Returns the magnitude of this amount with the sign of `other` (like Decimal.copy_sign).
+    fn copysign(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        let sign_source = match other.extract::<Self>() {
+            Ok(money) => money.amount,
+            Err(_) => decimal_extract(other)?,
+        };
+        let mut amount = self.amount;
+        amount.set_sign_negative(sign_source.is_sign_negative());
+
+        Ok(Self {
+            amount,
+            currency: self.currency,
+        })
+    }
+
+    /// Compares like Decimal.compare_total, returning -1, 0 or 1 and telling 1.0 from 1.00.
+    fn compare_total(&self, other: Bound<PyAny>) -> PyResult<Decimal> {
+        let other_amount = match other.extract::<Self>() {
+            Ok(money) => money.amount,
+            Err(_) => decimal_extract(other)?,
+        };
+
+        Ok(match decimal_compare_total(self.amount, other_amount) {
+            cmp::Ordering::Less => Decimal::NEGATIVE_ONE,
+            cmp::Ordering::Equal => Decimal::ZERO,
+            cmp::Ordering::Greater => Decimal::ONE,
+        })
+    }
+
+    /// Parses localized input like `1.234,56 €`, `-$1,234.56` or `CHF 1’234.50`. Without a
+    /// locale the separators are inferred and ambiguous input like `1.234` is rejected.
+    #[staticmethod]
+    #[pyo3(signature = (text, locale=None))]
+    fn parse(text: &str, locale: Option<&str>) -> PyResult<Self> {
+        let locale = locale.map(locale_extract).transpose()?;
+        let (amount, currency) = parse_money(text, locale)?;
+        Ok(Self { amount, currency })
+    }
+
+    /// Exact construction from a scaled integer: `mantissa * 10 ** exponent`,
+    /// e.g. `Money.from_parts(1500000, -6)` for 1.50 EUR stored as micro-euros.
+    #[staticmethod]
+    fn from_parts(mantissa: i128, exponent: i32) -> PyResult<Self> {
+        let amount = if exponent <= 0 {
+            Decimal::try_from_i128_with_scale(mantissa, exponent.unsigned_abs()).ok()
+        } else {
+            10_i128
+                .checked_pow(exponent as u32)
+                .and_then(|factor| mantissa.checked_mul(factor))
+                .and_then(|value| Decimal::try_from_i128_with_scale(value, 0).ok())
+        };
+
+        amount
+            .map(|amount| Self {
+                amount,
+                currency: None,
+            })
+            .ok_or_else(|| {
+                InvalidAmountError::new_err(format!(
+                    "Invalid amount {}e{}: out of range",
+                    mantissa, exponent
+                ))
+            })
+    }
+
+    /// Returns (mantissa, exponent) with `amount == mantissa * 10 ** exponent`, keeping the
+    /// scale, e.g. `Money("1.50").to_parts() ==> (150, -2)`.
+    fn to_parts(&self) -> (i128, i32) {
+        (self.amount.mantissa(), -(self.amount.scale() as i32))
+    }
+
+    /// Returns the amount as `decimal.Decimal` with the exact internal scale,
+    /// e.g. `Money("1.50").as_decimal() ==> Decimal("1.50")`.
+    fn as_decimal(&self) -> Decimal {
+        self.amount
+    }
+
+    /// Returns the amount as exact ratio of integers in lowest terms with a positive
+    /// denominator, like `Decimal.as_integer_ratio()`, e.g. `Money("1.50") ==> (3, 2)`.
+    fn as_integer_ratio(&self) -> (i128, i128) {
+        let numerator = self.amount.mantissa();
+        let denominator = 10_i128.pow(self.amount.scale());
+
+        let (mut a, mut b) = (numerator.abs(), denominator);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+
+        (numerator / a, denominator / a)
+    }
+
+    /// Returns a hashable key ordering like the amounts, for cheap sorting and grouping.
+    pub fn sort_key(&self) -> (i128, i128) {
+        decimal_sort_key(self.amount)
+    }
+
+    /// Like `+`, but treats None as zero.
+    #[pyo3(signature = (other))]
+    fn add_opt(&self, other: Option<Bound<PyAny>>) -> PyResult<Self> {
+        match other {
+            Some(value) => self.__add__(value),
+            None => Ok(self.clone()),
+        }
+    }
+
+    /// Like `-`, but treats None as zero.
+    #[pyo3(signature = (other))]
+    fn sub_opt(&self, other: Option<Bound<PyAny>>) -> PyResult<Self> {
+        match other {
+            Some(value) => self.__sub__(value),
+            None => Ok(self.clone()),
+        }
     }
 
     fn __mul__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        if let Ok(other_decimal) = decimal_extract(other) {
+        count(Counter::Operation);
+        if let Some((numerator, denominator)) = fraction_extract(&other) {
+            Ok(Self {
+                amount: decimal_mult_fraction(self.amount, numerator, denominator),
+                currency: self.currency,
+            })
+        } else if let Ok(other_decimal) = decimal_extract(other) {
             Ok(Self {
                 amount: decimal_mult(self.amount, other_decimal),
+                currency: self.currency,
             })
         } else {
             Err(pyo3::exceptions::PyTypeError::new_err(
@@ -127,23 +495,28 @@ impl Money {
     }
 
     fn __truediv__(&self, other: Bound<PyAny>) -> PyResult<PyObject> {
+        count(Counter::Operation);
         Python::with_gil(|py| {
             if let Ok(other_money) = other.extract::<Self>() {
-                if other_money.amount == Decimal::new(0, 0) {
-                    Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                        "Division by zero",
-                    ))
+                check_money_division()?;
+                Ok(self.ratio_to(other_money)?.into_py(py))
+            } else if let Some((numerator, denominator)) = fraction_extract(&other) {
+                if numerator == Decimal::new(0, 0) {
+                    Err(division_by_zero())
                 } else {
-                    Ok((decimal_div(self.amount, other_money.amount)).into_py(py))
+                    Ok(Self {
+                        amount: decimal_mult_fraction(self.amount, denominator, numerator),
+                        currency: self.currency,
+                    }
+                    .into_py(py))
                 }
             } else if let Ok(other_decimal) = decimal_extract(other) {
                 if other_decimal == Decimal::new(0, 0) {
-                    Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                        "Division by zero",
-                    ))
+                    Err(division_by_zero())
                 } else {
                     Ok(Self {
                         amount: decimal_div(self.amount, other_decimal),
+                        currency: self.currency,
                     }
                     .into_py(py))
                 }
@@ -156,18 +529,19 @@ impl Money {
     }
 
     fn __rtruediv__(&self, other: Bound<PyAny>) -> PyResult<PyObject> {
+        count(Counter::Operation);
         if self.amount == Decimal::new(0, 0) {
-            return Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                "Division by zero",
-            ));
+            return Err(division_by_zero());
         }
 
         Python::with_gil(|py| {
             if let Ok(other_money) = other.extract::<Self>() {
-                Ok((decimal_div(other_money.amount, self.amount)).into_py(py))
+                check_money_division()?;
+                Ok(other_money.ratio_to(self.clone())?.into_py(py))
             } else if let Ok(other_decimal) = decimal_extract(other) {
                 Ok(Self {
                     amount: decimal_div(other_decimal, self.amount),
+                    currency: self.currency,
                 }
                 .into_py(py))
             } else {
@@ -178,32 +552,487 @@ impl Money {
         })
     }
 
+    /// Compounds escalation percentages given as (date, percent) pairs in date order, e.g.
+    /// `Money(1000).apply_indices([(date(2024, 1, 1), 2.5), (date(2025, 1, 1), 3)])`.
+    /// Rounds to cents after every period, or only once at the end with `round_only_at_end`.
+    #[pyo3(signature = (indices, round_only_at_end=false))]
+    fn apply_indices(&self, indices: Bound<PyAny>, round_only_at_end: bool) -> PyResult<Self> {
+        let py = indices.py();
+        let by_date = py
+            .import_bound("operator")?
+            .call_method1("itemgetter", (0,))?;
+        let kwargs = PyDict::new_bound(py);
+        kwargs.set_item("key", by_date)?;
+        let sorted = py
+            .import_bound("builtins")?
+            .getattr("sorted")?
+            .call((indices,), Some(&kwargs))?;
+
+        let mut amount = self.amount;
+        for item in PyIterator::from_bound_object(&sorted)? {
+            let (_, pct) = item?.extract::<(Bound<PyAny>, Bound<PyAny>)>()?;
+            let factor = decimal_add(
+                Decimal::ONE,
+                decimal_div(decimal_extract(pct)?, Decimal::ONE_HUNDRED),
+            );
+            amount = decimal_mult(amount, factor);
+            if !round_only_at_end {
+                amount = decimal_round(amount, 2);
+            }
+        }
+
+        Ok(Self {
+            amount: if round_only_at_end {
+                decimal_round(amount, 2)
+            } else {
+                amount
+            },
+            currency: self.currency,
+        })
+    }
+
+    /// Treats this amount as net and adds VAT at the given rate, e.g. `Money(100).apply_vat(0.19)`.
+    fn apply_vat(&self, rate: Bound<PyAny>) -> PyResult<MoneyWithVAT> {
+        let rate_decimal = decimal_extract(rate)?;
+
+        Ok(MoneyWithVAT {
+            net: self.clone(),
+            tax: Self {
+                amount: decimal_mult(self.amount, rate_decimal),
+                currency: self.currency,
+            },
+        })
+    }
+
+    /// Splits the amount into `divisor` equal parts rounded down to cents plus a remainder,
+    /// so that `quotient * divisor + remainder == self` holds exactly.
+    fn divide_with_remainder(&self, divisor: Bound<PyAny>) -> PyResult<(Self, Self)> {
+        let divisor_decimal = decimal_extract(divisor)?;
+
+        if divisor_decimal == Decimal::new(0, 0) {
+            return Err(division_by_zero());
+        }
+
+        let quotient = decimal_div(self.amount, divisor_decimal)
+            .round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        let remainder = decimal_add(
+            self.amount,
+            decimal_neg(decimal_mult(quotient, divisor_decimal)),
+        );
+
+        Ok((
+            Self {
+                amount: quotient,
+                currency: self.currency,
+            },
+            Self {
+                amount: remainder,
+                currency: self.currency,
+            },
+        ))
+    }
+
+    /// Splits the amount proportionally to `ratios` into parts that add up to it exactly,
+    /// e.g. `Money(100).allocate([1, 1, 1]) ==> [33.34, 33.33, 33.33]`. Parts are in
+    /// cents (or the minor unit of the currency) unless the amount has more decimals,
+    /// the remaining units go to the largest remainders (the earlier parts on ties).
+    fn allocate(&self, ratios: Bound<PyAny>) -> PyResult<Vec<Self>> {
+        let ratios = PyIterator::from_bound_object(&ratios)?
+            .map(|ratio| decimal_extract(ratio?))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(
+            decimal_allocate(self.amount, &ratios, self.allocation_scale())?
+                .into_iter()
+                .map(|amount| Self {
+                    amount,
+                    currency: self.currency,
+                })
+                .collect(),
+        )
+    }
+
+    /// Splits into whole currency units and the remaining fraction (both carrying the sign),
+    /// e.g. `12.34 ==> (12, Money('0.34'))` and `-12.34 ==> (-12, Money('-0.34'))`.
+    fn split_units(&self) -> (i128, Self) {
+        let units = self.amount.trunc();
+
+        (
+            units.to_i128().unwrap_or_default(),
+            Self {
+                amount: decimal_add(self.amount, decimal_neg(units)),
+                currency: self.currency,
+            },
+        )
+    }
+
+    /// Pro-rata share of this amount for the days of `sub_start` to `sub_end` within the
+    /// period from `period_start` to `period_end` (ends exclusive, the sub-range is clipped
+    /// to the period). Days are actual ones except for `day_count="30/360"`. Not rounded.
+    #[pyo3(signature = (period_start, period_end, sub_start, sub_end, day_count="act/365"))]
+    fn prorate(
+        &self,
+        period_start: Bound<PyAny>,
+        period_end: Bound<PyAny>,
+        sub_start: Bound<PyAny>,
+        sub_end: Bound<PyAny>,
+        day_count: &str,
+    ) -> PyResult<Self> {
+        let day_count = day_count_extract(day_count)?;
+        let (period_start, period_end) = (
+            ordinal_extract(&period_start)?,
+            ordinal_extract(&period_end)?,
+        );
+        let sub_start = ordinal_extract(&sub_start)?.max(period_start);
+        let sub_end = ordinal_extract(&sub_end)?.min(period_end);
+
+        let period_days = day_count.days(period_start, period_end);
+        if period_days <= 0 {
+            return Err(PyValueError::new_err(
+                "The period must span at least one day",
+            ));
+        }
+        let sub_days = day_count.days(sub_start, sub_end).max(0);
+
+        Ok(Self {
+            amount: decimal_mult_fraction(
+                self.amount,
+                Decimal::from(sub_days),
+                Decimal::from(period_days),
+            ),
+            currency: self.currency,
+        })
+    }
+
+    /// Ratio of this amount to another amount as Decimal, the explicit spelling of `self / other`.
+    pub fn ratio_to(&self, other: Self) -> PyResult<Decimal> {
+        combined_currency(self.currency, other.currency)?;
+        if other.amount == Decimal::new(0, 0) {
+            Err(division_by_zero())
+        } else {
+            Ok(decimal_div(self.amount, other.amount))
+        }
+    }
+
+    /// Signed deviation of the actual amount from the budget, `actual - budget`.
+    #[staticmethod]
+    fn variance(actual: Self, budget: Self) -> PyResult<Self> {
+        Ok(Self {
+            amount: decimal_add(actual.amount, decimal_neg(budget.amount)),
+            currency: combined_currency(actual.currency, budget.currency)?,
+        })
+    }
+
+    /// Deviation from the budget in percent of the budget, e.g. `(110, 100) ==> 10`.
+    /// Returns None for a zero budget.
+    #[staticmethod]
+    fn variance_pct(actual: Self, budget: Self) -> Option<Decimal> {
+        decimal_variance_pct(actual.amount, budget.amount)
+    }
+
+    /// Largest amount of the arguments or a single iterable, skipping None. Returns
+    /// `default` if nothing remains, raises ValueError without one.
+    #[staticmethod]
+    #[pyo3(signature = (*args, **kwargs))]
+    fn max(
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        Self::extreme(args, default_kwarg(kwargs)?, cmp::max, py)
+    }
+
+    /// Smallest amount, see `max`.
+    #[staticmethod]
+    #[pyo3(signature = (*args, **kwargs))]
+    fn min(
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        Self::extreme(args, default_kwarg(kwargs)?, cmp::min, py)
+    }
+
     fn __bool__(&self) -> bool {
         !self.amount.is_zero()
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> bool {
-        op.matches(self.amount.cmp(&other.amount))
+    /// The nearest float, which loses precision beyond about 15 significant digits
+    /// (e.g. `float(Money("0.1"))` is not exactly 0.1). Keep `.amount` for calculations.
+    fn __float__(&self) -> f64 {
+        self.amount.to_f64().unwrap_or(f64::NAN)
     }
 
-    pub fn for_json(&self) -> String {
-        return format!(
-            "{number:.prec$}",
-            number = self.round(MONEY_PRECISION).amount,
-            prec = MONEY_PRECISION.unwrap() as usize
-        );
+    /// The amount truncated towards zero like `int(Decimal(...))`, dropping the cents
+    fn __int__(&self) -> i128 {
+        self.amount.trunc().to_i128().unwrap_or_default()
     }
 
+    fn __trunc__(&self) -> i128 {
+        self.__int__()
+    }
+
+    /// Orders against Money and decimals (which pydantic's `ge`/`le`/`gt`/`lt` constraints
+    /// rely on), while equality stays restricted to Money.
+    /// Amounts in different currencies are never equal and can't be ordered.
+    fn __richcmp__(&self, other: Bound<PyAny>, op: CompareOp, py: Python) -> PyResult<PyObject> {
+        let other_amount = if let Ok(money) = other.extract::<Self>() {
+            // Equality needs the same currency, amounts without one only order with others
+            match op {
+                CompareOp::Eq if self.currency != money.currency => return Ok(false.into_py(py)),
+                CompareOp::Ne if self.currency != money.currency => return Ok(true.into_py(py)),
+                _ => combined_currency(self.currency, money.currency)?,
+            };
+            money.amount
+        } else if matches!(op, CompareOp::Eq | CompareOp::Ne)
+            || other.is_instance_of::<PyString>()
+            || other.is_instance_of::<MoneyWithVAT>()
+        {
+            return Ok(py.NotImplemented());
+        } else if let Ok(decimal) = decimal_extract(other) {
+            decimal
+        } else {
+            return Ok(py.NotImplemented());
+        };
+
+        Ok(op.matches(self.amount.cmp(&other_amount)).into_py(py))
+    }
+
+    /// Remainder with the sign of the amount like Decimal's `%`, e.g. `Money("10.07") % 0.05`.
+    fn __mod__(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Operation);
+        let (divisor, currency) = self.divisor_extract(other)?;
+
+        Ok(Self {
+            amount: decimal_rem(self.amount, divisor)?,
+            currency,
+        })
+    }
+
+    /// Integer part of the quotient like Decimal's `//`, truncating towards zero.
+    /// `Money // Money` returns a Decimal, `Money // number` returns Money.
+    fn __floordiv__(&self, other: Bound<PyAny>, py: Python) -> PyResult<PyObject> {
+        count(Counter::Operation);
+        let by_money = other.is_instance_of::<Self>();
+        let (divisor, currency) = self.divisor_extract(other)?;
+        let quotient = decimal_floordiv(self.amount, divisor)?;
+
+        Ok(if by_money {
+            quotient.into_py(py)
+        } else {
+            Self {
+                amount: quotient,
+                currency,
+            }
+            .into_py(py)
+        })
+    }
+
+    fn __rfloordiv__(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Operation);
+        if self.amount.is_zero() {
+            return Err(division_by_zero());
+        }
+
+        match decimal_extract(other) {
+            Ok(other_decimal) => Ok(Self {
+                amount: decimal_floordiv(other_decimal, self.amount)?,
+                currency: self.currency,
+            }),
+            Err(_) => Err(pyo3::exceptions::PyTypeError::new_err(
+                "Unsupported operand",
+            )),
+        }
+    }
+
+    /// Returns `(self // other, self % other)`.
+    fn __divmod__(&self, other: Bound<PyAny>, py: Python) -> PyResult<(PyObject, Self)> {
+        let remainder = self.__mod__(other.clone())?;
+        Ok((self.__floordiv__(other, py)?, remainder))
+    }
+
+    /// The amount rounded to MONEY_PRECISION as string, e.g. `"1.500000000000"`, or for
+    /// amounts with a currency `{"amount": "1.500000000000", "currency": "EUR"}`.
+    pub fn for_json(&self) -> PyResult<PyObject> {
+        Python::with_gil(|py| match self.currency {
+            Some(currency) => {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("amount", self.json_amount())?;
+                dict.set_item("currency", currency.get_code())?;
+                Ok(dict.into())
+            }
+            None => Ok(self.json_amount().into_py(py)),
+        })
+    }
+
+    /// Returns the (float value, is_exact, exact string) triple needed for spreadsheet exports.
+    /// `is_exact` tells whether the float still represents the exact amount.
+    pub fn to_excel(&self) -> (f64, bool, String) {
+        let value = self.amount.to_f64().unwrap_or(f64::NAN);
+        let is_exact = Decimal::from_str(&value.to_string()).is_ok_and(|d| d == self.amount);
+
+        (value, is_exact, self.amount.normalize().to_string())
+    }
+
+    /// Returns the amount the way DATEV imports expect it: absolute value with two
+    /// decimals and a decimal comma, plus the debit/credit indicator ("S" or "H").
+    pub fn to_datev(&self) -> (String, String) {
+        let rounded = self.round(Some(2)).amount;
+        let indicator = if rounded.is_sign_negative() && !rounded.is_zero() {
+            "H"
+        } else {
+            "S"
+        };
+
+        (
+            format!("{:.2}", rounded.abs()).replace('.', ","),
+            indicator.to_string(),
+        )
+    }
+
+    /// Returns the amount formatted for SEPA payment files (pain.001), e.g. `1234.50`.
+    /// Raises SepaAmountError if the amount has more than 2 decimals,
+    /// is not positive or exceeds 999999999.99.
+    pub fn to_sepa_amount(&self) -> PyResult<String> {
+        if self.amount.normalize().scale() > 2 {
+            return Err(SepaAmountError::new_err(format!(
+                "SEPA amounts allow at most 2 decimals, got {}",
+                self.amount
+            )));
+        }
+        if self.amount <= Decimal::new(0, 0) {
+            return Err(SepaAmountError::new_err(format!(
+                "SEPA amounts must be positive, got {}",
+                self.amount
+            )));
+        }
+        if self.amount > SEPA_MAX_AMOUNT {
+            return Err(SepaAmountError::new_err(format!(
+                "SEPA amounts must not exceed {}, got {}",
+                SEPA_MAX_AMOUNT, self.amount
+            )));
+        }
+
+        Ok(format!("{:.2}", self.amount))
+    }
+
+    /// Builds Money from one column of DB-API result rows (a cursor or any iterable of
+    /// rows), `column` being an index or a key for dict-like rows. NULL cells become None.
+    #[staticmethod]
+    #[pyo3(signature = (rows, column=None))]
+    fn from_rows(rows: Bound<PyAny>, column: Option<Bound<PyAny>>) -> PyResult<Vec<Option<Self>>> {
+        let py = rows.py();
+        let column = column.unwrap_or_else(|| 0_usize.into_py(py).into_bound(py));
+        let mut result = Vec::new();
+
+        for row in PyIterator::from_bound_object(&rows)? {
+            let cell = row?.get_item(&column)?;
+            if cell.is_none() {
+                result.push(None);
+            } else {
+                result.push(Some(Self::new(Some(cell), None)?));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The JSON string of `for_json`, e.g. `"1.500000000000"`.
+    fn to_json_str(&self) -> String {
+        json_dumps(&self.json_record())
+    }
+
+    /// JSON array of `to_json_str` values built without intermediate Python objects,
+    /// None values become null.
     #[staticmethod]
-    #[pyo3(signature = (value, _info=None))]
-    fn validate(value: Bound<PyAny>, _info: Option<Bound<PyAny>>) -> PyResult<Self> {
+    fn to_json_str_batch(elems: Bound<PyAny>) -> PyResult<String> {
+        let iterator = PyIterator::from_bound_object(&elems)?;
+        let mut result = Vec::new();
+
+        for item in iterator {
+            let money = item?.extract::<Option<Self>>()?;
+            result.push(money.map(|value| value.json_record()));
+        }
+
+        Ok(json_dumps(&result))
+    }
+
+    /// Reads an amount from a JSON string or number, e.g. `'"1.50"'` or `'1.50'`, or the
+    /// object written for amounts with a currency.
+    #[staticmethod]
+    fn from_json_str(text: &str) -> PyResult<Self> {
+        Self::from_json_record(json_loads(text)?)
+    }
+
+    /// Reads a JSON array of amounts like `to_json_str_batch` writes, null becomes None.
+    #[staticmethod]
+    fn from_json_str_batch(text: &str) -> PyResult<Vec<Option<Self>>> {
+        let records: Vec<Option<MoneyRecord<JsonAmount>>> = json_loads(text)?;
+        records
+            .into_iter()
+            .map(|record| record.map(Self::from_json_record).transpose())
+            .collect()
+    }
+
+    /// Exact msgpack encoding `{"amount": "1.50", "currency": "EUR"}` for message queues,
+    /// keeping the full precision unlike `for_json`.
+    fn to_msgpack_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let message = MoneyMessage {
+            amount: self.amount,
+            currency: self
+                .currency
+                .map(|currency| currency.get_code().to_string()),
+        };
+        PyBytes::new_bound(py, &msgpack_dumps(&message))
+    }
+
+    #[staticmethod]
+    fn from_msgpack_bytes(data: &[u8]) -> PyResult<Self> {
+        let message: MoneyMessage = msgpack_loads(data)?;
+        Ok(Self {
+            amount: message.amount,
+            currency: message_currency(message.currency)?,
+        })
+    }
+
+    /// Batch variant of `to_excel`, None values are passed through.
+    #[staticmethod]
+    fn to_excel_batch(elems: Bound<PyAny>) -> PyResult<Vec<Option<(f64, bool, String)>>> {
+        let iterator = PyIterator::from_bound_object(&elems)?;
+        let mut result = Vec::new();
+
+        for item in iterator {
+            let money = item?.extract::<Option<Self>>()?;
+            result.push(money.map(|value| value.to_excel()));
+        }
+
+        Ok(result)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (value, _info=None, locale=None))]
+    pub fn validate(
+        value: Bound<PyAny>,
+        _info: Option<Bound<PyAny>>,
+        locale: Option<&str>,
+    ) -> PyResult<Self> {
         if let Ok(money) = value.extract::<Self>() {
             return Ok(money);
-        } else if let Ok(decimal) = value.extract::<Decimal>() {
-            return Ok(Self { amount: decimal });
+        } else if let Ok(dict) = value.downcast::<PyDict>() {
+            if let Some(money) = Self::from_json_dict(dict, locale)? {
+                return Ok(money);
+            }
+        } else if let Some(decimal) = validation_extract(&value, locale)? {
+            return Ok(Self {
+                amount: decimal,
+                currency: None,
+            });
         }
 
-        Err(PyValueError::new_err("Validation error"))
+        Err(InvalidAmountError::new_err(format!(
+            "Validation error: can't use {} as Money",
+            value.repr()?
+        )))
     }
 
     #[staticmethod]
@@ -231,7 +1060,7 @@ impl Money {
             None,
             None,
             |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<Self> {
-                Self::validate(args.get_item(0).unwrap(), None)
+                Self::validate(args.get_item(0).unwrap(), None, None)
             },
         )?;
 
@@ -240,12 +1069,29 @@ impl Money {
             py,
             None,
             None,
-            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<String> {
-                if let Ok(money) = args.get_item(0)?.extract::<Self>() {
-                    return Ok(money.for_json());
+            |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
+                let py = args.py();
+                let value = args.get_item(0)?;
+                if let Ok(money) = value.extract::<Self>() {
+                    return match pydantic_serialization_mode(args)? {
+                        Some(true) => money.for_json(),
+                        Some(false) => match money.currency {
+                            Some(currency) => {
+                                let dict = PyDict::new_bound(py);
+                                dict.set_item("amount", money.amount)?;
+                                dict.set_item("currency", currency.get_code())?;
+                                Ok(dict.into())
+                            }
+                            None => Ok(money.amount.into_py(py)),
+                        },
+                        None => Ok(value.unbind()),
+                    };
                 }
 
-                Err(PyValueError::new_err("Validation error"))
+                Err(InvalidAmountError::new_err(format!(
+                    "Validation error: can't serialize {} as Money",
+                    value.repr()?
+                )))
             },
         )?;
 
@@ -253,15 +1099,13 @@ impl Money {
         function.set_item("type", "with-info")?;
         function.set_item("function", validate_fn)?;
 
-        let serialization = PyDict::new_bound(py);
-        serialization.set_item("type", "function-plain")?;
-        serialization.set_item("when_used", "json")?;
-        serialization.set_item("function", serialize_fn)?;
+        let serialization = pydantic_serialization_schema(py, serialize_fn)?;
 
         let schema = PyDict::new_bound(py);
         schema.set_item("type", "function-plain")?;
         schema.set_item("function", function)?;
         schema.set_item("serialization", serialization)?;
+        schema.set_item("ref", "alasco_money.Money")?;
 
         Ok(schema.into())
     }
@@ -277,21 +1121,451 @@ impl Money {
     pub fn __deepcopy__(&self, _memo: Bound<PyDict>) -> Self {
         self.clone()
     }
+
+    /// Pickles as the exact amount string and the currency code, keeping subclasses.
+    fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> (Bound<'py, PyType>, (String, Option<&'static str>)) {
+        let money = slf.get();
+        (
+            slf.get_type(),
+            (
+                money.amount.to_string(),
+                money.currency.map(|currency| currency.get_code()),
+            ),
+        )
+    }
+
+    /// `Money["EUR"]` annotates the currency, it evaluates to
+    /// `Annotated[Money, CurrencyConstraint]` which pydantic fields use to reject others.
+    #[classmethod]
+    fn __class_getitem__(cls: &Bound<PyType>, currency: &str) -> PyResult<PyObject> {
+        currency_annotated(cls, currency)
+    }
+
+    /// Supports `copy.replace(money, amount=..., currency=...)`.
+    #[pyo3(signature = (*, amount=None, currency=None))]
+    fn __replace__(
+        &self,
+        amount: Option<Bound<PyAny>>,
+        currency: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        let currency = match currency {
+            Some(currency) => Some(currency_extract(&currency)?),
+            None => self.currency,
+        };
+        let amount = match amount {
+            Some(amount) => Self::new(Some(amount), None)?.amount,
+            None => self.amount,
+        };
+
+        Ok(Self { amount, currency })
+    }
 }
 
 #[pyfunction]
+#[pyo3(signature = (elems, per_currency=false))]
 /// Sums Money elements while ignoring None values. Is ok with empty lists/iterables.
-pub fn sum_(elems: Bound<PyAny>) -> PyResult<Money> {
+/// Raises CurrencyMismatchError for elements in different currencies, unless
+/// `per_currency=True` asks for a MoneyBag with the sum per currency.
+pub fn sum_(py: Python, elems: Bound<PyAny>, per_currency: bool) -> PyResult<PyObject> {
     let iterator = PyIterator::from_bound_object(&elems)?;
     let mut amount: Decimal = Decimal::new(0, 0);
+    let mut currency = None;
+    let mut totals = CurrencyTotals::default();
 
-    for elem in iterator {
-        if let Ok(item) = elem {
-            if let Ok(Some(value)) = item.extract::<Option<Money>>() {
+    for item in iterator.flatten() {
+        if let Ok(Some(value)) = item.extract::<Option<Money>>() {
+            if per_currency {
+                totals.add(value.currency, value.amount, Decimal::new(0, 0));
+            } else {
                 amount = decimal_add(amount, value.amount);
+                currency = combined_currency(currency, value.currency)?;
             }
         }
     }
 
-    Ok(Money { amount })
+    Ok(if per_currency {
+        totals.sums(false).into_py(py)
+    } else {
+        Money { amount, currency }.into_py(py)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (elems, per_currency=false))]
+/// Averages Money or MoneyWithVAT elements while ignoring None values.
+/// Returns None for empty iterables or if only None values are given.
+/// Raises CurrencyMismatchError for elements in different currencies, unless
+/// `per_currency=True` asks for a MoneyBag with the average per currency (empty instead
+/// of None).
+pub fn average_(py: Python, elems: Bound<PyAny>, per_currency: bool) -> PyResult<Option<PyObject>> {
+    let iterator = PyIterator::from_bound_object(&elems)?;
+    let mut net: Decimal = Decimal::new(0, 0);
+    let mut tax: Decimal = Decimal::new(0, 0);
+    let mut count: u64 = 0;
+    let mut with_vat: Option<bool> = None;
+    let mut currency = None;
+    let mut totals = CurrencyTotals::default();
+
+    for elem in iterator {
+        let item = elem?;
+        if item.is_none() {
+            continue;
+        }
+
+        let (item_net, item_tax, item_currency, item_with_vat) =
+            if let Ok(value) = item.extract::<Money>() {
+                (value.amount, Decimal::new(0, 0), value.currency, false)
+            } else if let Ok(value) = item.extract::<MoneyWithVAT>() {
+                (
+                    value.net.amount,
+                    value.tax.amount,
+                    value.get_currency(),
+                    true,
+                )
+            } else {
+                return Err(pyo3::exceptions::PyTypeError::new_err(
+                    "Unsupported element",
+                ));
+            };
+
+        if *with_vat.get_or_insert(item_with_vat) != item_with_vat {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "Cannot average Money and MoneyWithVAT together",
+            ));
+        }
+
+        if per_currency {
+            totals.add(item_currency, item_net, item_tax);
+            continue;
+        }
+        currency = combined_currency(currency, item_currency)?;
+        net = decimal_add(net, item_net);
+        tax = decimal_add(tax, item_tax);
+        count += 1;
+    }
+
+    if per_currency {
+        return Ok(Some(totals.averages(with_vat == Some(true)).into_py(py)));
+    }
+
+    let divisor = Decimal::from(count);
+
+    Ok(match with_vat {
+        None => None,
+        Some(false) => Some(
+            Money {
+                amount: decimal_div(net, divisor),
+                currency,
+            }
+            .into_py(py),
+        ),
+        Some(true) => Some(
+            MoneyWithVAT {
+                net: Money {
+                    amount: decimal_div(net, divisor),
+                    currency,
+                },
+                tax: Money {
+                    amount: decimal_div(tax, divisor),
+                    currency,
+                },
+            }
+            .into_py(py),
+        ),
+    })
+}
+
+#[pyfunction]
+/// Returns the differences between consecutive Money elements (one less than given).
+pub fn diffs(elems: Bound<PyAny>) -> PyResult<Vec<Money>> {
+    let iterator = PyIterator::from_bound_object(&elems)?;
+    let mut result = Vec::new();
+    let mut previous: Option<Money> = None;
+
+    for elem in iterator {
+        let money = elem?.extract::<Money>()?;
+        if let Some(true_previous) = previous {
+            result.push(Money {
+                amount: decimal_add(money.amount, decimal_neg(true_previous.amount)),
+                currency: combined_currency(true_previous.currency, money.currency)?,
+            });
+        }
+        previous = Some(money);
+    }
+
+    Ok(result)
+}
+
+#[pyfunction]
+/// Counts and sums Money elements into buckets delimited by the sorted `boundaries`,
+/// ignoring None values. Bucket `i` holds values `boundaries[i - 1] <= value < boundaries[i]`,
+/// so there is one bucket more than boundaries. Raises CurrencyMismatchError unless
+/// elements and boundaries share their currency.
+pub fn bucketize(elems: Bound<PyAny>, boundaries: Vec<Money>) -> PyResult<Vec<(u64, Money)>> {
+    let limits: Vec<Decimal> = boundaries.iter().map(|money| money.amount).collect();
+    let mut currency = boundaries.iter().try_fold(None, |currency, money| {
+        combined_currency(currency, money.currency)
+    })?;
+
+    if limits.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(PyValueError::new_err(
+            "Boundaries must be strictly increasing",
+        ));
+    }
+
+    let mut buckets: Vec<(u64, Decimal)> = vec![(0, Decimal::new(0, 0)); limits.len() + 1];
+    let iterator = PyIterator::from_bound_object(&elems)?;
+
+    for elem in iterator {
+        if let Some(value) = elem?.extract::<Option<Money>>()? {
+            currency = combined_currency(currency, value.currency)?;
+            let bucket = &mut buckets[limits.partition_point(|limit| *limit <= value.amount)];
+            bucket.0 += 1;
+            bucket.1 = decimal_add(bucket.1, value.amount);
+        }
+    }
+
+    Ok(buckets
+        .into_iter()
+        .map(|(count, amount)| (count, Money { amount, currency }))
+        .collect())
+}
+
+#[pyfunction]
+/// Returns the constants and the current runtime configuration of the library.
+pub fn get_config(py: Python) -> PyResult<Bound<PyDict>> {
+    let config = PyDict::new_bound(py);
+    config.set_item("version", env!("CARGO_PKG_VERSION"))?;
+    config.set_item("money_precision", MONEY_PRECISION)?;
+    config.set_item("default_rounding", DEFAULT_ROUNDING)?;
+    config.set_item("known_vat_rates", MoneyWithVAT::known_vat_rates(None))?;
+    config.set_item(
+        "german_vat_rates",
+        MoneyWithVAT::german_vat_rates().to_vec(),
+    )?;
+    config.set_item(
+        "money_division",
+        if MONEY_DIVISION_RAISES.load(Ordering::Relaxed) {
+            "raise"
+        } else {
+            "decimal"
+        },
+    )?;
+    config.set_item(
+        "pydantic_serialization",
+        if PYDANTIC_SERIALIZES_PYTHON.load(Ordering::Relaxed) {
+            "always"
+        } else {
+            "json"
+        },
+    )?;
+    config.set_item(
+        "stats",
+        if stats_enabled() {
+            "enabled"
+        } else {
+            "disabled"
+        },
+    )?;
+    config.set_item(
+        "negative_zero_policy",
+        if normalizes_negative_zero() {
+            "normalize"
+        } else {
+            "preserve"
+        },
+    )?;
+
+    Ok(config)
+}
+
+// Builds the pydantic serialization schema, `serialize_fn` is called with (value, info)
+pub fn pydantic_serialization_schema<'py>(
+    py: Python<'py>,
+    serialize_fn: Bound<'py, PyCFunction>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let serialization = PyDict::new_bound(py);
+    serialization.set_item("type", "function-plain")?;
+    serialization.set_item("when_used", "always")?;
+    serialization.set_item("info_arg", true)?;
+    serialization.set_item("function", serialize_fn)?;
+    Ok(serialization)
+}
+
+// Tells the mode a value is serialized in: Some(true) for JSON, Some(false) for python
+// mode with Decimals and None for python mode keeping the value as is (the default)
+pub fn pydantic_serialization_mode(args: &Bound<PyTuple>) -> PyResult<Option<bool>> {
+    if args.get_item(1)?.getattr("mode")?.extract::<String>()? == "json" {
+        Ok(Some(true))
+    } else if PYDANTIC_SERIALIZES_PYTHON.load(Ordering::Relaxed) {
+        Ok(Some(false))
+    } else {
+        Ok(None)
+    }
+}
+
+// The only keyword argument `default` of max/min, where `default=None` counts as given
+pub fn default_kwarg<'py>(
+    kwargs: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Option<Bound<'py, PyAny>>> {
+    let Some(kwargs) = kwargs else {
+        return Ok(None);
+    };
+    if let Some(key) = kwargs
+        .keys()
+        .iter()
+        .find(|key| !key.eq("default").unwrap_or(false))
+    {
+        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "Unexpected keyword argument {}",
+            key
+        )));
+    }
+    kwargs.get_item("default")
+}
+
+// Iterates a single iterable argument or else the arguments themselves, like builtins.max
+pub fn variadic_items<'py>(args: &Bound<'py, PyTuple>) -> PyResult<Bound<'py, PyIterator>> {
+    if args.len() == 1 {
+        PyIterator::from_bound_object(&args.get_item(0)?)
+    } else {
+        PyIterator::from_bound_object(args)
+    }
+}
+
+// Builds `Annotated[cls, CurrencyConstraint(currency)]` for `Money["EUR"]`, checking for
+// a known ISO 4217 code
+pub fn currency_annotated(cls: &Bound<PyType>, currency: &str) -> PyResult<PyObject> {
+    let invalid =
+        || pyo3::exceptions::PyTypeError::new_err(format!("Invalid currency '{}'", currency));
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(invalid());
+    }
+    let constraint = CurrencyConstraint {
+        currency: Currency::from_code(currency).map_err(|_| invalid())?,
+    };
+
+    let py = cls.py();
+    let annotated = py.import_bound("typing")?.getattr("Annotated")?;
+    Ok(annotated
+        .get_item((cls, Bound::new(py, constraint)?))?
+        .unbind())
+}
+
+/// Metadata of `Money["EUR"]` and `MoneyWithVAT["EUR"]` annotations, pydantic fields
+/// annotated with it reject amounts in other currencies.
+#[pyclass(frozen, eq)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrencyConstraint {
+    #[pyo3(get)]
+    currency: Currency,
+}
+
+#[pymethods]
+impl CurrencyConstraint {
+    fn __get_pydantic_core_schema__(
+        &self,
+        source: Bound<PyAny>,
+        handler: Bound<PyAny>,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        let expected = self.currency;
+        let check_fn = PyCFunction::new_closure_bound(
+            py,
+            None,
+            None,
+            move |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
+                let value = args.get_item(0)?;
+                let currency = if let Ok(money) = value.extract::<Money>() {
+                    money.currency
+                } else {
+                    value.extract::<MoneyWithVAT>()?.get_currency()
+                };
+                combined_currency(Some(expected), currency)?;
+                Ok(value.unbind())
+            },
+        )?;
+
+        let function = PyDict::new_bound(py);
+        function.set_item("type", "no-info")?;
+        function.set_item("function", check_fn)?;
+
+        let schema = PyDict::new_bound(py);
+        schema.set_item("type", "function-after")?;
+        schema.set_item("function", function)?;
+        schema.set_item("schema", handler.call1((source,))?)?;
+
+        Ok(schema.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CurrencyConstraint('{}')", self.currency.get_code())
+    }
+}
+
+#[pyfunction]
+/// Configures when pydantic serializes money values: "json" (default) only in JSON mode,
+/// "always" also in python mode (e.g. `model_dump()`), into Decimals and dicts of Decimals.
+pub fn set_pydantic_serialization(mode: &str) -> PyResult<()> {
+    match mode {
+        "json" => PYDANTIC_SERIALIZES_PYTHON.store(false, Ordering::Relaxed),
+        "always" => PYDANTIC_SERIALIZES_PYTHON.store(true, Ordering::Relaxed),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown pydantic serialization mode '{}'",
+                mode
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+fn check_money_division() -> PyResult<()> {
+    if MONEY_DIVISION_RAISES.load(Ordering::Relaxed) {
+        Err(pyo3::exceptions::PyTypeError::new_err(
+            "Money / Money is disabled, use Money.ratio_to",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[pyfunction]
+/// Configures the result of `Money / Money`: "decimal" (default) returns the ratio
+/// as Decimal, "raise" raises a TypeError so that `ratio_to` has to be used.
+pub fn set_money_division(mode: &str) -> PyResult<()> {
+    match mode {
+        "decimal" => MONEY_DIVISION_RAISES.store(false, Ordering::Relaxed),
+        "raise" => MONEY_DIVISION_RAISES.store(true, Ordering::Relaxed),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown money division mode '{}'",
+                mode
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+#[pyfunction]
+/// Configures the handling of negative zeros: "preserve" (default) emulates Python's
+/// decimal semantics, "normalize" turns them into positive zeros in arithmetic,
+/// formatting and for_json.
+pub fn set_negative_zero_policy(policy: &str) -> PyResult<()> {
+    match policy {
+        "preserve" => set_normalize_negative_zero(false),
+        "normalize" => set_normalize_negative_zero(true),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown negative zero policy '{}'",
+                policy
+            )))
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,623 @@
+use pyo3::exceptions::{PyIndexError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyIterator, PyList, PySlice, PyString};
+use rust_decimal::Decimal;
+
+use crate::currency::{combined_currency, currency_extract, Currency};
+use crate::decimals::*;
+use crate::money::{Money, MONEY_PRECISION};
+use crate::money_vat::MoneyWithVAT;
+use crate::stats::{count, Counter};
+
+/// Compact container of amounts. The amounts are stored contiguously as decimals
+/// (96 bit mantissa plus sign and scale) instead of as one Python object each, sharing
+/// one currency.
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct MoneyArray {
+    pub amounts: Vec<Decimal>,
+    pub currency: Option<Currency>,
+}
+
+// Turns a Python index into a position, counting negative indices from the end
+fn position(len: usize, index: isize) -> PyResult<usize> {
+    let len = len as isize;
+    let position = if index < 0 { index + len } else { index };
+
+    if position < 0 || position >= len {
+        Err(PyIndexError::new_err("Array index out of range"))
+    } else {
+        Ok(position as usize)
+    }
+}
+
+fn check_lengths(len: usize, other_len: usize) -> PyResult<()> {
+    if len == other_len {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(format!(
+            "Array lengths differ: {} and {}",
+            len, other_len
+        )))
+    }
+}
+
+// Running totals of the values
+fn cumulated(values: &[Decimal]) -> Vec<Decimal> {
+    values
+        .iter()
+        .scan(Decimal::new(0, 0), |total, value| {
+            *total = decimal_add(*total, *value);
+            Some(*total)
+        })
+        .collect()
+}
+
+// Returns the positions selected by a Python slice
+fn slice_positions(slice: &Bound<PySlice>, len: usize) -> PyResult<Vec<usize>> {
+    let indices = slice.indices(len as isize)?;
+    Ok((0..indices.slicelength)
+        .map(|i| (indices.start + i as isize * indices.step) as usize)
+        .collect())
+}
+
+impl MoneyArray {
+    fn money(&self, amount: Decimal) -> Money {
+        Money {
+            amount,
+            currency: self.currency,
+        }
+    }
+
+    // Amounts in the currency of the array
+    fn with_amounts(&self, amounts: Vec<Decimal>) -> Self {
+        Self {
+            amounts,
+            currency: self.currency,
+        }
+    }
+
+    // Extracts an amount (Money or anything Money accepts) in the currency of the array
+    fn amount_extract(&self, value: Bound<PyAny>) -> PyResult<(Decimal, Option<Currency>)> {
+        let money = Money::new(Some(value), None)?;
+        Ok((
+            money.amount,
+            combined_currency(self.currency, money.currency)?,
+        ))
+    }
+
+    // Reads the amounts of an array or an iterable up front, so that the array to extend
+    // isn't borrowed yet and `a.extend(a)` works
+    fn extension(elems: &Bound<PyAny>) -> PyResult<Self> {
+        if let Ok(other) = elems.downcast::<Self>() {
+            return Ok(other.borrow().clone());
+        }
+
+        let mut result = Self::default();
+        for item in PyIterator::from_bound_object(elems)? {
+            result.append(item?)?;
+        }
+        Ok(result)
+    }
+
+    fn extend_with(&mut self, other: Self) -> PyResult<()> {
+        self.currency = combined_currency(self.currency, other.currency)?;
+        self.amounts.extend(other.amounts);
+        Ok(())
+    }
+
+    // Evaluates a condition given as threshold (amounts above it), predicate or boolean mask
+    fn selection(&self, condition: Bound<PyAny>) -> PyResult<Vec<bool>> {
+        let invalid = || PyTypeError::new_err("Expected a threshold, predicate or mask");
+        if condition.is_instance_of::<PyBool>() {
+            return Err(invalid());
+        }
+        // Sequences and arrays are masks even if they convert to a number, like a NumPy
+        // array of one element does
+        let is_mask = condition.hasattr("__len__")? || condition.hasattr("__array__")?;
+
+        let threshold = if let Ok(money) = condition.extract::<Money>() {
+            combined_currency(self.currency, money.currency)?;
+            Some(money.amount)
+        } else if is_mask || condition.is_callable() || condition.is_instance_of::<PyString>() {
+            None
+        } else {
+            decimal_extract(condition.clone()).ok()
+        };
+
+        if let Some(threshold) = threshold {
+            return Ok(self
+                .amounts
+                .iter()
+                .map(|amount| *amount > threshold)
+                .collect());
+        }
+
+        if condition.is_callable() {
+            return self
+                .amounts
+                .iter()
+                .map(|amount| condition.call1((self.money(*amount),))?.is_truthy())
+                .collect();
+        }
+
+        let mask: Vec<bool> = PyIterator::from_bound_object(&condition)
+            .and_then(|iterator| iterator.map(|item| item?.extract::<bool>()).collect())
+            .map_err(|_| invalid())?;
+        if mask.len() != self.amounts.len() {
+            return Err(PyValueError::new_err("Mask length doesn't match the array"));
+        }
+        Ok(mask)
+    }
+}
+
+#[pymethods]
+impl MoneyArray {
+    /// `currency` is an optional ISO 4217 code or Currency, otherwise the array adopts the
+    /// currency of its elements.
+    #[new]
+    #[pyo3(signature = (elems=None, currency=None))]
+    fn new(elems: Option<Bound<PyAny>>, currency: Option<Bound<PyAny>>) -> PyResult<Self> {
+        let mut result = Self {
+            amounts: Vec::new(),
+            currency: currency.map(|obj| currency_extract(&obj)).transpose()?,
+        };
+        if let Some(elems) = elems {
+            result.extend_with(Self::extension(&elems)?)?;
+        }
+        Ok(result)
+    }
+
+    fn __repr__(&self) -> String {
+        let amounts: Vec<String> = self
+            .amounts
+            .iter()
+            .map(|amount| format!("'{}'", amount))
+            .collect();
+        match self.currency {
+            Some(currency) => format!(
+                "MoneyArray([{}], currency='{}')",
+                amounts.join(", "),
+                currency.get_code()
+            ),
+            None => format!("MoneyArray([{}])", amounts.join(", ")),
+        }
+    }
+
+    #[getter(currency)]
+    fn get_currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    fn __len__(&self) -> usize {
+        self.amounts.len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let elems = self.tolist().into_iter().map(|money| money.into_py(py));
+        PyList::new_bound(py, elems).into_any().iter()
+    }
+
+    fn __getitem__(&self, index: Bound<PyAny>, py: Python) -> PyResult<PyObject> {
+        if let Ok(slice) = index.downcast::<PySlice>() {
+            let amounts = slice_positions(slice, self.amounts.len())?
+                .into_iter()
+                .map(|i| self.amounts[i])
+                .collect();
+            Ok(self.with_amounts(amounts).into_py(py))
+        } else {
+            let position = position(self.amounts.len(), index.extract::<isize>()?)?;
+            Ok(self.money(self.amounts[position]).into_py(py))
+        }
+    }
+
+    fn __setitem__(&mut self, index: isize, value: Bound<PyAny>) -> PyResult<()> {
+        let position = position(self.amounts.len(), index)?;
+        let (amount, currency) = self.amount_extract(value)?;
+        self.amounts[position] = amount;
+        self.currency = currency;
+        Ok(())
+    }
+
+    /// Appends an amount (Money or anything Money accepts) in the currency of the array.
+    fn append(&mut self, value: Bound<PyAny>) -> PyResult<()> {
+        let (amount, currency) = self.amount_extract(value)?;
+        self.amounts.push(amount);
+        self.currency = currency;
+        Ok(())
+    }
+
+    /// Appends all amounts of an iterable.
+    fn extend(slf: &Bound<Self>, elems: Bound<PyAny>) -> PyResult<()> {
+        let extension = Self::extension(&elems)?;
+        slf.borrow_mut().extend_with(extension)
+    }
+
+    fn sum(&self) -> Money {
+        self.money(
+            self.amounts
+                .iter()
+                .fold(Decimal::new(0, 0), |total, amount| {
+                    decimal_add(total, *amount)
+                }),
+        )
+    }
+
+    /// Element-wise sum with an array of the same length, or adds an amount (Money or
+    /// anything Money accepts) to every element.
+    fn add(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        let (amounts, currency) = if let Ok(other) = other.extract::<PyRef<Self>>() {
+            check_lengths(self.amounts.len(), other.amounts.len())?;
+            let amounts = self
+                .amounts
+                .iter()
+                .zip(&other.amounts)
+                .map(|(amount, other)| decimal_add(*amount, *other))
+                .collect();
+            (amounts, combined_currency(self.currency, other.currency)?)
+        } else {
+            let (addend, currency) = self.amount_extract(other)?;
+            let amounts = self
+                .amounts
+                .iter()
+                .map(|amount| decimal_add(*amount, addend))
+                .collect();
+            (amounts, currency)
+        };
+
+        Ok(Self { amounts, currency })
+    }
+
+    /// Multiplies every amount by a number.
+    fn mul_scalar(&self, factor: Bound<PyAny>) -> PyResult<Self> {
+        let factor = decimal_extract(factor)?;
+        Ok(self.with_amounts(
+            self.amounts
+                .iter()
+                .map(|amount| decimal_mult(*amount, factor))
+                .collect(),
+        ))
+    }
+
+    /// Running totals, the last one being `sum()`.
+    fn cumsum(&self) -> Self {
+        self.with_amounts(cumulated(&self.amounts))
+    }
+
+    /// Rounds every amount like `Money.round`.
+    #[pyo3(signature = (n=None))]
+    fn round(&self, n: Option<i32>) -> Self {
+        let n = n.unwrap_or_default();
+        self.with_amounts(
+            self.amounts
+                .iter()
+                .map(|amount| decimal_round(*amount, n))
+                .collect(),
+        )
+    }
+
+    /// Rounds every amount to cents with one of the rounding constants of Python's
+    /// decimal module, e.g. `decimal.ROUND_HALF_UP`.
+    #[pyo3(signature = (strategy=DEFAULT_ROUNDING))]
+    fn round_to_cents(&self, strategy: &str) -> PyResult<Self> {
+        let strategy = rounding_strategy_extract(strategy)?;
+        Ok(self.with_amounts(
+            self.amounts
+                .iter()
+                .map(|amount| {
+                    count(Counter::Rounding);
+                    zero_policy(amount.round_dp_with_strategy(2, strategy))
+                })
+                .collect(),
+        ))
+    }
+
+    /// Rounds every amount like `MoneyWithVAT.rounded_to_money_field_precision` does net
+    /// and tax, to MONEY_PRECISION unless `precision` is given.
+    #[pyo3(signature = (precision=None))]
+    fn rounded_to_money_field_precision(&self, precision: Option<i32>) -> Self {
+        self.round(precision.or(MONEY_PRECISION))
+    }
+
+    /// Sums the amounts above a threshold, matching a predicate or selected by a boolean
+    /// mask (e.g. a NumPy array), e.g. `sum_where(0)` sums all positive amounts.
+    fn sum_where(&self, condition: Bound<PyAny>) -> PyResult<Money> {
+        let selection = self.selection(condition)?;
+        let amount = self
+            .amounts
+            .iter()
+            .zip(selection)
+            .filter(|(_, selected)| *selected)
+            .fold(Decimal::new(0, 0), |total, (amount, _)| {
+                decimal_add(total, *amount)
+            });
+
+        Ok(self.money(amount))
+    }
+
+    /// Counts the amounts selected like in `sum_where`.
+    fn count_where(&self, condition: Bound<PyAny>) -> PyResult<usize> {
+        Ok(self
+            .selection(condition)?
+            .into_iter()
+            .filter(|selected| *selected)
+            .count())
+    }
+
+    /// Sums the amounts per label, labels being any hashable values with one label per
+    /// amount. Labels keep their order of first appearance.
+    fn group_sum<'py>(&self, labels: Bound<'py, PyAny>) -> PyResult<Bound<'py, PyDict>> {
+        let py = labels.py();
+        let positions = PyDict::new_bound(py);
+        let mut totals: Vec<Decimal> = Vec::new();
+        let mut count = 0;
+
+        for (item, amount) in PyIterator::from_bound_object(&labels)?.zip(&self.amounts) {
+            let label = item?;
+            match positions.get_item(&label)? {
+                Some(position) => {
+                    let position = position.extract::<usize>()?;
+                    totals[position] = decimal_add(totals[position], *amount);
+                }
+                None => {
+                    positions.set_item(&label, totals.len())?;
+                    totals.push(*amount);
+                }
+            }
+            count += 1;
+        }
+
+        if count != self.amounts.len() || labels.len().is_ok_and(|len| len != count) {
+            return Err(PyValueError::new_err(
+                "Labels length doesn't match the array",
+            ));
+        }
+
+        let result = PyDict::new_bound(py);
+        for (label, position) in positions.iter() {
+            let amount = totals[position.extract::<usize>()?];
+            result.set_item(label, self.money(amount).into_py(py))?;
+        }
+        Ok(result)
+    }
+
+    fn tolist(&self) -> Vec<Money> {
+        self.amounts
+            .iter()
+            .map(|amount| self.money(*amount))
+            .collect()
+    }
+}
+
+/// Compact container of amounts with VAT, stored as parallel net and tax columns sharing
+/// one currency.
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct MoneyWithVATArray {
+    pub net: Vec<Decimal>,
+    pub tax: Vec<Decimal>,
+    pub currency: Option<Currency>,
+}
+
+impl MoneyWithVATArray {
+    fn get(&self, position: usize) -> MoneyWithVAT {
+        MoneyWithVAT::in_currency(self.net[position], self.tax[position], self.currency)
+    }
+
+    fn with_columns(&self, net: Vec<Decimal>, tax: Vec<Decimal>) -> Self {
+        Self {
+            net,
+            tax,
+            currency: self.currency,
+        }
+    }
+
+    // Reads the elements of an array or an iterable up front, see `MoneyArray::extension`
+    fn extension(elems: &Bound<PyAny>) -> PyResult<Self> {
+        if let Ok(other) = elems.downcast::<Self>() {
+            return Ok(other.borrow().clone());
+        }
+
+        let mut result = Self::default();
+        for item in PyIterator::from_bound_object(elems)? {
+            result.append(item?.extract::<MoneyWithVAT>()?)?;
+        }
+        Ok(result)
+    }
+
+    fn extend_with(&mut self, other: Self) -> PyResult<()> {
+        self.currency = combined_currency(self.currency, other.currency)?;
+        self.net.extend(other.net);
+        self.tax.extend(other.tax);
+        Ok(())
+    }
+
+    fn column(&self, amounts: &[Decimal]) -> MoneyArray {
+        MoneyArray {
+            amounts: amounts.to_vec(),
+            currency: self.currency,
+        }
+    }
+}
+
+#[pymethods]
+impl MoneyWithVATArray {
+    /// `currency` is an optional ISO 4217 code or Currency, otherwise the array adopts the
+    /// currency of its elements.
+    #[new]
+    #[pyo3(signature = (elems=None, currency=None))]
+    fn new(elems: Option<Bound<PyAny>>, currency: Option<Bound<PyAny>>) -> PyResult<Self> {
+        let mut result = Self {
+            currency: currency.map(|obj| currency_extract(&obj)).transpose()?,
+            ..Self::default()
+        };
+        if let Some(elems) = elems {
+            result.extend_with(Self::extension(&elems)?)?;
+        }
+        Ok(result)
+    }
+
+    fn __repr__(&self) -> String {
+        let elems: Vec<String> = self
+            .net
+            .iter()
+            .zip(&self.tax)
+            .map(|(net, tax)| format!("('{}', '{}')", net, tax))
+            .collect();
+        match self.currency {
+            Some(currency) => format!(
+                "MoneyWithVATArray([{}], currency='{}')",
+                elems.join(", "),
+                currency.get_code()
+            ),
+            None => format!("MoneyWithVATArray([{}])", elems.join(", ")),
+        }
+    }
+
+    #[getter(currency)]
+    fn get_currency(&self) -> Option<Currency> {
+        self.currency
+    }
+
+    fn __len__(&self) -> usize {
+        self.net.len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let elems = self.tolist().into_iter().map(|value| value.into_py(py));
+        PyList::new_bound(py, elems).into_any().iter()
+    }
+
+    fn __getitem__(&self, index: Bound<PyAny>, py: Python) -> PyResult<PyObject> {
+        if let Ok(slice) = index.downcast::<PySlice>() {
+            let positions = slice_positions(slice, self.net.len())?;
+            Ok(self
+                .with_columns(
+                    positions.iter().map(|i| self.net[*i]).collect(),
+                    positions.iter().map(|i| self.tax[*i]).collect(),
+                )
+                .into_py(py))
+        } else {
+            let position = position(self.net.len(), index.extract::<isize>()?)?;
+            Ok(self.get(position).into_py(py))
+        }
+    }
+
+    /// Net amounts as a MoneyArray.
+    #[getter(net)]
+    fn get_net(&self) -> MoneyArray {
+        self.column(&self.net)
+    }
+
+    /// Tax amounts as a MoneyArray.
+    #[getter(tax)]
+    fn get_tax(&self) -> MoneyArray {
+        self.column(&self.tax)
+    }
+
+    fn append(&mut self, value: MoneyWithVAT) -> PyResult<()> {
+        self.currency = combined_currency(self.currency, value.get_currency())?;
+        self.net.push(value.net.amount);
+        self.tax.push(value.tax.amount);
+        Ok(())
+    }
+
+    /// Appends all MoneyWithVAT elements of an iterable.
+    fn extend(slf: &Bound<Self>, elems: Bound<PyAny>) -> PyResult<()> {
+        let extension = Self::extension(&elems)?;
+        slf.borrow_mut().extend_with(extension)
+    }
+
+    fn sum(&self) -> MoneyWithVAT {
+        let zero = Decimal::new(0, 0);
+        MoneyWithVAT::in_currency(
+            self.net
+                .iter()
+                .fold(zero, |total, net| decimal_add(total, *net)),
+            self.tax
+                .iter()
+                .fold(zero, |total, tax| decimal_add(total, *tax)),
+            self.currency,
+        )
+    }
+
+    /// Element-wise sum with an array of the same length, or adds a MoneyWithVAT to every
+    /// element.
+    fn add(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        let (net, tax, currency) = if let Ok(other) = other.extract::<PyRef<Self>>() {
+            check_lengths(self.net.len(), other.net.len())?;
+            (other.net.clone(), other.tax.clone(), other.currency)
+        } else {
+            let addend = other.extract::<MoneyWithVAT>()?;
+            (
+                vec![addend.net.amount; self.net.len()],
+                vec![addend.tax.amount; self.tax.len()],
+                addend.get_currency(),
+            )
+        };
+
+        Ok(Self {
+            currency: combined_currency(self.currency, currency)?,
+            net: self
+                .net
+                .iter()
+                .zip(net)
+                .map(|(amount, other)| decimal_add(*amount, other))
+                .collect(),
+            tax: self
+                .tax
+                .iter()
+                .zip(tax)
+                .map(|(amount, other)| decimal_add(*amount, other))
+                .collect(),
+        })
+    }
+
+    /// Multiplies net and tax of every element by a number.
+    fn mul_scalar(&self, factor: Bound<PyAny>) -> PyResult<Self> {
+        let factor = decimal_extract(factor)?;
+        let scaled = |values: &[Decimal]| -> Vec<Decimal> {
+            values
+                .iter()
+                .map(|value| decimal_mult(*value, factor))
+                .collect()
+        };
+
+        Ok(self.with_columns(scaled(&self.net), scaled(&self.tax)))
+    }
+
+    /// Running totals of net and tax, the last element being `sum()`.
+    fn cumsum(&self) -> Self {
+        self.with_columns(cumulated(&self.net), cumulated(&self.tax))
+    }
+
+    /// Sums the elements per `tax_rate_for_display`, in order of first appearance.
+    fn group_by_rate<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let mut groups: Vec<(Decimal, MoneyWithVAT)> = Vec::new();
+
+        for position in 0..self.net.len() {
+            let value = self.get(position);
+            let rate = value.get_tax_rate_for_display();
+
+            match groups.iter_mut().find(|(known, _)| *known == rate) {
+                Some((_, total)) => {
+                    total.net.amount = decimal_add(total.net.amount, value.net.amount);
+                    total.tax.amount = decimal_add(total.tax.amount, value.tax.amount);
+                }
+                None => groups.push((rate, value)),
+            }
+        }
+
+        let result = PyDict::new_bound(py);
+        for (rate, total) in groups {
+            result.set_item(rate, total.into_py(py))?;
+        }
+        Ok(result)
+    }
+
+    fn tolist(&self) -> Vec<MoneyWithVAT> {
+        (0..self.net.len())
+            .map(|position| self.get(position))
+            .collect()
+    }
+}
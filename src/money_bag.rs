@@ -0,0 +1,163 @@
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+use pyo3::types::{PyIterator, PyList};
+use rust_decimal::Decimal;
+
+use crate::currency::{currency_extract, Currency};
+use crate::decimals::*;
+use crate::money::Money;
+use crate::money_vat::MoneyWithVAT;
+
+// Net and tax totals plus the number of values per currency, in order of first appearance
+#[derive(Debug, Default)]
+pub struct CurrencyTotals(Vec<(Option<Currency>, Decimal, Decimal, u64)>);
+
+impl CurrencyTotals {
+    pub fn add(&mut self, currency: Option<Currency>, net: Decimal, tax: Decimal) {
+        match self.0.iter_mut().find(|(known, ..)| *known == currency) {
+            Some((_, total_net, total_tax, count)) => {
+                *total_net = decimal_add(*total_net, net);
+                *total_tax = decimal_add(*total_tax, tax);
+                *count += 1;
+            }
+            None => self.0.push((currency, net, tax, 1)),
+        }
+    }
+
+    // The totals as MoneyWithVAT values, or as Money values of the net
+    pub fn sums(self, with_vat: bool) -> MoneyBag {
+        self.bag(with_vat, |total, _| total)
+    }
+
+    pub fn averages(self, with_vat: bool) -> MoneyBag {
+        self.bag(with_vat, |total, count| {
+            decimal_div(total, Decimal::from(count))
+        })
+    }
+
+    fn bag(self, with_vat: bool, amount: fn(Decimal, u64) -> Decimal) -> MoneyBag {
+        let values = self
+            .0
+            .into_iter()
+            .map(|(currency, net, tax, count)| {
+                if with_vat {
+                    BagValue::MoneyWithVAT(MoneyWithVAT::in_currency(
+                        amount(net, count),
+                        amount(tax, count),
+                        currency,
+                    ))
+                } else {
+                    BagValue::Money(Money {
+                        amount: amount(net, count),
+                        currency,
+                    })
+                }
+            })
+            .collect();
+
+        MoneyBag { values }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum BagValue {
+    Money(Money),
+    MoneyWithVAT(MoneyWithVAT),
+}
+
+impl BagValue {
+    fn currency(&self) -> Option<Currency> {
+        match self {
+            Self::Money(money) => money.currency,
+            Self::MoneyWithVAT(money_with_vat) => money_with_vat.get_currency(),
+        }
+    }
+
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            Self::Money(money) => money.clone().into_py(py),
+            Self::MoneyWithVAT(money_with_vat) => money_with_vat.clone().into_py(py),
+        }
+    }
+
+    fn repr(&self) -> String {
+        match self {
+            Self::Money(money) => money.__repr__(),
+            Self::MoneyWithVAT(money_with_vat) => money_with_vat.__repr__(),
+        }
+    }
+}
+
+/// Amounts per currency as returned with `per_currency=True`, reading like a dict of
+/// currencies to Money or MoneyWithVAT in order of first appearance. Amounts without a
+/// currency are kept apart under None.
+#[pyclass(frozen)]
+#[derive(Debug, Clone)]
+pub struct MoneyBag {
+    values: Vec<BagValue>,
+}
+
+impl MoneyBag {
+    fn find(&self, currency: Option<Bound<PyAny>>) -> PyResult<Option<&BagValue>> {
+        let currency = currency.map(|obj| currency_extract(&obj)).transpose()?;
+        Ok(self
+            .values
+            .iter()
+            .find(|value| value.currency() == currency))
+    }
+}
+
+#[pymethods]
+impl MoneyBag {
+    fn __len__(&self) -> usize {
+        self.values.len()
+    }
+
+    fn __getitem__(&self, currency: Option<Bound<PyAny>>, py: Python) -> PyResult<PyObject> {
+        let key = currency.clone().into_py(py);
+        match self.find(currency)? {
+            Some(value) => Ok(value.to_object(py)),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    fn __contains__(&self, currency: Option<Bound<PyAny>>) -> bool {
+        self.find(currency).is_ok_and(|value| value.is_some())
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let keys = self.keys().into_iter().map(|currency| currency.into_py(py));
+        PyList::new_bound(py, keys).into_any().iter()
+    }
+
+    /// Currencies in order of first appearance, None for amounts without currency.
+    fn keys(&self) -> Vec<Option<Currency>> {
+        self.values.iter().map(BagValue::currency).collect()
+    }
+
+    fn values(&self, py: Python) -> Vec<PyObject> {
+        self.values
+            .iter()
+            .map(|value| value.to_object(py))
+            .collect()
+    }
+
+    fn items(&self, py: Python) -> Vec<(Option<Currency>, PyObject)> {
+        self.values
+            .iter()
+            .map(|value| (value.currency(), value.to_object(py)))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        let items: Vec<String> = self
+            .values
+            .iter()
+            .map(|value| match value.currency() {
+                Some(currency) => format!("'{}': {}", currency.get_code(), value.repr()),
+                None => format!("None: {}", value.repr()),
+            })
+            .collect();
+        format!("MoneyBag({{{}}})", items.join(", "))
+    }
+}
@@ -0,0 +1,167 @@
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+
+use crate::currency::{combined_currency, Currency};
+use crate::decimals::*;
+use crate::errors::{division_by_zero, InvalidAmountError};
+use crate::money::Money;
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Add(Decimal),
+    // Multiplication by the fraction numerator / denominator
+    Mul(Decimal, Decimal),
+}
+
+/// Records a chain of operations on an amount and only rounds once when materialized.
+/// The value is kept as a fraction internally, so divisions don't introduce
+/// intermediate rounding either (as long as the fraction fits into a decimal).
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct MoneyExpr {
+    start: Decimal,
+    currency: Option<Currency>,
+    operations: Vec<Operation>,
+}
+
+impl MoneyExpr {
+    fn with(&self, operation: Operation, currency: Option<Currency>) -> PyResult<Self> {
+        let mut operations = self.operations.clone();
+        operations.push(operation);
+        Ok(Self {
+            start: self.start,
+            currency: combined_currency(self.currency, currency)?,
+            operations,
+        })
+    }
+
+    // Amount of a summand with its currency
+    fn summand(other: Bound<PyAny>) -> PyResult<(Decimal, Option<Currency>)> {
+        match other.extract::<Money>() {
+            Ok(money) => Ok((money.amount, money.currency)),
+            Err(_) => Ok((decimal_extract(other)?, None)),
+        }
+    }
+
+    fn factor(other: &Bound<PyAny>) -> PyResult<(Decimal, Decimal)> {
+        if let Some(fraction) = fraction_extract(other) {
+            Ok(fraction)
+        } else {
+            Ok((decimal_extract(other.clone())?, Decimal::new(1, 0)))
+        }
+    }
+
+    fn out_of_range(&self) -> PyErr {
+        InvalidAmountError::new_err(format!("Invalid amount {}: out of range", self.__repr__()))
+    }
+
+    // Divides the fraction, checking for overflow
+    fn divided(&self, numerator: Decimal, denominator: Decimal) -> PyResult<Decimal> {
+        numerator
+            .checked_div(denominator)
+            .map(|_| decimal_div(numerator, denominator))
+            .ok_or_else(|| self.out_of_range())
+    }
+
+    fn evaluate(&self) -> PyResult<Decimal> {
+        let mut numerator = self.start;
+        let mut denominator = Decimal::new(1, 0);
+
+        for operation in &self.operations {
+            let exact = match operation {
+                Operation::Add(summand) => summand
+                    .checked_mul(denominator)
+                    .and_then(|scaled| numerator.checked_add(scaled))
+                    .map(|sum| (sum, denominator)),
+                Operation::Mul(factor_numerator, factor_denominator) => numerator
+                    .checked_mul(*factor_numerator)
+                    .zip(denominator.checked_mul(*factor_denominator)),
+            };
+
+            (numerator, denominator) = match exact {
+                Some(fraction) => fraction,
+                None => {
+                    // Fraction grew too large, continue with the divided value
+                    let value = self.divided(numerator, denominator)?;
+                    let collapsed = match operation {
+                        Operation::Add(summand) => value
+                            .checked_add(*summand)
+                            .map(|_| decimal_add(value, *summand)),
+                        Operation::Mul(factor_numerator, factor_denominator) => value
+                            .checked_mul(*factor_numerator)
+                            .and_then(|product| product.checked_div(*factor_denominator))
+                            .map(|_| {
+                                decimal_mult_fraction(value, *factor_numerator, *factor_denominator)
+                            }),
+                    };
+                    (
+                        collapsed.ok_or_else(|| self.out_of_range())?,
+                        Decimal::new(1, 0),
+                    )
+                }
+            };
+        }
+
+        self.divided(numerator, denominator)
+    }
+}
+
+#[pymethods]
+impl MoneyExpr {
+    #[new]
+    fn new(start: Money) -> Self {
+        Self {
+            start: start.amount,
+            currency: start.currency,
+            operations: Vec::new(),
+        }
+    }
+
+    fn add(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        let (summand, currency) = Self::summand(other)?;
+        self.with(Operation::Add(summand), currency)
+    }
+
+    fn sub(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        let (subtrahend, currency) = Self::summand(other)?;
+        self.with(Operation::Add(decimal_neg(subtrahend)), currency)
+    }
+
+    fn mul(&self, factor: Bound<PyAny>) -> PyResult<Self> {
+        let (numerator, denominator) = Self::factor(&factor)?;
+        self.with(Operation::Mul(numerator, denominator), None)
+    }
+
+    fn div(&self, divisor: Bound<PyAny>) -> PyResult<Self> {
+        let (numerator, denominator) = Self::factor(&divisor)?;
+        if numerator == Decimal::new(0, 0) {
+            return Err(division_by_zero());
+        }
+        self.with(Operation::Mul(denominator, numerator), None)
+    }
+
+    /// Evaluates all recorded operations and rounds the result once to `n` decimals.
+    #[pyo3(signature = (n=2))]
+    fn materialize(&self, n: i32) -> PyResult<Money> {
+        Ok(Money {
+            amount: decimal_round(self.evaluate()?, n),
+            currency: self.currency,
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.operations.len()
+    }
+
+    fn __repr__(&self) -> String {
+        let start = Money {
+            amount: self.start,
+            currency: self.currency,
+        };
+        format!(
+            "MoneyExpr({}, operations={})",
+            start.__repr__(),
+            self.operations.len()
+        )
+    }
+}
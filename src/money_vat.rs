@@ -1,15 +1,28 @@
 use pyo3::basic::CompareOp;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyCFunction, PyDict, PyIterator, PyTuple};
-use rust_decimal::Decimal;
+use pyo3::types::{PyBytes, PyCFunction, PyDict, PyIterator, PyString, PyTuple, PyType};
+use rayon::prelude::*;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::cmp;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::str::FromStr;
 
+use crate::country::Country;
+use crate::currency::{combined_currency, currency_extract, Currency};
 use crate::decimals::*;
-use crate::money::{Money, MONEY_PRECISION};
+use crate::errors::{division_by_zero, InvalidAmountError};
+use crate::invoice::VATRoundingPolicy;
+use crate::json::{json_dumps, json_loads, JsonAmount, MoneyWithVATRecord};
+use crate::money::{
+    currency_annotated, default_kwarg, pydantic_serialization_mode, pydantic_serialization_schema,
+    variadic_items, Money, MONEY_PRECISION,
+};
+use crate::money_bag::CurrencyTotals;
 use crate::money_vat_ratio::MoneyWithVATRatio;
+use crate::msgpack::{message_currency, msgpack_dumps, msgpack_loads, MoneyWithVATMessage};
+use crate::stats::{count, Counter};
+use crate::tax_breakdown::TaxBreakdown;
 
 /// Known VAT rates in countries
 /// Germany (0.19, 0.16, 0.07, 0.05)
@@ -19,7 +32,32 @@ const KNOWN_VAT_RATES: [i16; 9] = [0, 5, 7, 10, 13, 16, 19, 20, 25];
 
 const GERMAN_VAT_RATES: [i16; 5] = [0, 5, 7, 16, 19];
 
-#[pyclass(subclass)]
+/// KNOWN_VAT_RATES as decimals, built once at compile time for `tax_rate_for_display`
+const KNOWN_VAT_RATE_DECIMALS: [Decimal; 9] = {
+    let mut rates = [Decimal::ZERO; 9];
+    let mut index = 0;
+    while index < rates.len() {
+        rates[index] = Decimal::from_parts(KNOWN_VAT_RATES[index] as u32, 0, 0, false, 2);
+        index += 1;
+    }
+    rates
+};
+
+/// Largest deviation of the tax from a known rate still displayed as that rate
+const DISPLAY_RATE_TOLERANCE: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+
+/// DATEV tax keys (BU-Schlüssel) as (VAT rate, input tax key, output tax key)
+const DATEV_TAX_KEYS: [(i16, &str, &str); 3] = [(0, "", ""), (7, "8", "2"), (19, "9", "3")];
+
+/// Smallest number of elements summed by one thread in `fast_sum`
+const PARALLEL_SUM_CHUNK: usize = 16384;
+
+/// Version of the flat JSON shape written by `for_json(with_schema_version=True)`,
+/// version 2 added the optional `currency`
+const JSON_SCHEMA_VERSION: u32 = 2;
+
+/// Frozen like Money, see MoneyWithVATBuilder for incremental construction.
+#[pyclass(subclass, frozen, module = "alasco_money")]
 #[derive(Debug, Clone)]
 pub struct MoneyWithVAT {
     #[pyo3(get)]
@@ -29,31 +67,175 @@ pub struct MoneyWithVAT {
     pub tax: Money,
 }
 
+impl MoneyWithVAT {
+    pub fn in_currency(net: Decimal, tax: Decimal, currency: Option<Currency>) -> Self {
+        Self {
+            net: Money {
+                amount: net,
+                currency,
+            },
+            tax: Money {
+                amount: tax,
+                currency,
+            },
+        }
+    }
+
+    fn json_record(&self) -> MoneyWithVATRecord<String> {
+        MoneyWithVATRecord {
+            net: self.net.json_amount(),
+            tax: self.tax.json_amount(),
+            currency: self
+                .get_currency()
+                .map(|currency| currency.get_code().to_string()),
+        }
+    }
+
+    fn from_json_record(record: MoneyWithVATRecord<JsonAmount>) -> PyResult<Self> {
+        let currency = record
+            .currency
+            .as_deref()
+            .map(Currency::from_code)
+            .transpose()?;
+        Ok(Self::in_currency(record.net.0, record.tax.0, currency))
+    }
+
+    // Splits the gross into net and tax for the given rate, the tax absorbs the difference
+    fn split_gross(gross: Money, rate: Decimal) -> PyResult<Self> {
+        let divisor = decimal_add(Decimal::new(1, 0), rate);
+
+        if divisor == Decimal::new(0, 0) {
+            return Err(division_by_zero());
+        }
+
+        let net = decimal_div(gross.amount, divisor);
+        Ok(Self::in_currency(
+            net,
+            decimal_add(gross.amount, decimal_neg(net)),
+            gross.currency,
+        ))
+    }
+
+    // `rounded_to_cents()` with its default policy, the tax absorbs the difference
+    fn rounded_to_cents_keeping_gross(&self, strategy: RoundingStrategy) -> Self {
+        self.rounded_keeping_gross(2, strategy)
+    }
+
+    // Rounds net and gross to `n` decimals, the tax absorbs the difference
+    fn rounded_keeping_gross(&self, n: i32, strategy: RoundingStrategy) -> Self {
+        let rounded_net = self.net.round_with_strategy(n, strategy).amount;
+        Self {
+            net: Money {
+                amount: rounded_net,
+                currency: self.get_currency(),
+            },
+            tax: Money {
+                amount: decimal_add(
+                    self.get_gross().round_with_strategy(n, strategy).amount,
+                    decimal_neg(rounded_net),
+                ),
+                currency: self.get_currency(),
+            },
+        }
+    }
+
+    fn extreme(
+        args: &Bound<PyTuple>,
+        default: Option<Bound<PyAny>>,
+        pick: fn(Decimal, Decimal) -> Decimal,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        let mut extreme: Option<(Decimal, Decimal)> = None;
+        let mut currency = None;
+        for item in variadic_items(args)? {
+            let item = item?;
+            if item.is_none() {
+                continue;
+            }
+            let value = item.extract::<Self>()?;
+            currency = combined_currency(currency, value.get_currency())?;
+            let (net, gross) = (value.net.amount, value.get_gross().amount);
+            extreme = Some(
+                extreme.map_or((net, gross), |(extreme_net, extreme_gross)| {
+                    (pick(extreme_net, net), pick(extreme_gross, gross))
+                }),
+            );
+        }
+
+        match (extreme, default) {
+            (Some((net, gross)), _) => Ok(Self {
+                net: Money {
+                    amount: net,
+                    currency,
+                },
+                tax: Money {
+                    amount: decimal_add(gross, decimal_neg(net)),
+                    currency,
+                },
+            }
+            .into_py(py)),
+            (None, Some(default)) => Ok(default.unbind()),
+            (None, None) => Err(PyValueError::new_err("Insufficient arguments")),
+        }
+    }
+
+    fn negated(&self) -> Self {
+        Self {
+            net: Money {
+                amount: decimal_neg(self.net.amount),
+                currency: self.get_currency(),
+            },
+            tax: Money {
+                amount: decimal_neg(self.tax.amount),
+                currency: self.get_currency(),
+            },
+        }
+    }
+}
+
 #[pymethods]
 impl MoneyWithVAT {
+    /// `currency` applies to net and tax, which must not be in different currencies.
     #[new]
-    #[pyo3(signature = (net=None, tax=None))]
-    fn new(net: Option<Bound<PyAny>>, tax: Option<Bound<PyAny>>) -> PyResult<Self> {
-        let net_result = Money::new(net);
-        let tax_result = Money::new(tax);
+    #[pyo3(signature = (net=None, tax=None, currency=None))]
+    fn new(
+        net: Option<Bound<PyAny>>,
+        tax: Option<Bound<PyAny>>,
+        currency: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        count(Counter::Construction);
+        let net_result = Money::new(net, currency.clone());
+        let tax_result = Money::new(tax, currency);
 
         match (net_result, tax_result) {
-            (Ok(net_money), Ok(tax_money)) => Ok(Self {
-                net: net_money,
-                tax: tax_money,
-            }),
+            (Ok(net_money), Ok(tax_money)) => Ok(Self::in_currency(
+                net_money.amount,
+                tax_money.amount,
+                combined_currency(net_money.currency, tax_money.currency)?,
+            )),
             (Err(err), _) => Err(err),
             (_, Err(err)) => Err(err),
         }
     }
 
+    #[getter(currency)]
+    pub fn get_currency(&self) -> Option<Currency> {
+        self.net.currency
+    }
+
     #[getter(gross)]
-    fn get_gross(&self) -> Money {
+    pub fn get_gross(&self) -> Money {
         Money {
             amount: decimal_add(self.net.amount, self.tax.amount),
+            currency: self.get_currency(),
         }
     }
 
+    /// Returns `(net, tax, gross)` as `decimal.Decimal` with their exact internal scales.
+    fn as_decimals(&self) -> (Decimal, Decimal, Decimal) {
+        (self.net.amount, self.tax.amount, self.get_gross().amount)
+    }
+
     #[getter(tax_rate)]
     fn get_tax_rate(&self) -> Decimal {
         if self.net.amount == Decimal::new(0, 0) {
@@ -63,27 +245,55 @@ impl MoneyWithVAT {
         }
     }
 
+    /// Tax rate relative to the gross (tax / gross) instead of the net, 0 for a zero gross
+    #[getter(tax_rate_of_gross)]
+    fn get_tax_rate_of_gross(&self) -> Decimal {
+        self.get_tax_share()
+    }
+
+    /// Share of the gross represented by the net, 0 for a zero gross
+    #[getter(net_share)]
+    fn get_net_share(&self) -> Decimal {
+        let gross = self.get_gross().amount;
+        if gross == Decimal::new(0, 0) {
+            Decimal::new(0, 0)
+        } else {
+            decimal_div(self.net.amount, gross)
+        }
+    }
+
+    /// Share of the gross represented by the tax, 0 for a zero gross
+    #[getter(tax_share)]
+    fn get_tax_share(&self) -> Decimal {
+        let gross = self.get_gross().amount;
+        if gross == Decimal::new(0, 0) {
+            Decimal::new(0, 0)
+        } else {
+            decimal_div(self.tax.amount, gross)
+        }
+    }
+
     /// Tax rate as decimal which is rounded to nearest known "real" VAT ratio
     /// if applicable (19.01 ==> 19.00; but not 23 ==> 19)
     /// ATTENTION: Don't use the result of this for calculations!
     #[getter(tax_rate_for_display)]
-    fn get_tax_rate_for_display(&self) -> Decimal {
-        let boundary = Decimal::from_str("0.05").unwrap();
+    pub fn get_tax_rate_for_display(&self) -> Decimal {
         let tax_rate = self.get_tax_rate();
 
-        if Self::known_vat_rates().contains(&tax_rate) {
-            return tax_rate;
-        }
-
-        for rate in Self::known_vat_rates() {
-            let vat = decimal_mult(rate, self.net.amount);
-            let vat_diff = (decimal_add(vat, decimal_neg(self.tax.amount))).abs();
-            if vat_diff < boundary {
+        // An exact match wins over an earlier rate within the tolerance
+        let mut nearby = None;
+        for rate in KNOWN_VAT_RATE_DECIMALS {
+            if rate == tax_rate {
                 return rate;
             }
+            if nearby.is_none()
+                && (rate * self.net.amount - self.tax.amount).abs() < DISPLAY_RATE_TOLERANCE
+            {
+                nearby = Some(rate);
+            }
         }
 
-        return tax_rate;
+        nearby.unwrap_or(tax_rate)
     }
 
     #[getter(is_positive)]
@@ -108,6 +318,164 @@ impl MoneyWithVAT {
         self.is_equal_up_to_cents(other.clone()) || self.is_lower_up_to_cents(other.clone())
     }
 
+    /// Adds a net amount which is taxed at the current tax rate,
+    /// so the tax grows proportionally and the tax rate stays the same.
+    fn add_to_net(&self, money: Money) -> PyResult<Self> {
+        Ok(Self::in_currency(
+            decimal_add(self.net.amount, money.amount),
+            decimal_add(
+                self.tax.amount,
+                decimal_mult(money.amount, self.get_tax_rate()),
+            ),
+            combined_currency(self.get_currency(), money.currency)?,
+        ))
+    }
+
+    /// Adds an amount to the tax only, the net stays untouched.
+    fn add_to_tax(&self, money: Money) -> PyResult<Self> {
+        Ok(Self::in_currency(
+            self.net.amount,
+            decimal_add(self.tax.amount, money.amount),
+            combined_currency(self.get_currency(), money.currency)?,
+        ))
+    }
+
+    /// Adds an untaxed amount to the net only, the tax stays untouched
+    /// (and therefore the tax rate changes).
+    fn add_net_only(&self, money: Money) -> PyResult<Self> {
+        Ok(Self::in_currency(
+            decimal_add(self.net.amount, money.amount),
+            self.tax.amount,
+            combined_currency(self.get_currency(), money.currency)?,
+        ))
+    }
+
+    /// Keeps the net and recomputes the tax with the given rate,
+    /// e.g. to correct documents which declared the wrong rate.
+    /// With `stable="gross"` the VAT is taken out of the gross instead, like `redistribute`.
+    #[pyo3(signature = (rate, *, stable="net"))]
+    fn with_tax_rate(&self, rate: Bound<PyAny>, stable: &str) -> PyResult<Self> {
+        let rate_decimal = decimal_extract(rate)?;
+
+        match stable {
+            "net" => {}
+            "gross" => return Self::split_gross(self.get_gross(), rate_decimal),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown stable amount '{}', use 'net' or 'gross'",
+                    stable
+                )))
+            }
+        }
+
+        Ok(Self {
+            net: self.net.clone(),
+            tax: Money {
+                amount: decimal_mult(self.net.amount, rate_decimal),
+                currency: self.get_currency(),
+            },
+        })
+    }
+
+    /// Keeps the gross and splits it anew into net and tax for the given rate
+    /// (net = gross / (1 + rate)), e.g. for bookings recorded under the wrong VAT regime.
+    fn redistribute(&self, rate: Bound<PyAny>) -> PyResult<Self> {
+        Self::split_gross(self.get_gross(), decimal_extract(rate)?)
+    }
+
+    /// Builds the amount from the net and a VAT rate, e.g.
+    /// `MoneyWithVAT.from_net_and_rate(100, "0.19") ==> net 100 + VAT 19`. Not rounded.
+    #[staticmethod]
+    fn from_net_and_rate(net: Bound<PyAny>, rate: Bound<PyAny>) -> PyResult<Self> {
+        let net = Money::new(Some(net), None)?;
+        let tax = decimal_mult(net.amount, decimal_extract(rate)?);
+
+        Ok(Self::in_currency(net.amount, tax, net.currency))
+    }
+
+    /// Builds the amount from the gross and a VAT rate (net = gross / (1 + rate)), the tax
+    /// is the difference so that the gross is kept exactly. Not rounded.
+    #[staticmethod]
+    fn from_gross_and_rate(gross: Bound<PyAny>, rate: Bound<PyAny>) -> PyResult<Self> {
+        Self::split_gross(Money::new(Some(gross), None)?, decimal_extract(rate)?)
+    }
+
+    /// Builds amounts from `(net, tax)` rows (a cursor or any iterable of pairs) in one pass,
+    /// much faster than calling `MoneyWithVAT(net, tax)` per row.
+    #[staticmethod]
+    fn from_rows(rows: Bound<PyAny>) -> PyResult<Vec<Self>> {
+        let mut result = Vec::new();
+
+        for row in PyIterator::from_bound_object(&rows)? {
+            let row = row?;
+            // Tuples (what DB-API cursors yield) skip the generic sequence protocol
+            let (net, tax) = match row.downcast::<PyTuple>() {
+                Ok(tuple) if tuple.len() == 2 => (tuple.get_item(0)?, tuple.get_item(1)?),
+                _ if row.len()? == 2 => (row.get_item(0)?, row.get_item(1)?),
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "Expected (net, tax) rows, got a row of length {}",
+                        row.len()?
+                    )))
+                }
+            };
+            count(Counter::Construction);
+            let net = Money::new(Some(net), None)?;
+            let tax = Money::new(Some(tax), None)?;
+            result.push(Self::in_currency(
+                net.amount,
+                tax.amount,
+                combined_currency(net.currency, tax.currency)?,
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Splits proportionally to `ratios` like `Money.allocate`: the nets and the grosses of
+    /// the parts are allocated and add up exactly, the tax of a part is the difference
+    /// (same policy as `rounded_to_cents`), so the taxes add up to the tax as well.
+    fn allocate(&self, ratios: Bound<PyAny>) -> PyResult<Vec<Self>> {
+        let ratios = PyIterator::from_bound_object(&ratios)?
+            .map(|ratio| decimal_extract(ratio?))
+            .collect::<PyResult<Vec<_>>>()?;
+        let scale = cmp::max(self.net.allocation_scale(), self.tax.allocation_scale());
+
+        let nets = decimal_allocate(self.net.amount, &ratios, scale)?;
+        let grosses = decimal_allocate(self.get_gross().amount, &ratios, scale)?;
+
+        Ok(nets
+            .into_iter()
+            .zip(grosses)
+            .map(|(net, gross)| {
+                Self::in_currency(
+                    net,
+                    decimal_add(gross, decimal_neg(net)),
+                    self.get_currency(),
+                )
+            })
+            .collect())
+    }
+
+    /// Scales net and tax proportionally so that the gross equals `target_gross` exactly.
+    /// The net is scaled, the tax is the difference between target gross and scaled net
+    /// and therefore absorbs any rounding difference (same policy as `rounded_to_cents`).
+    fn scale_to_gross(&self, target_gross: Money) -> PyResult<Self> {
+        let gross = self.get_gross().amount;
+
+        if gross == Decimal::new(0, 0) {
+            return Err(division_by_zero());
+        }
+
+        let net = decimal_div(decimal_mult(self.net.amount, target_gross.amount), gross);
+
+        Ok(Self::in_currency(
+            net,
+            decimal_add(target_gross.amount, decimal_neg(net)),
+            combined_currency(self.get_currency(), target_gross.currency)?,
+        ))
+    }
+
     /// Use with caution - only intended for displaying money or before comparing exact amounts with user input.
     /// Effects of rounding money to cents include:
     ///     (a) the .tax_rate is no longer accurate (e.g. 0.1882 instead of 0.19)
@@ -116,74 +484,199 @@ impl MoneyWithVAT {
     ///             on net=4.44 & tax=2.23 so as to keep gross stable in 6.67 EUR)
     ///     (c) Comparing them later to their exact counterparts returns False
     ///     (d) Ratios formed from rounded amounts no longer add to 100%
-    fn rounded_to_cents(&self) -> Self {
-        let rounded_net = self.net.round(Some(2)).amount;
-        return Self {
-            net: Money {
-                amount: rounded_net,
-            },
-            tax: Money {
-                amount: decimal_add(
-                    self.get_gross().round(Some(2)).amount,
-                    decimal_neg(rounded_net),
-                ),
-            },
-        };
+    /// `stable` chooses the amounts matching their individually rounded exact values:
+    ///     "gross" (default): net and gross, the tax absorbs the difference
+    ///     "tax": tax and gross, the net absorbs the difference
+    ///     "net": net and tax, the gross (their sum) absorbs the difference
+    /// `strategy` is the rounding like in `Money.round`, e.g. "half_up" instead of half even.
+    #[pyo3(signature = (stable="gross", strategy=DEFAULT_ROUNDING))]
+    fn rounded_to_cents(&self, stable: &str, strategy: &str) -> PyResult<Self> {
+        let strategy = rounding_strategy_extract(strategy)?;
+        match stable {
+            "gross" => Ok(self.rounded_to_cents_keeping_gross(strategy)),
+            "tax" => {
+                let rounded_tax = self.tax.round_with_strategy(2, strategy).amount;
+                Ok(Self {
+                    net: Money {
+                        amount: decimal_add(
+                            self.get_gross().round_with_strategy(2, strategy).amount,
+                            decimal_neg(rounded_tax),
+                        ),
+                        currency: self.get_currency(),
+                    },
+                    tax: Money {
+                        amount: rounded_tax,
+                        currency: self.get_currency(),
+                    },
+                })
+            }
+            "net" => Ok(Self {
+                net: self.net.round_with_strategy(2, strategy),
+                tax: self.tax.round_with_strategy(2, strategy),
+            }),
+            _ => Err(PyValueError::new_err(format!(
+                "Unknown stable amount '{}'",
+                stable
+            ))),
+        }
     }
 
     /// When storing Money, values are implicitly rounded to the field precision,
     /// which is lower than normal decimal precision.
     /// This method returns an equivalently rounded value for comparison.
-    fn rounded_to_money_field_precision(&self) -> Self {
+    /// `precision` defaults to MONEY_PRECISION, pass the column scale for other fields.
+    #[pyo3(signature = (precision=None))]
+    fn rounded_to_money_field_precision(&self, precision: Option<i32>) -> Self {
+        let precision = precision.or(MONEY_PRECISION);
+
         Self {
-            net: self.net.round(MONEY_PRECISION),
-            tax: self.tax.round(MONEY_PRECISION),
+            net: self.net.round(precision),
+            tax: self.tax.round(precision),
         }
     }
 
+    /// Converts into the `currency` with a single exchange rate and rounds to its minor
+    /// unit (cents if no currency is given, the result has none then either).
+    /// By default the rounding happens after summation: net and gross are converted and
+    /// rounded, the tax is the difference (like `rounded_to_cents`, keeps gross stable).
+    /// With `round_before_sum` net and tax are rounded individually and gross is their sum.
+    #[pyo3(signature = (rate, round_before_sum=false, currency=None))]
+    fn convert(
+        &self,
+        rate: Bound<PyAny>,
+        round_before_sum: bool,
+        currency: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        let rate_decimal = decimal_extract(rate)?;
+        let currency = currency.map(|obj| currency_extract(&obj)).transpose()?;
+
+        if rate_decimal <= Decimal::new(0, 0) {
+            return Err(PyValueError::new_err("Exchange rate must be positive"));
+        }
+
+        let converted = Self::in_currency(
+            decimal_mult(self.net.amount, rate_decimal),
+            decimal_mult(self.tax.amount, rate_decimal),
+            currency,
+        );
+        let decimals = currency.map_or(2, |currency| currency.get_exponent()) as i32;
+
+        if round_before_sum {
+            Ok(Self {
+                net: converted.net.round(Some(decimals)),
+                tax: converted.tax.round(Some(decimals)),
+            })
+        } else {
+            Ok(converted.rounded_keeping_gross(decimals, RoundingStrategy::MidpointNearestEven))
+        }
+    }
+
+    /// Human friendly representation rounded to cents, e.g. `net 100.00 + VAT 19.00`.
+    /// Use `repr()` for the exact technical representation.
     fn __str__(&self) -> String {
-        self.__repr__()
+        format!("net {} + VAT {}", self.net.__str__(), self.tax.__str__())
     }
 
-    fn __repr__(&self) -> String {
-        format!(
-            "MoneyWithVAT(net='{}', tax='{}')",
-            self.net.amount, self.tax.amount
-        )
+    /// Formats the gross like `Money.__format__`, a "net:", "tax:" or "gross:" prefix
+    /// selects the amount, e.g. `f"{price:net:,.2n}"`.
+    fn __format__(&self, spec: &str, py: Python) -> PyResult<String> {
+        if spec.is_empty() {
+            return Ok(self.__str__());
+        }
+        match spec.split_once(':') {
+            Some(("net", spec)) => self.net.__format__(spec, py),
+            Some(("tax", spec)) => self.tax.__format__(spec, py),
+            Some(("gross", spec)) => self.get_gross().__format__(spec, py),
+            _ => self.get_gross().__format__(spec, py),
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        match self.get_currency() {
+            Some(currency) => format!(
+                "MoneyWithVAT(net='{}', tax='{}', currency='{}')",
+                self.net.amount,
+                self.tax.amount,
+                currency.get_code()
+            ),
+            None => format!(
+                "MoneyWithVAT(net='{}', tax='{}')",
+                self.net.amount, self.tax.amount
+            ),
+        }
     }
 
     fn __hash__(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.get_gross().amount.hash(&mut hasher);
+        self.get_currency().hash(&mut hasher);
         hasher.finish()
     }
 
+    /// Returns a hash that is stable across processes and versions, unlike `hash()`.
+    /// Like `hash()` it only depends on the gross amount, see `Money.stable_hash`.
+    fn stable_hash(&self) -> u64 {
+        self.get_gross().stable_hash()
+    }
+
     fn __neg__(&self) -> Self {
+        count(Counter::Operation);
+        self.negated()
+    }
+
+    fn __pos__(&self) -> Self {
+        count(Counter::Operation);
         Self {
-            net: self.net.__neg__(),
-            tax: self.tax.__neg__(),
+            net: Money {
+                amount: decimal_pos(self.net.amount),
+                currency: self.get_currency(),
+            },
+            tax: Money {
+                amount: decimal_pos(self.tax.amount),
+                currency: self.get_currency(),
+            },
         }
     }
 
     fn __abs__(&self) -> Self {
+        count(Counter::Operation);
         Self {
             net: Money {
                 amount: self.net.amount.abs(),
+                currency: self.get_currency(),
             },
             tax: Money {
                 amount: self.tax.amount.abs(),
+                currency: self.get_currency(),
             },
         }
     }
 
     fn __add__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        if let Ok(other_money_with_vat) = other.extract::<Self>() {
+        count(Counter::Operation);
+        if let Ok(other_money_with_vat) = other.downcast::<Self>() {
+            let other_money_with_vat = other_money_with_vat.get();
+            let currency =
+                combined_currency(self.get_currency(), other_money_with_vat.get_currency())?;
             Ok(Self {
                 net: Money {
                     amount: decimal_add(self.net.amount, other_money_with_vat.net.amount),
+                    currency,
                 },
                 tax: Money {
                     amount: decimal_add(self.tax.amount, other_money_with_vat.tax.amount),
+                    currency,
+                },
+            })
+        } else if let Some(zero) = zero_operand_extract(&other)? {
+            Ok(Self {
+                net: Money {
+                    amount: decimal_add(self.net.amount, zero),
+                    currency: self.get_currency(),
+                },
+                tax: Money {
+                    amount: decimal_add(self.tax.amount, zero),
+                    currency: self.get_currency(),
                 },
             })
         } else if let Ok(other_decimal) = decimal_extract(other) {
@@ -191,9 +684,11 @@ impl MoneyWithVAT {
                 Ok(Self {
                     net: Money {
                         amount: decimal_add(self.net.amount, other_decimal),
+                        currency: self.get_currency(),
                     },
                     tax: Money {
                         amount: decimal_add(self.tax.amount, other_decimal),
+                        currency: self.get_currency(),
                     },
                 })
             } else {
@@ -213,19 +708,36 @@ impl MoneyWithVAT {
     }
 
     fn __sub__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        if let Ok(other_money_with_vat) = other.extract::<Self>() {
+        count(Counter::Operation);
+        if let Ok(other_money_with_vat) = other.downcast::<Self>() {
+            let other_money_with_vat = other_money_with_vat.get();
+            let currency =
+                combined_currency(self.get_currency(), other_money_with_vat.get_currency())?;
             Ok(Self {
                 net: Money {
                     amount: decimal_add(
                         self.net.amount,
                         decimal_neg(other_money_with_vat.net.amount),
                     ),
+                    currency,
                 },
                 tax: Money {
                     amount: decimal_add(
                         self.tax.amount,
                         decimal_neg(other_money_with_vat.tax.amount),
                     ),
+                    currency,
+                },
+            })
+        } else if let Some(zero) = zero_operand_extract(&other)? {
+            Ok(Self {
+                net: Money {
+                    amount: decimal_add(self.net.amount, decimal_neg(zero)),
+                    currency: self.get_currency(),
+                },
+                tax: Money {
+                    amount: decimal_add(self.tax.amount, decimal_neg(zero)),
+                    currency: self.get_currency(),
                 },
             })
         } else if let Ok(other_decimal) = decimal_extract(other) {
@@ -233,9 +745,11 @@ impl MoneyWithVAT {
                 Ok(Self {
                     net: Money {
                         amount: decimal_add(self.net.amount, decimal_neg(other_decimal)),
+                        currency: self.get_currency(),
                     },
                     tax: Money {
                         amount: decimal_add(self.tax.amount, decimal_neg(other_decimal)),
+                        currency: self.get_currency(),
                     },
                 })
             } else {
@@ -251,28 +765,98 @@ impl MoneyWithVAT {
     }
 
     fn __rsub__(&self, other: Bound<PyAny>) -> PyResult<Self> {
-        self.__neg__().__add__(other)
+        self.negated().__add__(other)
+    }
+
+    /// Returns a hashable key ordering like the gross amounts, for cheap sorting and grouping.
+    fn sort_key(&self) -> (i128, i128) {
+        self.get_gross().sort_key()
+    }
+
+    /// Signed component-wise deviation of the actual amount from the budget.
+    #[staticmethod]
+    fn variance(actual: Self, budget: Self) -> PyResult<Self> {
+        Ok(Self::in_currency(
+            decimal_add(actual.net.amount, decimal_neg(budget.net.amount)),
+            decimal_add(actual.tax.amount, decimal_neg(budget.tax.amount)),
+            combined_currency(actual.get_currency(), budget.get_currency())?,
+        ))
+    }
+
+    /// Deviation of the gross amounts in percent of the gross budget, None for a zero budget.
+    #[staticmethod]
+    fn variance_pct(actual: Self, budget: Self) -> Option<Decimal> {
+        decimal_variance_pct(actual.get_gross().amount, budget.get_gross().amount)
+    }
+
+    /// Returns the component-wise difference `self - other` together with its
+    /// component-wise absolute value.
+    fn delta(&self, other: Self) -> PyResult<(Self, Self)> {
+        let diff = Self::in_currency(
+            decimal_add(self.net.amount, decimal_neg(other.net.amount)),
+            decimal_add(self.tax.amount, decimal_neg(other.tax.amount)),
+            combined_currency(self.get_currency(), other.get_currency())?,
+        );
+        let abs = diff.__abs__();
+
+        Ok((diff, abs))
+    }
+
+    /// Like `+`, but treats None as zero.
+    #[pyo3(signature = (other))]
+    fn add_opt(&self, other: Option<Bound<PyAny>>) -> PyResult<Self> {
+        match other {
+            Some(value) => self.__add__(value),
+            None => Ok(self.clone()),
+        }
+    }
+
+    /// Like `-`, but treats None as zero.
+    #[pyo3(signature = (other))]
+    fn sub_opt(&self, other: Option<Bound<PyAny>>) -> PyResult<Self> {
+        match other {
+            Some(value) => self.__sub__(value),
+            None => Ok(self.clone()),
+        }
     }
 
     fn __mul__(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Operation);
         if let Ok(other_ratio) = other.extract::<MoneyWithVATRatio>() {
             let net_value = decimal_mult(other_ratio.net_ratio, self.net.amount);
             Ok(Self {
-                net: Money { amount: net_value },
+                net: Money {
+                    amount: net_value,
+                    currency: self.get_currency(),
+                },
                 tax: Money {
                     amount: decimal_add(
                         decimal_mult(other_ratio.gross_ratio, self.get_gross().amount),
                         decimal_neg(net_value),
                     ),
+                    currency: self.get_currency(),
+                },
+            })
+        } else if let Some((numerator, denominator)) = fraction_extract(&other) {
+            Ok(Self {
+                net: Money {
+                    amount: decimal_mult_fraction(self.net.amount, numerator, denominator),
+                    currency: self.get_currency(),
+                },
+                tax: Money {
+                    amount: decimal_mult_fraction(self.tax.amount, numerator, denominator),
+                    currency: self.get_currency(),
                 },
             })
         } else if let Ok(other_decimal) = decimal_extract(other) {
             Ok(Self {
                 net: Money {
                     amount: decimal_mult(self.net.amount, other_decimal),
+                    currency: self.get_currency(),
                 },
                 tax: Money {
                     amount: decimal_mult(self.tax.amount, other_decimal),
+                    currency: self.get_currency(),
                 },
             })
         } else {
@@ -287,44 +871,63 @@ impl MoneyWithVAT {
     }
 
     fn __truediv__(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Operation);
+        if let Some((numerator, denominator)) = fraction_extract(&other) {
+            return if numerator == Decimal::new(0, 0) {
+                Err(division_by_zero())
+            } else {
+                Ok(Self {
+                    net: Money {
+                        amount: decimal_mult_fraction(self.net.amount, denominator, numerator),
+                        currency: self.get_currency(),
+                    },
+                    tax: Money {
+                        amount: decimal_mult_fraction(self.tax.amount, denominator, numerator),
+                        currency: self.get_currency(),
+                    },
+                })
+            };
+        }
+
         let other_decimal = match decimal_extract(other) {
             Ok(decimal) => decimal,
             Err(_) => return Err(pyo3::exceptions::PyTypeError::new_err("Invalid decimal")),
         };
 
         if other_decimal == Decimal::new(0, 0) {
-            Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                "Division by zero",
-            ))
+            Err(division_by_zero())
         } else {
             Ok(Self {
                 net: Money {
                     amount: decimal_div(self.net.amount, other_decimal),
+                    currency: self.get_currency(),
                 },
                 tax: Money {
                     amount: decimal_div(self.tax.amount, other_decimal),
+                    currency: self.get_currency(),
                 },
             })
         }
     }
 
     fn __rtruediv__(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Operation);
         let other_decimal = match decimal_extract(other) {
             Ok(decimal) => decimal,
             Err(_) => return Err(pyo3::exceptions::PyTypeError::new_err("Invalid decimal")),
         };
 
         if self.net.amount == Decimal::new(0, 0) || self.tax.amount == Decimal::new(0, 0) {
-            Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                "Division by zero",
-            ))
+            Err(division_by_zero())
         } else {
             Ok(Self {
                 net: Money {
                     amount: decimal_div(other_decimal, self.net.amount),
+                    currency: self.get_currency(),
                 },
                 tax: Money {
                     amount: decimal_div(other_decimal, self.tax.amount),
+                    currency: self.get_currency(),
                 },
             })
         }
@@ -334,69 +937,66 @@ impl MoneyWithVAT {
         !self.net.amount.is_zero() || !self.tax.amount.is_zero()
     }
 
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> bool {
-        op.matches(self.get_gross().amount.cmp(&other.get_gross().amount))
-    }
-
-    #[staticmethod]
-    #[pyo3(signature = (*args))]
-    fn max(args: &Bound<PyTuple>) -> PyResult<Self> {
-        let items = if args.len() == 1 {
-            PyIterator::from_bound_object(&args.get_item(0).unwrap()).unwrap()
+    /// Compares by gross. Like Money, equality needs a MoneyWithVAT in the same currency,
+    /// while ordering also works against Money and decimals (e.g. `invoice > Money("10000")`).
+    fn __richcmp__(&self, other: Bound<PyAny>, op: CompareOp, py: Python) -> PyResult<PyObject> {
+        let (other_gross, other_currency) = if let Ok(money_with_vat) = other.extract::<Self>() {
+            let currency = money_with_vat.get_currency();
+            match op {
+                CompareOp::Eq if self.get_currency() != currency => return Ok(false.into_py(py)),
+                CompareOp::Ne if self.get_currency() != currency => return Ok(true.into_py(py)),
+                _ => (money_with_vat.get_gross().amount, currency),
+            }
+        } else if matches!(op, CompareOp::Eq | CompareOp::Ne) {
+            return Ok(py.NotImplemented());
+        } else if let Ok(money) = other.extract::<Money>() {
+            (money.amount, money.currency)
+        } else if other.is_instance_of::<PyString>() {
+            return Ok(py.NotImplemented());
+        } else if let Ok(decimal) = decimal_extract(other) {
+            (decimal, None)
         } else {
-            PyIterator::from_bound_object(&args).unwrap()
+            return Ok(py.NotImplemented());
         };
 
-        let mut max_net: Option<Decimal> = None;
-        let mut max_gross: Option<Decimal> = None;
-
-        for item in items {
-            if let Ok(raw_value) = item {
-                if let Ok(value) = raw_value.extract::<MoneyWithVAT>() {
-                    max_net = Some(if let Some(true_max_net) = max_net {
-                        true_max_net.max(value.net.amount)
-                    } else {
-                        value.net.amount
-                    });
-                    max_gross = Some(if let Some(true_max_gross) = max_gross {
-                        true_max_gross.max(value.get_gross().amount)
-                    } else {
-                        value.get_gross().amount
-                    });
-                }
-            }
-        }
+        combined_currency(self.get_currency(), other_currency)?;
 
-        if let Some(true_max_net) = max_net {
-            if let Some(true_max_gross) = max_gross {
-                Ok(Self {
-                    net: Money {
-                        amount: true_max_net,
-                    },
-                    tax: Money {
-                        amount: decimal_add(true_max_gross, decimal_neg(true_max_net)),
-                    },
-                })
-            } else {
-                Err(pyo3::exceptions::PyValueError::new_err(
-                    "Insufficient arguments",
-                ))
-            }
-        } else {
-            Err(pyo3::exceptions::PyValueError::new_err(
-                "Insufficient arguments",
-            ))
-        }
+        Ok(op
+            .matches(self.get_gross().amount.cmp(&other_gross))
+            .into_py(py))
+    }
+
+    /// Maximum net and maximum gross (taken independently) of the arguments or a single
+    /// iterable, skipping None. Returns `default` if nothing remains, raises ValueError
+    /// without one.
+    #[staticmethod]
+    #[pyo3(signature = (*args, **kwargs))]
+    fn max(
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        Self::extreme(args, default_kwarg(kwargs)?, cmp::max, py)
+    }
+
+    /// Minimum net and minimum gross, see `max`.
+    #[staticmethod]
+    #[pyo3(signature = (*args, **kwargs))]
+    fn min(
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+        py: Python,
+    ) -> PyResult<PyObject> {
+        Self::extreme(args, default_kwarg(kwargs)?, cmp::min, py)
     }
 
     #[staticmethod]
     fn ratio(dividend: Self, divisor: Self) -> PyResult<MoneyWithVATRatio> {
+        combined_currency(dividend.get_currency(), divisor.get_currency())?;
         if divisor.net.amount == Decimal::new(0, 0)
             || divisor.get_gross().amount == Decimal::new(0, 0)
         {
-            Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                "Division by zero",
-            ))
+            Err(division_by_zero())
         } else {
             Ok(MoneyWithVATRatio {
                 net_ratio: decimal_div(dividend.net.amount, divisor.net.amount),
@@ -409,26 +1009,30 @@ impl MoneyWithVAT {
     #[pyo3(signature = (dividend=None, divisor=None))]
     fn safe_ratio(dividend: Option<Self>, divisor: Option<Self>) -> Option<MoneyWithVATRatio> {
         let fixed_dividend = if let Some(true_dividend) = dividend {
-            true_dividend.rounded_to_cents()
+            true_dividend.rounded_to_cents_keeping_gross(RoundingStrategy::MidpointNearestEven)
         } else {
             Self {
                 net: Money {
                     amount: Decimal::new(0, 0),
+                    currency: None,
                 },
                 tax: Money {
                     amount: Decimal::new(0, 0),
+                    currency: None,
                 },
             }
         };
         let fixed_divisor = if let Some(true_divisor) = divisor {
-            true_divisor.rounded_to_cents()
+            true_divisor.rounded_to_cents_keeping_gross(RoundingStrategy::MidpointNearestEven)
         } else {
             Self {
                 net: Money {
                     amount: Decimal::new(0, 0),
+                    currency: None,
                 },
                 tax: Money {
                     amount: Decimal::new(0, 0),
+                    currency: None,
                 },
             }
         };
@@ -462,9 +1066,11 @@ impl MoneyWithVAT {
                     Some(Self {
                         net: Money {
                             amount: decimal_div(true_dividend.net.amount, true_divisor),
+                            currency: true_dividend.get_currency(),
                         },
                         tax: Money {
                             amount: decimal_div(true_dividend.tax.amount, true_divisor),
+                            currency: true_dividend.get_currency(),
                         },
                     })
                 }
@@ -476,21 +1082,49 @@ impl MoneyWithVAT {
         }
     }
 
+    /// Sums MoneyWithVAT values while ignoring None values. `key` is an attribute name
+    /// or callable used to pull the MoneyWithVAT out of each (non None) item. Raises
+    /// CurrencyMismatchError for values in different currencies, unless
+    /// `per_currency=True` asks for a MoneyBag with the sum per currency.
     #[staticmethod]
-    fn fast_sum(iterable: Bound<PyAny>) -> PyResult<Self> {
-        match Self::fast_sum_with_none(iterable) {
+    #[pyo3(signature = (iterable, key=None, per_currency=false))]
+    fn fast_sum(
+        iterable: Bound<PyAny>,
+        key: Option<Bound<PyAny>>,
+        per_currency: bool,
+    ) -> PyResult<PyObject> {
+        let py = iterable.py();
+        if per_currency {
+            let mut totals = CurrencyTotals::default();
+            for raw_item in PyIterator::from_bound_object(&iterable)?.flatten() {
+                let item = match &key {
+                    Some(true_key) if !raw_item.is_none() => apply_key(&raw_item, true_key)?,
+                    _ => raw_item,
+                };
+                if let Ok(value) = item.downcast::<Self>() {
+                    let value = value.get();
+                    totals.add(value.get_currency(), value.net.amount, value.tax.amount);
+                }
+            }
+            return Ok(totals.sums(true).into_py(py));
+        }
+
+        match Self::fast_sum_with_none(iterable, key) {
             Ok(sum) => {
                 if let Some(value) = sum {
-                    Ok(value)
+                    Ok(value.into_py(py))
                 } else {
                     Ok(Self {
                         net: Money {
                             amount: Decimal::new(0, 0),
+                            currency: None,
                         },
                         tax: Money {
                             amount: Decimal::new(0, 0),
+                            currency: None,
                         },
-                    })
+                    }
+                    .into_py(py))
                 }
             }
             Err(err) => Err(err),
@@ -499,119 +1133,329 @@ impl MoneyWithVAT {
 
     /// This is a variation of fast_sum, that returns None if only None values are given.
     #[staticmethod]
-    fn fast_sum_with_none(iterable: Bound<PyAny>) -> PyResult<Option<Self>> {
+    #[pyo3(signature = (iterable, key=None))]
+    fn fast_sum_with_none(
+        iterable: Bound<PyAny>,
+        key: Option<Bound<PyAny>>,
+    ) -> PyResult<Option<Self>> {
+        let py = iterable.py();
         let iterator = PyIterator::from_bound_object(&iterable)?;
 
-        let mut net_sum: Decimal = Decimal::new(0, 0);
-        let mut tax_sum: Decimal = Decimal::new(0, 0);
-        let mut any_value: bool = false;
+        let mut amounts: Vec<(Decimal, Decimal)> = Vec::new();
+        let mut currency = None;
+
+        for raw_item in iterator.flatten() {
+            let item = match &key {
+                Some(true_key) if !raw_item.is_none() => apply_key(&raw_item, true_key)?,
+                _ => raw_item,
+            };
+            if let Ok(value) = item.downcast::<Self>() {
+                let value = value.get();
+                amounts.push((value.net.amount, value.tax.amount));
+                currency = combined_currency(currency, value.get_currency())?;
+            }
+        }
+
+        if amounts.is_empty() {
+            return Ok(None);
+        }
+
+        // The extracted amounts are summed in parallel without holding the GIL
+        let (net_sum, tax_sum) = py.allow_threads(|| {
+            amounts
+                .par_iter()
+                .with_min_len(PARALLEL_SUM_CHUNK)
+                .copied()
+                .reduce(
+                    || (Decimal::new(0, 0), Decimal::new(0, 0)),
+                    |(net, tax), (other_net, other_tax)| {
+                        (decimal_add(net, other_net), decimal_add(tax, other_tax))
+                    },
+                )
+        });
 
-        for elem in iterator {
-            if let Ok(item) = elem {
-                if let Ok(Some(value)) = item.extract::<Option<Self>>() {
-                    net_sum = decimal_add(net_sum, value.net.amount);
-                    tax_sum = decimal_add(tax_sum, value.tax.amount);
-                    any_value = true;
+        Ok(Some(Self::in_currency(net_sum, tax_sum, currency)))
+    }
+
+    /// Variation of fast_sum which also sums per `tax_rate_for_display`, so totals of
+    /// positions with different VAT rates keep their breakdown (see TaxBreakdown).
+    #[staticmethod]
+    #[pyo3(signature = (iterable, key=None))]
+    fn fast_sum_with_breakdown(
+        iterable: Bound<PyAny>,
+        key: Option<Bound<PyAny>>,
+    ) -> PyResult<TaxBreakdown> {
+        let iterator = PyIterator::from_bound_object(&iterable)?;
+
+        let mut sums: Vec<(Decimal, Decimal, Decimal)> = Vec::new();
+        let mut currency = None;
+
+        for raw_item in iterator.flatten() {
+            let item = match &key {
+                Some(true_key) if !raw_item.is_none() => apply_key(&raw_item, true_key)?,
+                _ => raw_item,
+            };
+            if let Ok(value) = item.downcast::<Self>() {
+                let value = value.get();
+                currency = combined_currency(currency, value.get_currency())?;
+                let rate = value.get_tax_rate_for_display();
+
+                match sums.iter_mut().find(|(known, _, _)| *known == rate) {
+                    Some((_, net, tax)) => {
+                        *net = decimal_add(*net, value.net.amount);
+                        *tax = decimal_add(*tax, value.tax.amount);
+                    }
+                    None => sums.push((rate, value.net.amount, value.tax.amount)),
                 }
             }
         }
 
-        if !any_value {
-            Ok(None)
-        } else {
-            Ok(Some(Self {
-                net: Money { amount: net_sum },
-                tax: Money { amount: tax_sum },
-            }))
+        Ok(TaxBreakdown::from_sums(sums, currency))
+    }
+
+    /// Variation of fast_sum rounding to cents according to `policy`, e.g. per line or
+    /// only on the document total (see VATRoundingPolicy).
+    #[staticmethod]
+    #[pyo3(signature = (iterable, policy, key=None))]
+    fn fast_sum_rounded(
+        iterable: Bound<PyAny>,
+        policy: VATRoundingPolicy,
+        key: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        let iterator = PyIterator::from_bound_object(&iterable)?;
+        let mut lines: Vec<Self> = Vec::new();
+
+        for raw_item in iterator.flatten() {
+            let item = match &key {
+                Some(true_key) if !raw_item.is_none() => apply_key(&raw_item, true_key)?,
+                _ => raw_item,
+            };
+            if let Ok(Some(value)) = item.extract::<Option<Self>>() {
+                lines.push(value);
+            }
         }
+
+        Ok(policy.totals(&lines)?.0)
+    }
+
+    /// Returns the DATEV booking fields for the gross amount: "Umsatz",
+    /// "Soll/Haben-Kennzeichen" and "BU-Schlüssel" (input or output tax key).
+    #[pyo3(signature = (input_tax=true))]
+    fn to_datev_booking(&self, input_tax: bool) -> PyResult<PyObject> {
+        let rate = self.get_tax_rate_for_display();
+        let tax_key = DATEV_TAX_KEYS
+            .iter()
+            .find(|(known_rate, _, _)| Decimal::new(*known_rate as i64, 2) == rate)
+            .map(|(_, input_key, output_key)| if input_tax { input_key } else { output_key })
+            .ok_or_else(|| PyValueError::new_err(format!("No DATEV tax key for rate {}", rate)))?;
+        let (amount, indicator) = self.get_gross().to_datev();
+
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("Umsatz", amount)?;
+            dict.set_item("Soll/Haben-Kennzeichen", indicator)?;
+            dict.set_item("BU-Schlüssel", *tax_key)?;
+            Ok(dict.into())
+        })
     }
 
-    fn for_json(&self) -> PyResult<PyObject> {
+    /// Flat `{"net": ..., "tax": ...}` dict plus the `currency` code if there is one,
+    /// tagged with `schema_version` on request.
+    #[pyo3(signature = (with_schema_version=false))]
+    pub fn for_json(&self, with_schema_version: bool) -> PyResult<PyObject> {
         Python::with_gil(|py| {
             let dict = PyDict::new_bound(py);
-            dict.set_item("net", self.net.for_json())?;
-            dict.set_item("tax", self.tax.for_json())?;
+            dict.set_item("net", self.net.json_amount())?;
+            dict.set_item("tax", self.tax.json_amount())?;
+            if let Some(currency) = self.get_currency() {
+                dict.set_item("currency", currency.get_code())?;
+            }
+            if with_schema_version {
+                dict.set_item("schema_version", JSON_SCHEMA_VERSION)?;
+            }
             Ok(dict.into())
         })
     }
 
+    /// The JSON object of `for_json` (without schema version) serialized in Rust.
+    fn to_json_str(&self) -> String {
+        json_dumps(&self.json_record())
+    }
+
+    /// JSON array of `to_json_str` objects built without intermediate Python dicts,
+    /// None values become null.
+    #[staticmethod]
+    fn to_json_str_batch(elems: Bound<PyAny>) -> PyResult<String> {
+        let iterator = PyIterator::from_bound_object(&elems)?;
+        let mut result = Vec::new();
+
+        for item in iterator {
+            let money_with_vat = item?.extract::<Option<Self>>()?;
+            result.push(money_with_vat.map(|value| value.json_record()));
+        }
+
+        Ok(json_dumps(&result))
+    }
+
+    /// Exact msgpack encoding `{"net": ..., "tax": ..., "currency": ...}` (amounts as
+    /// decimal strings) for message queues, see `Money.to_msgpack_bytes`.
+    fn to_msgpack_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let message = MoneyWithVATMessage {
+            net: self.net.amount,
+            tax: self.tax.amount,
+            currency: self
+                .get_currency()
+                .map(|currency| currency.get_code().to_string()),
+        };
+        PyBytes::new_bound(py, &msgpack_dumps(&message))
+    }
+
+    #[staticmethod]
+    fn from_msgpack_bytes(data: &[u8]) -> PyResult<Self> {
+        let message: MoneyWithVATMessage = msgpack_loads(data)?;
+        Ok(Self::in_currency(
+            message.net,
+            message.tax,
+            message_currency(message.currency)?,
+        ))
+    }
+
+    /// Reads a flat `{"net": ..., "tax": ...}` JSON object, amounts as strings or numbers,
+    /// with an optional `currency` code.
+    #[staticmethod]
+    fn from_json_str(text: &str) -> PyResult<Self> {
+        Self::from_json_record(json_loads(text)?)
+    }
+
+    /// Reads a JSON array of objects like `to_json_str_batch` writes, null becomes None.
     #[staticmethod]
-    #[pyo3(signature = (value, _info=None))]
-    fn validate(value: Bound<PyAny>, _info: Option<Bound<PyAny>>) -> PyResult<Self> {
+    fn from_json_str_batch(text: &str) -> PyResult<Vec<Option<Self>>> {
+        let records: Vec<Option<MoneyWithVATRecord<JsonAmount>>> = json_loads(text)?;
+        records
+            .into_iter()
+            .map(|record| record.map(Self::from_json_record).transpose())
+            .collect()
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (value, _info=None, locale=None))]
+    pub fn validate(
+        value: Bound<PyAny>,
+        _info: Option<Bound<PyAny>>,
+        locale: Option<&str>,
+    ) -> PyResult<Self> {
         if let Ok(money_with_vat) = value.extract::<Self>() {
             return Ok(money_with_vat);
         } else if let Ok(dict) = value.extract::<Bound<PyDict>>() {
             if let Ok(Some(net)) = dict.get_item("net") {
                 if let Ok(Some(tax)) = dict.get_item("tax") {
-                    if let Ok(true_net) = net.extract::<Decimal>() {
-                        if let Ok(true_tax) = tax.extract::<Decimal>() {
-                            return Ok(Self {
-                                net: Money { amount: true_net },
-                                tax: Money { amount: true_tax },
-                            });
+                    if let Some((true_net, net_currency)) = validation_field_extract(&net, locale)?
+                    {
+                        if let Some((true_tax, tax_currency)) =
+                            validation_field_extract(&tax, locale)?
+                        {
+                            let currency = combined_currency(
+                                combined_currency(net_currency, tax_currency)?,
+                                json_currency_extract(&dict)?,
+                            )?;
+                            return Ok(Self::in_currency(true_net, true_tax, currency));
                         }
                     }
                 }
             }
         }
 
-        Err(PyValueError::new_err("Validation error"))
+        Err(InvalidAmountError::new_err(format!(
+            "Validation error: can't use {} as MoneyWithVAT",
+            value.repr()?
+        )))
     }
 
     #[staticmethod]
     fn __get_pydantic_json_schema__(
-        _core_schema: Bound<PyAny>,
-        _handler: Bound<PyAny>,
+        core_schema: Bound<PyAny>,
+        handler: Bound<PyAny>,
         py: Python,
     ) -> PyResult<PyObject> {
-        let net = PyDict::new_bound(py);
-        net.set_item("title", "Net amount")?;
-        net.set_item("type", "string")?;
-        net.set_item("example", "123.123456789012")?;
-
-        let tax = PyDict::new_bound(py);
-        tax.set_item("title", "Tax amount")?;
-        tax.set_item("type", "string")?;
-        tax.set_item("example", "123.123456789012")?;
-
-        let properties = PyDict::new_bound(py);
-        properties.set_item("net", net)?;
-        properties.set_item("tax", tax)?;
-
-        let dict = PyDict::new_bound(py);
-        dict.set_item("properties", properties)?;
-        dict.set_item("type", "object")?;
-
-        Ok(dict.into())
+        // The net and tax properties reference the shared Money definition
+        let json_schema = handler.call1((core_schema,))?;
+        let json_schema = handler.call_method1("resolve_ref_schema", (json_schema,))?;
+
+        let properties = json_schema.get_item("properties")?;
+        properties
+            .get_item("net")?
+            .set_item("title", "Net amount")?;
+        properties
+            .get_item("tax")?
+            .set_item("title", "Tax amount")?;
+
+        let example = PyDict::new_bound(py);
+        example.set_item("net", "123.123456789012")?;
+        example.set_item("tax", "23.456789012345")?;
+        json_schema.set_item("example", example)?;
+
+        Ok(json_schema.into())
     }
 
     #[staticmethod]
     fn __get_pydantic_core_schema__(
         _source: Bound<PyAny>,
-        _handler: Bound<PyAny>,
+        handler: Bound<PyAny>,
         py: Python,
     ) -> PyResult<PyObject> {
-        // Define validation function
+        // Define validation function, wrapping the fields schema without calling it
         let validate_fn = PyCFunction::new_closure_bound(
             py,
             None,
             None,
             |args: &Bound<PyTuple>, _kwargs: Option<&Bound<PyDict>>| -> PyResult<Self> {
-                Self::validate(args.get_item(0).unwrap(), None)
+                Self::validate(args.get_item(0).unwrap(), None, None)
             },
         )?;
 
+        // Define the fields schema (only used for the JSON schema)
+        let money_schema =
+            handler.call_method1("generate_schema", (py.get_type_bound::<Money>(),))?;
+        let field = PyDict::new_bound(py);
+        field.set_item("type", "typed-dict-field")?;
+        field.set_item("schema", money_schema)?;
+
+        let fields = PyDict::new_bound(py);
+        fields.set_item("net", &field)?;
+        fields.set_item("tax", &field)?;
+
+        let fields_schema = PyDict::new_bound(py);
+        fields_schema.set_item("type", "typed-dict")?;
+        fields_schema.set_item("fields", fields)?;
+
         // Define serialization function
         let serialize_fn = PyCFunction::new_closure_bound(
             py,
             None,
             None,
             |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
-                if let Ok(money_with_vat) = args.get_item(0)?.extract::<Self>() {
-                    return money_with_vat.for_json();
+                let py = args.py();
+                let value = args.get_item(0)?;
+                if let Ok(money_with_vat) = value.extract::<Self>() {
+                    return match pydantic_serialization_mode(args)? {
+                        Some(true) => money_with_vat.for_json(false),
+                        Some(false) => {
+                            let dict = PyDict::new_bound(py);
+                            dict.set_item("net", money_with_vat.net.amount)?;
+                            dict.set_item("tax", money_with_vat.tax.amount)?;
+                            if let Some(currency) = money_with_vat.get_currency() {
+                                dict.set_item("currency", currency.get_code())?;
+                            }
+                            Ok(dict.into())
+                        }
+                        None => Ok(value.unbind()),
+                    };
                 }
 
-                Err(PyValueError::new_err("Validation error"))
+                Err(InvalidAmountError::new_err(format!(
+                    "Validation error: can't serialize {} as MoneyWithVAT",
+                    value.repr()?
+                )))
             },
         )?;
 
@@ -619,19 +1463,20 @@ impl MoneyWithVAT {
         function.set_item("type", "with-info")?;
         function.set_item("function", validate_fn)?;
 
-        let serialization = PyDict::new_bound(py);
-        serialization.set_item("type", "function-plain")?;
-        serialization.set_item("when_used", "json")?;
-        serialization.set_item("function", serialize_fn)?;
+        let serialization = pydantic_serialization_schema(py, serialize_fn)?;
 
         let schema = PyDict::new_bound(py);
-        schema.set_item("type", "function-plain")?;
+        schema.set_item("type", "function-wrap")?;
         schema.set_item("function", function)?;
+        schema.set_item("schema", fields_schema)?;
         schema.set_item("serialization", serialization)?;
+        schema.set_item("ref", "alasco_money.MoneyWithVAT")?;
 
         Ok(schema.into())
     }
 
+    /// Reads the nested `amount_with_vat` structure (net and gross) as well as the flat
+    /// shape of `for_json`, rejecting a `schema_version` this library doesn't know.
     #[staticmethod]
     #[pyo3(signature = (dict=None))]
     fn from_json(dict: Option<Bound<PyAny>>) -> PyResult<Self> {
@@ -653,14 +1498,103 @@ impl MoneyWithVAT {
         self.clone()
     }
 
+    /// Pickles like Money, as the exact net and tax strings and the currency code.
+    fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> (Bound<'py, PyType>, (String, String, Option<&'static str>)) {
+        let value = slf.get();
+        (
+            slf.get_type(),
+            (
+                value.net.amount.to_string(),
+                value.tax.amount.to_string(),
+                value.get_currency().map(|currency| currency.get_code()),
+            ),
+        )
+    }
+
+    /// `MoneyWithVAT["EUR"]` evaluates to `Annotated[MoneyWithVAT, CurrencyConstraint]`,
+    /// which pydantic fields use to reject other currencies.
+    #[classmethod]
+    fn __class_getitem__(cls: &Bound<PyType>, currency: &str) -> PyResult<PyObject> {
+        currency_annotated(cls, currency)
+    }
+
+    /// Supports `copy.replace(money_with_vat, net=..., tax=..., currency=...)`.
+    #[pyo3(signature = (*, net=None, tax=None, currency=None))]
+    fn __replace__(
+        &self,
+        net: Option<Bound<PyAny>>,
+        tax: Option<Bound<PyAny>>,
+        currency: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        Ok(Self::in_currency(
+            match net {
+                Some(net) => Money::new(Some(net), None)?.amount,
+                None => self.net.amount,
+            },
+            match tax {
+                Some(tax) => Money::new(Some(tax), None)?.amount,
+                None => self.tax.amount,
+            },
+            match currency {
+                Some(currency) => Some(currency_extract(&currency)?),
+                None => self.get_currency(),
+            },
+        ))
+    }
+
     #[staticmethod]
-    fn german_vat_rates() -> [Decimal; 5] {
+    pub fn german_vat_rates() -> [Decimal; 5] {
         GERMAN_VAT_RATES.map(|n| Decimal::new(n as i64, 2))
     }
 
+    /// Rates matched by `tax_rate_for_display`, or all rates of the given country.
     #[staticmethod]
-    fn known_vat_rates() -> [Decimal; 9] {
-        KNOWN_VAT_RATES.map(|n| Decimal::new(n as i64, 2))
+    #[pyo3(signature = (country=None))]
+    pub fn known_vat_rates(country: Option<Country>) -> Vec<Decimal> {
+        match country {
+            Some(country) => country.vat_rates(),
+            None => KNOWN_VAT_RATE_DECIMALS.to_vec(),
+        }
+    }
+}
+
+// Pulls the value out of an item, either by attribute name or by calling `key`
+fn apply_key<'py>(
+    item: &Bound<'py, PyAny>,
+    key: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    if let Ok(name) = key.downcast::<PyString>() {
+        item.getattr(name)
+    } else {
+        key.call1((item,))
+    }
+}
+
+// Extracts net or tax in `validate`, keeping the full precision and currency of Money and
+// parsing strings (without locale) like the Money constructor
+fn validation_field_extract(
+    value: &Bound<PyAny>,
+    locale: Option<&str>,
+) -> PyResult<Option<(Decimal, Option<Currency>)>> {
+    if let Ok(money) = value.downcast::<Money>() {
+        let money = money.get();
+        Ok(Some((money.amount, money.currency)))
+    } else if locale.is_none() && value.is_instance_of::<PyString>() {
+        Ok(decimal_extract(value.clone())
+            .ok()
+            .map(|amount| (amount, None)))
+    } else {
+        Ok(validation_extract(value, locale)?.map(|amount| (amount, None)))
+    }
+}
+
+// The optional `currency` code of the flat JSON shape
+fn json_currency_extract(dict: &Bound<PyDict>) -> PyResult<Option<Currency>> {
+    match dict.get_item("currency")? {
+        Some(currency) if !currency.is_none() => Ok(Some(currency_extract(&currency)?)),
+        _ => Ok(None),
     }
 }
 
@@ -685,14 +1619,45 @@ fn json_to_money_vat(raw: Option<Bound<PyAny>>) -> PyResult<MoneyWithVAT> {
     };
 
     if let Some(true_dict) = raw {
+        if let Ok(dict) = true_dict.downcast::<PyDict>() {
+            if let Some(version) = dict.get_item("schema_version")? {
+                let supported = version
+                    .extract::<u32>()
+                    .is_ok_and(|version| (1..=JSON_SCHEMA_VERSION).contains(&version));
+                if !supported {
+                    return Err(PyValueError::new_err(format!(
+                        "Unsupported schema_version {}",
+                        version
+                    )));
+                }
+            }
+
+            if !dict.contains("amount_with_vat")? {
+                if let (Some(net), Some(tax)) = (dict.get_item("net")?, dict.get_item("tax")?) {
+                    return match (decimal_extract(net), decimal_extract(tax)) {
+                        (Ok(net), Ok(tax)) => Ok(MoneyWithVAT::in_currency(
+                            net,
+                            tax,
+                            json_currency_extract(dict)?,
+                        )),
+                        _ => Err(PyValueError::new_err("Invalid dict")),
+                    };
+                }
+            }
+        }
+
         let raw_net = dig(&true_dict, "net");
         let raw_gross = dig(&true_dict, "gross");
 
         return match (raw_net, raw_gross) {
             (Ok(net), Ok(gross)) => Ok(MoneyWithVAT {
-                net: Money { amount: net },
+                net: Money {
+                    amount: net,
+                    currency: None,
+                },
                 tax: Money {
                     amount: decimal_add(gross, decimal_neg(net)),
+                    currency: None,
                 },
             }),
             _ => Err(PyValueError::new_err("Invalid dict")),
@@ -1,12 +1,15 @@
-use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::{PyCFunction, PyDict, PyTuple};
+use pyo3::types::{PyCFunction, PyDict, PyIterator, PyTuple, PyType};
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 
 use crate::decimals::*;
+use crate::errors::{division_by_zero, MoneyError};
+use crate::json::{json_dumps, json_loads, JsonAmount, MoneyWithVATRatioRecord};
+use crate::money::{pydantic_serialization_mode, pydantic_serialization_schema};
+use crate::stats::{count, Counter};
 
-#[pyclass]
+#[pyclass(module = "alasco_money")]
 #[derive(Debug, Clone)]
 pub struct MoneyWithVATRatio {
     #[pyo3(get)]
@@ -16,10 +19,27 @@ pub struct MoneyWithVATRatio {
     pub gross_ratio: Decimal,
 }
 
+impl MoneyWithVATRatio {
+    fn json_record(&self) -> MoneyWithVATRatioRecord<String> {
+        MoneyWithVATRatioRecord {
+            net_ratio: self.net_ratio.to_string(),
+            gross_ratio: self.gross_ratio.to_string(),
+        }
+    }
+
+    fn from_json_record(record: MoneyWithVATRatioRecord<JsonAmount>) -> Self {
+        Self {
+            net_ratio: record.net_ratio.0,
+            gross_ratio: record.gross_ratio.0,
+        }
+    }
+}
+
 #[pymethods]
 impl MoneyWithVATRatio {
     #[new]
     fn new(net_ratio: Bound<PyAny>, gross_ratio: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Construction);
         let net_ratio_result = decimal_extract(net_ratio);
         let gross_ratio_result = decimal_extract(gross_ratio);
 
@@ -44,7 +64,17 @@ impl MoneyWithVATRatio {
         )
     }
 
+    /// Pickles as the exact ratio strings.
+    fn __reduce__<'py>(slf: &Bound<'py, Self>) -> PyResult<(Bound<'py, PyType>, (String, String))> {
+        let ratio = slf.try_borrow()?;
+        Ok((
+            slf.get_type(),
+            (ratio.net_ratio.to_string(), ratio.gross_ratio.to_string()),
+        ))
+    }
+
     fn __neg__(&self) -> Self {
+        count(Counter::Operation);
         Self {
             net_ratio: decimal_neg(self.net_ratio),
             gross_ratio: decimal_neg(self.gross_ratio),
@@ -52,6 +82,7 @@ impl MoneyWithVATRatio {
     }
 
     fn __add__(&self, other: Self) -> Self {
+        count(Counter::Operation);
         Self {
             net_ratio: decimal_add(self.net_ratio, other.net_ratio),
             gross_ratio: decimal_add(self.gross_ratio, other.gross_ratio),
@@ -59,6 +90,7 @@ impl MoneyWithVATRatio {
     }
 
     fn __sub__(&self, other: Self) -> Self {
+        count(Counter::Operation);
         Self {
             net_ratio: decimal_add(self.net_ratio, decimal_neg(other.net_ratio)),
             gross_ratio: decimal_add(self.gross_ratio, decimal_neg(other.gross_ratio)),
@@ -66,6 +98,7 @@ impl MoneyWithVATRatio {
     }
 
     fn __mul__(&self, other: f64) -> Self {
+        count(Counter::Operation);
         let other_decimal = Decimal::from_f64(other).unwrap();
 
         Self {
@@ -75,11 +108,10 @@ impl MoneyWithVATRatio {
     }
 
     fn __truediv__(&self, other: Bound<PyAny>) -> PyResult<Self> {
+        count(Counter::Operation);
         if let Ok(other_decimal) = decimal_extract(other) {
             if other_decimal == Decimal::new(0, 0) {
-                Err(pyo3::exceptions::PyZeroDivisionError::new_err(
-                    "Division by zero",
-                ))
+                Err(division_by_zero())
             } else {
                 Ok(Self {
                     net_ratio: decimal_div(self.net_ratio, other_decimal),
@@ -97,7 +129,7 @@ impl MoneyWithVATRatio {
         self.net_ratio == other.net_ratio && self.gross_ratio == other.gross_ratio
     }
 
-    fn for_json(&self) -> PyResult<PyObject> {
+    pub fn for_json(&self) -> PyResult<PyObject> {
         Python::with_gil(|py| {
             let dict = PyDict::new_bound(py);
             dict.set_item("net_ratio", self.net_ratio.to_string())?;
@@ -106,6 +138,41 @@ impl MoneyWithVATRatio {
         })
     }
 
+    /// The JSON object of `for_json` serialized in Rust.
+    fn to_json_str(&self) -> String {
+        json_dumps(&self.json_record())
+    }
+
+    /// JSON array of `to_json_str` objects, None values become null.
+    #[staticmethod]
+    fn to_json_str_batch(elems: Bound<PyAny>) -> PyResult<String> {
+        let iterator = PyIterator::from_bound_object(&elems)?;
+        let mut result = Vec::new();
+
+        for item in iterator {
+            let ratio = item?.extract::<Option<Self>>()?;
+            result.push(ratio.map(|value| value.json_record()));
+        }
+
+        Ok(json_dumps(&result))
+    }
+
+    /// Reads a `{"net_ratio": ..., "gross_ratio": ...}` JSON object like `for_json` writes.
+    #[staticmethod]
+    fn from_json_str(text: &str) -> PyResult<Self> {
+        Ok(Self::from_json_record(json_loads(text)?))
+    }
+
+    /// Reads a JSON array of objects like `to_json_str_batch` writes, null becomes None.
+    #[staticmethod]
+    fn from_json_str_batch(text: &str) -> PyResult<Vec<Option<Self>>> {
+        let records: Vec<Option<MoneyWithVATRatioRecord<JsonAmount>>> = json_loads(text)?;
+        Ok(records
+            .into_iter()
+            .map(|record| record.map(Self::from_json_record))
+            .collect())
+    }
+
     #[staticmethod]
     fn zero() -> Self {
         Self {
@@ -116,7 +183,7 @@ impl MoneyWithVATRatio {
 
     #[staticmethod]
     #[pyo3(signature = (value, _info=None))]
-    fn validate(value: Bound<PyAny>, _info: Option<Bound<PyAny>>) -> PyResult<Self> {
+    pub fn validate(value: Bound<PyAny>, _info: Option<Bound<PyAny>>) -> PyResult<Self> {
         if let Ok(money_with_vat_ratio) = value.extract::<Self>() {
             return Ok(money_with_vat_ratio);
         } else if let Ok(dict) = value.extract::<Bound<PyDict>>() {
@@ -134,7 +201,10 @@ impl MoneyWithVATRatio {
             }
         }
 
-        Err(PyValueError::new_err("Validation error"))
+        Err(MoneyError::new_err(format!(
+            "Validation error: can't use {} as MoneyWithVATRatio",
+            value.repr()?
+        )))
     }
 
     #[staticmethod]
@@ -186,11 +256,25 @@ impl MoneyWithVATRatio {
             None,
             None,
             |args: &Bound<PyTuple>, _: Option<&Bound<PyDict>>| -> PyResult<PyObject> {
-                if let Ok(money_with_vat_ratio) = args.get_item(0)?.extract::<Self>() {
-                    return money_with_vat_ratio.for_json();
+                let py = args.py();
+                let value = args.get_item(0)?;
+                if let Ok(money_with_vat_ratio) = value.extract::<Self>() {
+                    return match pydantic_serialization_mode(args)? {
+                        Some(true) => money_with_vat_ratio.for_json(),
+                        Some(false) => {
+                            let dict = PyDict::new_bound(py);
+                            dict.set_item("net_ratio", money_with_vat_ratio.net_ratio)?;
+                            dict.set_item("gross_ratio", money_with_vat_ratio.gross_ratio)?;
+                            Ok(dict.into())
+                        }
+                        None => Ok(value.unbind()),
+                    };
                 }
 
-                Err(PyValueError::new_err("Validation error"))
+                Err(MoneyError::new_err(format!(
+                    "Validation error: can't serialize {} as MoneyWithVATRatio",
+                    value.repr()?
+                )))
             },
         )?;
 
@@ -198,10 +282,7 @@ impl MoneyWithVATRatio {
         function.set_item("type", "with-info")?;
         function.set_item("function", validate_fn)?;
 
-        let serialization = PyDict::new_bound(py);
-        serialization.set_item("type", "function-plain")?;
-        serialization.set_item("when_used", "json")?;
-        serialization.set_item("function", serialize_fn)?;
+        let serialization = pydantic_serialization_schema(py, serialize_fn)?;
 
         let schema = PyDict::new_bound(py);
         schema.set_item("type", "function-plain")?;
@@ -222,4 +303,23 @@ impl MoneyWithVATRatio {
     pub fn __deepcopy__(&self, _memo: Bound<PyDict>) -> Self {
         self.clone()
     }
+
+    /// Supports `copy.replace(ratio, net_ratio=..., gross_ratio=...)`.
+    #[pyo3(signature = (*, net_ratio=None, gross_ratio=None))]
+    fn __replace__(
+        &self,
+        net_ratio: Option<Bound<PyAny>>,
+        gross_ratio: Option<Bound<PyAny>>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            net_ratio: match net_ratio {
+                Some(net_ratio) => decimal_extract(net_ratio)?,
+                None => self.net_ratio,
+            },
+            gross_ratio: match gross_ratio {
+                Some(gross_ratio) => decimal_extract(gross_ratio)?,
+                None => self.gross_ratio,
+            },
+        })
+    }
 }
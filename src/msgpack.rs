@@ -0,0 +1,58 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::currency::Currency;
+
+// Amounts are written as exact decimal strings (keeping the scale), currencies as codes
+#[derive(Serialize, Deserialize)]
+pub struct MoneyMessage {
+    #[serde(with = "exact_amount")]
+    pub amount: Decimal,
+    pub currency: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MoneyWithVATMessage {
+    #[serde(with = "exact_amount")]
+    pub net: Decimal,
+    #[serde(with = "exact_amount")]
+    pub tax: Decimal,
+    pub currency: Option<String>,
+}
+
+// Decimal strings read back with their sign, Decimal's own deserialization turns "-0"
+// into 0
+mod exact_amount {
+    use rust_decimal::Decimal;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(amount: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(amount, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let mut amount = Decimal::from_str(&text).map_err(D::Error::custom)?;
+        if text.starts_with('-') {
+            amount.set_sign_negative(true);
+        }
+        Ok(amount)
+    }
+}
+
+pub fn msgpack_dumps<T: Serialize>(value: &T) -> Vec<u8> {
+    rmp_serde::to_vec_named(value).expect("Messages of decimals and strings are valid msgpack")
+}
+
+pub fn msgpack_loads<'a, T: Deserialize<'a>>(data: &'a [u8]) -> PyResult<T> {
+    rmp_serde::from_slice(data)
+        .map_err(|err| PyValueError::new_err(format!("Invalid msgpack: {}", err)))
+}
+
+pub fn message_currency(code: Option<String>) -> PyResult<Option<Currency>> {
+    code.as_deref().map(Currency::from_code).transpose()
+}
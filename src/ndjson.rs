@@ -0,0 +1,104 @@
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use rust_decimal::Decimal;
+
+use crate::currency::{combined_currency, Currency};
+use crate::decimals::decimal_add;
+use crate::errors::CurrencyMismatchError;
+use crate::json::{JsonAmount, MoneyWithVATRecord};
+use crate::money_vat::MoneyWithVAT;
+
+// Bytes requested per `read()` call on file objects
+const CHUNK_SIZE: usize = 1 << 20;
+
+#[derive(Default)]
+struct Totals {
+    net: Decimal,
+    tax: Decimal,
+    currency: Option<Currency>,
+    count: usize,
+    lines: usize,
+}
+
+impl Totals {
+    fn add_line(&mut self, line: &[u8]) -> PyResult<()> {
+        self.lines += 1;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(());
+        }
+
+        let record: MoneyWithVATRecord<JsonAmount> =
+            serde_json::from_slice(line).map_err(|err| {
+                PyValueError::new_err(format!("Invalid record on line {}: {}", self.lines, err))
+            })?;
+        let currency = record
+            .currency
+            .as_deref()
+            .map(Currency::from_code)
+            .transpose()
+            .map_err(|err| {
+                PyValueError::new_err(format!("Invalid record on line {}: {}", self.lines, err))
+            })?;
+        self.currency = combined_currency(self.currency, currency).map_err(|err| {
+            CurrencyMismatchError::new_err(format!("Record on line {}: {}", self.lines, err))
+        })?;
+        self.net = decimal_add(self.net, record.net.0);
+        self.tax = decimal_add(self.tax, record.tax.0);
+        self.count += 1;
+        Ok(())
+    }
+
+    // Adds all complete lines and returns the number of bytes consumed
+    fn add_complete_lines(&mut self, data: &[u8]) -> PyResult<usize> {
+        let mut consumed = 0;
+        while let Some(end) = data[consumed..].iter().position(|byte| *byte == b'\n') {
+            self.add_line(&data[consumed..consumed + end])?;
+            consumed += end + 1;
+        }
+        Ok(consumed)
+    }
+}
+
+#[pyfunction]
+/// Sums newline-delimited JSON records of MoneyWithVAT (as written by `for_json`) from
+/// bytes, a string or a file object opened in binary or text mode, reading it in chunks.
+/// Blank lines are skipped and records may name a `currency`, which all of them have to
+/// agree on. Returns the sum and the number of records.
+pub fn sum_ndjson(source: Bound<PyAny>) -> PyResult<(MoneyWithVAT, usize)> {
+    let mut totals = Totals::default();
+
+    if let Ok(bytes) = source.downcast::<PyBytes>() {
+        let data = bytes.as_bytes();
+        let consumed = totals.add_complete_lines(data)?;
+        totals.add_line(&data[consumed..])?;
+    } else if let Ok(string) = source.downcast::<PyString>() {
+        let data = string.to_str()?.as_bytes();
+        let consumed = totals.add_complete_lines(data)?;
+        totals.add_line(&data[consumed..])?;
+    } else if source.hasattr("read")? {
+        let mut buffer = Vec::new();
+        loop {
+            let chunk = source.call_method1("read", (CHUNK_SIZE,))?;
+            if let Ok(bytes) = chunk.downcast::<PyBytes>() {
+                buffer.extend_from_slice(bytes.as_bytes());
+            } else {
+                buffer.extend_from_slice(chunk.downcast::<PyString>()?.to_str()?.as_bytes());
+            }
+            if chunk.len()? == 0 {
+                break;
+            }
+
+            let consumed = totals.add_complete_lines(&buffer)?;
+            buffer.drain(..consumed);
+        }
+        totals.add_line(&buffer)?;
+    } else {
+        return Err(PyTypeError::new_err("Unsupported source"));
+    }
+
+    Ok((
+        MoneyWithVAT::in_currency(totals.net, totals.tax, totals.currency),
+        totals.count,
+    ))
+}
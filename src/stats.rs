@@ -0,0 +1,72 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Whether the counters below are updated, off by default to keep the hot paths lean
+static STATS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static CONSTRUCTIONS: AtomicU64 = AtomicU64::new(0);
+static OPERATIONS: AtomicU64 = AtomicU64::new(0);
+static ROUNDINGS: AtomicU64 = AtomicU64::new(0);
+
+pub enum Counter {
+    /// Constructor calls of Money, MoneyWithVAT and MoneyWithVATRatio
+    Construction,
+    /// Arithmetic operator calls (`+`, `-`, `*`, `/`, `%`, unary operators)
+    Operation,
+    /// Decimal roundings, e.g. by `round()` or `rounded_to_cents()`
+    Rounding,
+}
+
+#[inline]
+pub fn count(counter: Counter) {
+    if STATS_ENABLED.load(Ordering::Relaxed) {
+        let atomic = match counter {
+            Counter::Construction => &CONSTRUCTIONS,
+            Counter::Operation => &OPERATIONS,
+            Counter::Rounding => &ROUNDINGS,
+        };
+        atomic.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn stats_enabled() -> bool {
+    STATS_ENABLED.load(Ordering::Relaxed)
+}
+
+#[pyfunction]
+/// Enables ("enabled") or disables ("disabled", default) counting constructions,
+/// operator calls and roundings, see `get_stats()`.
+pub fn set_stats(mode: &str) -> PyResult<()> {
+    match mode {
+        "enabled" => STATS_ENABLED.store(true, Ordering::Relaxed),
+        "disabled" => STATS_ENABLED.store(false, Ordering::Relaxed),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown stats mode '{}'",
+                mode
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+#[pyfunction]
+/// Returns the counters collected since the last `reset_stats()` while stats were enabled.
+pub fn get_stats(py: Python) -> PyResult<Bound<PyDict>> {
+    let stats = PyDict::new_bound(py);
+    stats.set_item("constructions", CONSTRUCTIONS.load(Ordering::Relaxed))?;
+    stats.set_item("operations", OPERATIONS.load(Ordering::Relaxed))?;
+    stats.set_item("roundings", ROUNDINGS.load(Ordering::Relaxed))?;
+    Ok(stats)
+}
+
+#[pyfunction]
+/// Sets all counters back to zero.
+pub fn reset_stats() {
+    CONSTRUCTIONS.store(0, Ordering::Relaxed);
+    OPERATIONS.store(0, Ordering::Relaxed);
+    ROUNDINGS.store(0, Ordering::Relaxed);
+}
@@ -0,0 +1,75 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_decimal::Decimal;
+
+use crate::currency::Currency;
+use crate::decimals::*;
+use crate::money_vat::MoneyWithVAT;
+
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct TaxBreakdown {
+    /// Sum of all buckets
+    #[pyo3(get)]
+    pub total: MoneyWithVAT,
+
+    /// (rate, sum) per `tax_rate_for_display` in order of first appearance
+    pub buckets: Vec<(Decimal, MoneyWithVAT)>,
+}
+
+impl TaxBreakdown {
+    // Builds the breakdown from summed (rate, net, tax) buckets
+    pub fn from_sums(sums: Vec<(Decimal, Decimal, Decimal)>, currency: Option<Currency>) -> Self {
+        let (net, tax) = sums.iter().fold(
+            (Decimal::new(0, 0), Decimal::new(0, 0)),
+            |(net, tax), (_, other_net, other_tax)| {
+                (decimal_add(net, *other_net), decimal_add(tax, *other_tax))
+            },
+        );
+
+        Self {
+            total: MoneyWithVAT::in_currency(net, tax, currency),
+            buckets: sums
+                .into_iter()
+                .map(|(rate, net, tax)| (rate, MoneyWithVAT::in_currency(net, tax, currency)))
+                .collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl TaxBreakdown {
+    /// `{rate: MoneyWithVAT}` with the sum per rate
+    #[getter(buckets)]
+    fn get_buckets(&self, py: Python) -> PyResult<PyObject> {
+        let dict = PyDict::new_bound(py);
+        for (rate, bucket) in &self.buckets {
+            dict.set_item(*rate, bucket.clone().into_py(py))?;
+        }
+
+        Ok(dict.into())
+    }
+
+    #[getter(rates)]
+    fn get_rates(&self) -> Vec<Decimal> {
+        self.buckets.iter().map(|(rate, _)| *rate).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.buckets.len()
+    }
+
+    fn __repr__(&self) -> String {
+        let rates: Vec<String> = self
+            .buckets
+            .iter()
+            .map(|(rate, _)| format!("'{}'", rate))
+            .collect();
+        format!(
+            "TaxBreakdown(net='{}', tax='{}', rates=[{}])",
+            self.total.net.amount,
+            self.total.tax.amount,
+            rates.join(", ")
+        )
+    }
+}